@@ -1,5 +1,11 @@
 use {
-    crate::{domain::fee::FeeFactor, infra},
+    crate::{
+        domain::{
+            eth,
+            fee::{FeeFactor, VolumeTier, VolumeTiers},
+        },
+        infra,
+    },
     anyhow::{anyhow, ensure, Context},
     clap::ValueEnum,
     primitive_types::{H160, U256},
@@ -14,6 +20,7 @@ use {
         fmt::{Display, Formatter},
         net::SocketAddr,
         num::NonZeroUsize,
+        path::PathBuf,
         str::FromStr,
         time::Duration,
     },
@@ -197,6 +204,16 @@ pub struct Arguments {
     #[clap(long, env, default_value = "0.01")]
     pub fee_policy_max_partner_fee: FeeFactor,
 
+    /// JSON file with market specific fee policies, which take precedence over
+    /// `fee_policies`. Rules are matched on the order's tokens, owner, app
+    /// code and USD notional. The file is reloaded whenever it changes.
+    #[clap(long, env)]
+    pub fee_policies_file: Option<PathBuf>,
+
+    /// How often the fee policies file is checked for changes.
+    #[clap(long, env, default_value = "30s", value_parser = humantime::parse_duration)]
+    pub fee_policies_reload_interval: Duration,
+
     /// Arguments for uploading information to S3.
     #[clap(flatten)]
     pub s3: infra::persistence::cli::S3,
@@ -211,6 +228,37 @@ pub struct Arguments {
     #[clap(long, env, default_value = "30d", value_parser = humantime::parse_duration)]
     pub order_events_cleanup_threshold: Duration,
 
+    /// Maximum payout in ETH a solver receives for winning a single auction.
+    #[clap(long, env, default_value = "0.012", value_parser = shared::arguments::wei_from_ether)]
+    pub solver_reward_cap: U256,
+
+    /// Maximum penalty in ETH a solver receives for failing to settle a won
+    /// auction in time.
+    #[clap(long, env, default_value = "0.01", value_parser = shared::arguments::wei_from_ether)]
+    pub solver_penalty_cap: U256,
+
+    /// Time interval between each computation of the rewards of the auctions
+    /// whose settlement deadline has passed.
+    #[clap(long, env, default_value = "1m", value_parser = humantime::parse_duration)]
+    pub solver_rewards_update_interval: Duration,
+
+    /// Share of failed settlement attempts (e.g. 0.5) from which on a driver
+    /// gets excluded from auctions for `--solver-suspension-cool-down`. A
+    /// settlement attempt fails if the `/settle` request fails, the settlement
+    /// transaction reverts or the solution doesn't get settled before the
+    /// deadline. Drivers are never suspended if unset.
+    #[clap(long, env, value_parser = failure_rate)]
+    pub solver_suspension_failure_rate: Option<f64>,
+
+    /// Number of most recent settlement attempts of a driver the failure rate
+    /// is computed from.
+    #[clap(long, env, default_value = "20")]
+    pub solver_suspension_window: NonZeroUsize,
+
+    /// For how long a suspended driver is excluded from auctions.
+    #[clap(long, env, default_value = "1h", value_parser = humantime::parse_duration)]
+    pub solver_suspension_cool_down: Duration,
+
     /// Configurations for indexing CoW AMMs. Supplied in the form of:
     /// "<factory1>|<helper1>|<block1>,<factory2>|<helper2>,<block2>"
     /// - factory is contract address emmiting CoW AMM deployment events.
@@ -237,6 +285,11 @@ pub struct Arguments {
     /// to settle their winning orders at the same time.
     pub max_winners_per_auction: usize,
 
+    /// How the winners of an auction are selected among the solutions that
+    /// swap disjoint tokens.
+    #[clap(long, env, default_value = "greedy", value_enum)]
+    pub winner_selection: WinnerSelection,
+
     #[clap(long, env, default_value = "3")]
     /// The maximum allowed number of solutions to be proposed from a single
     /// solver, per auction.
@@ -245,6 +298,22 @@ pub struct Arguments {
     /// Archive node URL used to index CoW AMM
     #[clap(long, env)]
     pub archive_node_url: Option<Url>,
+
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(clap::Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Prints the solver rewards of an accounting period as JSON and exits.
+    Rewards {
+        /// First block of the accounting period.
+        #[clap(long)]
+        from_block: u64,
+        /// First block after the accounting period.
+        #[clap(long)]
+        to_block: u64,
+    },
 }
 
 impl std::fmt::Display for Arguments {
@@ -277,8 +346,16 @@ impl std::fmt::Display for Arguments {
             solve_deadline,
             fee_policies,
             fee_policy_max_partner_fee,
+            fee_policies_file,
+            fee_policies_reload_interval,
             order_events_cleanup_interval,
             order_events_cleanup_threshold,
+            solver_reward_cap,
+            solver_penalty_cap,
+            solver_rewards_update_interval,
+            solver_suspension_failure_rate,
+            solver_suspension_window,
+            solver_suspension_cool_down,
             db_url,
             insert_batch_size,
             native_price_estimation_results_required,
@@ -288,8 +365,10 @@ impl std::fmt::Display for Arguments {
             max_run_loop_delay,
             run_loop_native_price_timeout,
             max_winners_per_auction,
+            winner_selection,
             archive_node_url,
             max_solutions_per_solver,
+            command,
         } = self;
 
         write!(f, "{}", shared)?;
@@ -337,6 +416,16 @@ impl std::fmt::Display for Arguments {
             "fee_policy_max_partner_fee: {:?}",
             fee_policy_max_partner_fee
         )?;
+        display_option(
+            f,
+            "fee_policies_file",
+            &fee_policies_file.as_ref().map(|path| path.display()),
+        )?;
+        writeln!(
+            f,
+            "fee_policies_reload_interval: {:?}",
+            fee_policies_reload_interval
+        )?;
         writeln!(
             f,
             "order_events_cleanup_interval: {:?}",
@@ -347,6 +436,24 @@ impl std::fmt::Display for Arguments {
             "order_events_cleanup_threshold: {:?}",
             order_events_cleanup_threshold
         )?;
+        writeln!(f, "solver_reward_cap: {}", solver_reward_cap)?;
+        writeln!(f, "solver_penalty_cap: {}", solver_penalty_cap)?;
+        writeln!(
+            f,
+            "solver_rewards_update_interval: {:?}",
+            solver_rewards_update_interval
+        )?;
+        display_option(
+            f,
+            "solver_suspension_failure_rate",
+            solver_suspension_failure_rate,
+        )?;
+        writeln!(f, "solver_suspension_window: {}", solver_suspension_window)?;
+        writeln!(
+            f,
+            "solver_suspension_cool_down: {:?}",
+            solver_suspension_cool_down
+        )?;
         writeln!(f, "insert_batch_size: {}", insert_batch_size)?;
         writeln!(
             f,
@@ -367,12 +474,14 @@ impl std::fmt::Display for Arguments {
             run_loop_native_price_timeout
         )?;
         writeln!(f, "max_winners_per_auction: {:?}", max_winners_per_auction)?;
+        writeln!(f, "winner_selection: {:?}", winner_selection)?;
         writeln!(f, "archive_node_url: {:?}", archive_node_url)?;
         writeln!(
             f,
             "max_solutions_per_solver: {:?}",
             max_solutions_per_solver
         )?;
+        writeln!(f, "command: {:?}", command)?;
         Ok(())
     }
 }
@@ -461,6 +570,9 @@ impl FromStr for Solver {
 ///   price_improvement:0.5:0.06:limit
 ///
 /// - Volume based fee for any order class: volume:0.1:any
+///
+/// - Tiered volume based fee, where orders with a notional of at least 10 ETH
+///   pay a lower fee: tieredVolume:0=0.0003,10=0.0001:any
 #[derive(Debug, Clone)]
 pub struct FeePolicy {
     pub fee_policy_kind: FeePolicyKind,
@@ -483,6 +595,9 @@ pub enum FeePolicyKind {
     },
     /// How much of the order's volume should be taken as a protocol fee.
     Volume { factor: FeeFactor },
+    /// How much of the order's volume should be taken as a protocol fee,
+    /// depending on the order's notional in native token.
+    TieredVolume { tiers: VolumeTiers },
 }
 
 #[derive(clap::Parser, clap::ValueEnum, Clone, Debug)]
@@ -495,6 +610,16 @@ pub enum FeePolicyOrderClass {
     Any,
 }
 
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum WinnerSelection {
+    /// Select winners one by one, starting from the best solution, skipping
+    /// solutions that swap tokens of any better solution.
+    Greedy,
+    /// Select the set of solutions with disjoint tokens that maximises the
+    /// total score, using a bounded search.
+    Combinatorial,
+}
+
 impl FromStr for FeePolicy {
     type Err = anyhow::Error;
 
@@ -546,6 +671,14 @@ impl FromStr for FeePolicy {
                     factor: factor.try_into()?,
                 })
             }
+            "tieredVolume" => {
+                let tiers = parts
+                    .next()
+                    .context("missing volume tiers")?
+                    .parse::<VolumeTiers>()
+                    .map_err(|e| anyhow::anyhow!("invalid volume tiers: {}", e))?;
+                Ok(FeePolicyKind::TieredVolume { tiers })
+            }
             _ => Err(anyhow::anyhow!("invalid fee policy kind: {}", kind)),
         }?;
         let fee_policy_order_class = FeePolicyOrderClass::from_str(
@@ -561,6 +694,38 @@ impl FromStr for FeePolicy {
     }
 }
 
+/// Parses a failure rate, which has to be in the range (0, 1].
+fn failure_rate(s: &str) -> anyhow::Result<f64> {
+    let rate = s.parse::<f64>()?;
+    anyhow::ensure!(
+        rate > 0. && rate <= 1.,
+        "failure rate must be in the range (0, 1]"
+    );
+    Ok(rate)
+}
+
+/// Parses a comma separated list of `<min notional in ETH>=<factor>` tiers,
+/// e.g. `0=0.0003,10=0.0002,100=0.0001`.
+impl FromStr for VolumeTiers {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tiers = s
+            .split(',')
+            .map(|tier| {
+                let (min_notional, factor) = tier
+                    .split_once('=')
+                    .context("volume tier must be formatted as <min notional>=<factor>")?;
+                Ok(VolumeTier {
+                    min_notional: eth::Ether(shared::arguments::wei_from_ether(min_notional)?),
+                    factor: factor.parse()?,
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        VolumeTiers::try_new(tiers)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CowAmmConfig {
     /// Which contract to index for CoW AMM deployment events.
@@ -632,6 +797,50 @@ mod test {
         }
     }
 
+    #[test]
+    fn parse_tiered_volume_fee_policy() {
+        let policy =
+            FeePolicy::from_str("tieredVolume:0=0.0003,10=0.0002,100.5=0.0001:any").unwrap();
+        let FeePolicyKind::TieredVolume { tiers } = policy.fee_policy_kind else {
+            panic!("unexpected fee policy kind");
+        };
+        let tiers = tiers
+            .tiers()
+            .iter()
+            .map(|tier| (tier.min_notional.0, f64::from(tier.factor)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tiers,
+            vec![
+                (U256::zero(), 0.0003),
+                (U256::exp10(19), 0.0002),
+                (U256::exp10(17) * 1005, 0.0001),
+            ]
+        );
+
+        for policy in [
+            // First tier doesn't start at zero.
+            "tieredVolume:1=0.0003:any",
+            // Notionals are not increasing.
+            "tieredVolume:0=0.0003,10=0.0002,10=0.0001:any",
+            // Invalid factor.
+            "tieredVolume:0=1.5:any",
+            // Missing factor.
+            "tieredVolume:0:any",
+        ] {
+            assert!(FeePolicy::from_str(policy).is_err());
+        }
+    }
+
+    #[test]
+    fn parse_failure_rate() {
+        assert_eq!(failure_rate("0.5").unwrap(), 0.5);
+        assert_eq!(failure_rate("1").unwrap(), 1.);
+        for rate in ["0", "-0.5", "1.5", "NaN", "rate"] {
+            assert!(failure_rate(rate).is_err());
+        }
+    }
+
     #[test]
     fn parse_driver_submission_account_address() {
         let argument = "name1|http://localhost:8080|0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
@@ -6,6 +6,7 @@ use {
 };
 
 mod participant;
+pub mod winner_selection;
 
 pub use participant::{Participant, Ranked, Unranked};
 
@@ -0,0 +1,365 @@
+//! Selection of the winning solutions of an auction.
+//!
+//! Solutions that swap overlapping tokens can't be settled at the same time,
+//! so at most one of them can win. How the winners are chosen among the
+//! candidates depends on the configured [`Mode`].
+
+use {
+    super::Solution,
+    crate::{arguments, domain::eth},
+    std::collections::HashSet,
+};
+
+/// Upper bound on the number of nodes the combinatorial search explores per
+/// auction. When exceeded, the best selection found so far is used, which is
+/// never worse than the greedy selection.
+const MAX_EXPLORED_NODES: usize = 100_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Winners are selected one by one, starting from the best solution. A
+    /// solution wins if it swaps tokens that are not yet swapped by any
+    /// previously processed solution (winning or not).
+    Greedy,
+    /// Selects the set of solutions with pairwise disjoint tokens that
+    /// maximises the total score.
+    Combinatorial,
+}
+
+impl From<arguments::WinnerSelection> for Mode {
+    fn from(value: arguments::WinnerSelection) -> Self {
+        match value {
+            arguments::WinnerSelection::Greedy => Self::Greedy,
+            arguments::WinnerSelection::Combinatorial => Self::Combinatorial,
+        }
+    }
+}
+
+/// A solution competing for a win.
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub score: eth::U256,
+    pub tokens: HashSet<eth::TokenAddress>,
+}
+
+impl Candidate {
+    pub fn new(solution: &Solution, wrapped_native_token: eth::WrappedNativeToken) -> Self {
+        Self {
+            score: solution.score().get().0,
+            tokens: solution
+                .orders()
+                .values()
+                .flat_map(|order| {
+                    [
+                        order.sell.token.as_erc20(wrapped_native_token),
+                        order.buy.token.as_erc20(wrapped_native_token),
+                    ]
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Selects at most `max_winners` winners among the candidates, which are
+/// expected to be sorted by score (best to worst). Returns whether the
+/// candidate at the respective index is a winner.
+pub fn select(mode: Mode, candidates: &[Candidate], max_winners: usize) -> Vec<bool> {
+    match mode {
+        Mode::Greedy => greedy(candidates, max_winners),
+        Mode::Combinatorial => combinatorial(candidates, max_winners),
+    }
+}
+
+/// Returns the reference score of every winner, i.e. the score the winner has
+/// to beat to be rewarded. The difference between the two is the winner's
+/// contribution to the auction: how much the total score of the winners
+/// exceeds the total score of the best selection without the winner's
+/// solution. With a single winner, this is the best score of the other
+/// solutions. Losers have no reference score.
+pub fn reference_scores(
+    mode: Mode,
+    candidates: &[Candidate],
+    max_winners: usize,
+    winners: &[bool],
+) -> Vec<Option<eth::U256>> {
+    let total = |candidates: &[Candidate], winners: &[bool]| {
+        candidates
+            .iter()
+            .zip(winners)
+            .filter(|(_, is_winner)| **is_winner)
+            .fold(eth::U256::zero(), |total, (candidate, _)| {
+                total.saturating_add(candidate.score)
+            })
+    };
+    let best = total(candidates, winners);
+
+    winners
+        .iter()
+        .enumerate()
+        .map(|(winner, is_winner)| {
+            is_winner.then(|| {
+                let others = candidates
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| *i != winner)
+                    .map(|(_, candidate)| candidate.clone())
+                    .collect::<Vec<_>>();
+                let without = total(&others, &select(mode, &others, max_winners));
+                candidates[winner]
+                    .score
+                    .saturating_sub(best.saturating_sub(without))
+            })
+        })
+        .collect()
+}
+
+fn greedy(candidates: &[Candidate], max_winners: usize) -> Vec<bool> {
+    let mut already_swapped_tokens = HashSet::new();
+    let mut winners = 0;
+    candidates
+        .iter()
+        .map(|candidate| {
+            let is_winner =
+                candidate.tokens.is_disjoint(&already_swapped_tokens) && winners < max_winners;
+            already_swapped_tokens.extend(candidate.tokens.iter().copied());
+            winners += usize::from(is_winner);
+            is_winner
+        })
+        .collect()
+}
+
+/// Branch and bound search over all sets of candidates with disjoint tokens.
+/// Including a candidate is explored before excluding it, so the first
+/// complete selection is a greedy one and ties are broken in favour of better
+/// ranked candidates.
+fn combinatorial(candidates: &[Candidate], max_winners: usize) -> Vec<bool> {
+    let mut order = (0..candidates.len()).collect::<Vec<_>>();
+    order.sort_by(|a, b| candidates[*b].score.cmp(&candidates[*a].score));
+
+    let mut search = Search {
+        candidates,
+        order: &order,
+        max_winners,
+        selected: Vec::new(),
+        used_tokens: HashSet::new(),
+        best: (eth::U256::zero(), Vec::new()),
+        explored: 0,
+    };
+    search.explore(0, eth::U256::zero());
+    if search.explored > MAX_EXPLORED_NODES {
+        tracing::debug!(
+            candidates = candidates.len(),
+            "winner selection search exceeded its budget"
+        );
+    }
+
+    let mut winners = vec![false; candidates.len()];
+    for index in search.best.1 {
+        winners[index] = true;
+    }
+    winners
+}
+
+struct Search<'a> {
+    candidates: &'a [Candidate],
+    /// Indices of the candidates sorted by score (best to worst).
+    order: &'a [usize],
+    max_winners: usize,
+    selected: Vec<usize>,
+    used_tokens: HashSet<eth::TokenAddress>,
+    best: (eth::U256, Vec<usize>),
+    explored: usize,
+}
+
+impl Search<'_> {
+    fn explore(&mut self, position: usize, total: eth::U256) {
+        self.explored += 1;
+        if total > self.best.0 {
+            self.best = (total, self.selected.clone());
+        }
+        if position == self.order.len()
+            || self.selected.len() == self.max_winners
+            || self.explored > MAX_EXPLORED_NODES
+        {
+            return;
+        }
+
+        // No selection in this branch can be better than adding the best
+        // remaining candidates until the winner limit is reached.
+        let remaining = self.max_winners - self.selected.len();
+        let bound = self.order[position..]
+            .iter()
+            .take(remaining)
+            .fold(total, |bound, index| {
+                bound.saturating_add(self.candidates[*index].score)
+            });
+        if bound <= self.best.0 {
+            return;
+        }
+
+        let index = self.order[position];
+        let candidate = &self.candidates[index];
+        if candidate.tokens.is_disjoint(&self.used_tokens) {
+            self.selected.push(index);
+            self.used_tokens.extend(candidate.tokens.iter().copied());
+            self.explore(position + 1, total.saturating_add(candidate.score));
+            self.selected.pop();
+            for token in &candidate.tokens {
+                self.used_tokens.remove(token);
+            }
+        }
+        self.explore(position + 1, total);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::domain::eth::H160};
+
+    fn candidate(score: u64, tokens: &[u8]) -> Candidate {
+        Candidate {
+            score: score.into(),
+            tokens: tokens
+                .iter()
+                .map(|token| H160::from_low_u64_be(u64::from(*token)).into())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn empty() {
+        assert!(select(Mode::Greedy, &[], 3).is_empty());
+        assert!(select(Mode::Combinatorial, &[], 3).is_empty());
+    }
+
+    #[test]
+    fn best_solution_blocks_two_disjoint_ones() {
+        let candidates = [
+            candidate(10, &[1, 2, 3, 4]),
+            candidate(7, &[1, 2]),
+            candidate(6, &[3, 4]),
+        ];
+        assert_eq!(select(Mode::Greedy, &candidates, 3), [true, false, false]);
+        assert_eq!(
+            select(Mode::Combinatorial, &candidates, 3),
+            [false, true, true]
+        );
+        // With a single winner the best solution still wins.
+        assert_eq!(
+            select(Mode::Combinatorial, &candidates, 1),
+            [true, false, false]
+        );
+    }
+
+    #[test]
+    fn losing_solutions_do_not_block_winners() {
+        let candidates = [
+            candidate(10, &[1, 2]),
+            candidate(9, &[2, 3]),
+            candidate(8, &[3, 4]),
+        ];
+        assert_eq!(select(Mode::Greedy, &candidates, 3), [true, false, false]);
+        assert_eq!(
+            select(Mode::Combinatorial, &candidates, 3),
+            [true, false, true]
+        );
+    }
+
+    #[test]
+    fn ties_prefer_better_ranked_solutions() {
+        let candidates = [
+            candidate(10, &[1, 2, 3, 4]),
+            candidate(5, &[1, 2]),
+            candidate(5, &[3, 4]),
+        ];
+        assert_eq!(
+            select(Mode::Combinatorial, &candidates, 3),
+            [true, false, false]
+        );
+    }
+
+    #[test]
+    fn respects_max_winners() {
+        let candidates = [
+            candidate(4, &[1]),
+            candidate(3, &[2]),
+            candidate(2, &[3]),
+            candidate(1, &[4]),
+        ];
+        assert_eq!(
+            select(Mode::Combinatorial, &candidates, 2),
+            [true, true, false, false]
+        );
+        assert_eq!(
+            select(Mode::Combinatorial, &candidates, 0),
+            [false, false, false, false]
+        );
+    }
+
+    #[test]
+    fn all_solutions_overlap() {
+        let candidates = [
+            candidate(3, &[1, 2]),
+            candidate(2, &[1, 3]),
+            candidate(1, &[1, 4]),
+        ];
+        for mode in [Mode::Greedy, Mode::Combinatorial] {
+            assert_eq!(select(mode, &candidates, 3), [true, false, false]);
+        }
+    }
+
+    #[test]
+    fn reference_score_of_single_winner_is_next_best_score() {
+        let candidates = [
+            candidate(10, &[1, 2]),
+            candidate(7, &[1, 3]),
+            candidate(5, &[2, 4]),
+        ];
+        for mode in [Mode::Greedy, Mode::Combinatorial] {
+            let winners = select(mode, &candidates, 1);
+            assert_eq!(
+                reference_scores(mode, &candidates, 1, &winners),
+                [Some(7.into()), None, None]
+            );
+        }
+    }
+
+    #[test]
+    fn reference_scores_of_multiple_winners() {
+        let candidates = [
+            candidate(10, &[1, 2]),
+            candidate(8, &[3, 4]),
+            candidate(7, &[1, 5]),
+            candidate(2, &[3, 6]),
+        ];
+        let winners = select(Mode::Combinatorial, &candidates, 3);
+        assert_eq!(winners, [true, true, false, false]);
+        // Without the first winner, the others select 8 + 7 instead of 18, so
+        // it contributes 3. Without the second winner, the others select
+        // 10 + 2, so it contributes 6.
+        assert_eq!(
+            reference_scores(Mode::Combinatorial, &candidates, 3, &winners),
+            [Some(7.into()), Some(2.into()), None, None]
+        );
+    }
+
+    #[test]
+    fn bounded_search_is_never_worse_than_greedy() {
+        let candidates = (0..64)
+            .map(|i| candidate(1000 - i, &[(i % 16) as u8, (i % 16 + 1) as u8]))
+            .collect::<Vec<_>>();
+        let total = |winners: Vec<bool>| {
+            candidates
+                .iter()
+                .zip(winners)
+                .filter(|(_, is_winner)| *is_winner)
+                .fold(eth::U256::zero(), |total, (candidate, _)| {
+                    total + candidate.score
+                })
+        };
+        assert!(
+            total(select(Mode::Combinatorial, &candidates, 64))
+                >= total(select(Mode::Greedy, &candidates, 64))
+        );
+    }
+}
@@ -5,6 +5,7 @@
 //! parameters.
 
 mod policy;
+pub mod rules;
 
 use {
     crate::{
@@ -16,7 +17,10 @@ use {
     derive_more::Into,
     primitive_types::{H160, U256},
     prometheus::core::Number,
-    std::{collections::HashSet, str::FromStr},
+    std::{
+        collections::{BTreeMap, HashSet},
+        str::FromStr,
+    },
 };
 
 #[derive(Debug)]
@@ -26,6 +30,18 @@ enum OrderClass {
     Any,
 }
 
+impl OrderClass {
+    /// Whether orders of this class include an order with the given position
+    /// relative to the market price.
+    fn includes(&self, outside_market_price: bool) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Limit => outside_market_price,
+            Self::Market => !outside_market_price,
+        }
+    }
+}
+
 impl From<arguments::FeePolicyOrderClass> for OrderClass {
     fn from(value: arguments::FeePolicyOrderClass) -> Self {
         match value {
@@ -55,6 +71,9 @@ pub type ProtocolFeeExemptAddresses = HashSet<H160>;
 
 pub struct ProtocolFees {
     fee_policies: Vec<ProtocolFee>,
+    /// Market specific fee policies, which take precedence over the default
+    /// fee policies.
+    rules: rules::Reloadable,
     max_partner_fee: FeeFactor,
 }
 
@@ -62,6 +81,7 @@ impl ProtocolFees {
     pub fn new(
         fee_policies: &[arguments::FeePolicy],
         fee_policy_max_partner_fee: FeeFactor,
+        rules: rules::Reloadable,
     ) -> Self {
         Self {
             fee_policies: fee_policies
@@ -69,6 +89,7 @@ impl ProtocolFees {
                 .cloned()
                 .map(ProtocolFee::from)
                 .collect(),
+            rules,
             max_partner_fee: fee_policy_max_partner_fee,
         }
     }
@@ -80,6 +101,7 @@ impl ProtocolFees {
         order: boundary::Order,
         quote: Option<domain::Quote>,
         surplus_capturing_jit_order_owners: &[eth::Address],
+        prices: &BTreeMap<H160, U256>,
     ) -> domain::Order {
         let partner_fee = order
             .metadata
@@ -128,7 +150,7 @@ impl ProtocolFees {
             fee: quote.fee.into(),
         };
 
-        self.apply_policies(order, quote, order_, quote_, partner_fee)
+        self.apply_policies(order, quote, order_, quote_, partner_fee, prices)
     }
 
     fn apply_policies(
@@ -138,9 +160,15 @@ impl ProtocolFees {
         order_: boundary::Amounts,
         quote_: boundary::Amounts,
         partner_fees: Vec<Policy>,
+        prices: &BTreeMap<H160, U256>,
     ) -> domain::Order {
-        let protocol_fees = self
-            .fee_policies
+        let outside_market_price =
+            boundary::is_order_outside_market_price(&order_, &quote_, order.data.kind);
+        let rules = self.rules.current();
+        let fee_policies = rules
+            .find(&order, outside_market_price, prices)
+            .unwrap_or(&self.fee_policies);
+        let protocol_fees = fee_policies
             .iter()
             .filter_map(|fee_policy| {
                 Self::protocol_fee_into_policy(&order, &order_, &quote_, fee_policy)
@@ -160,6 +188,7 @@ impl ProtocolFees {
             policy::Policy::Surplus(variant) => variant.apply(order),
             policy::Policy::PriceImprovement(variant) => variant.apply(order, quote),
             policy::Policy::Volume(variant) => variant.apply(order),
+            policy::Policy::TieredVolume(variant) => variant.apply(order),
         }
     }
 
@@ -171,16 +200,14 @@ impl ProtocolFees {
     ) -> Option<&'a policy::Policy> {
         let outside_market_price =
             boundary::is_order_outside_market_price(order_, quote_, order.data.kind);
-        match (outside_market_price, &protocol_fee.order_class) {
-            (_, OrderClass::Any) => Some(&protocol_fee.policy),
-            (true, OrderClass::Limit) => Some(&protocol_fee.policy),
-            (false, OrderClass::Market) => Some(&protocol_fee.policy),
-            _ => None,
-        }
+        protocol_fee
+            .order_class
+            .includes(outside_market_price)
+            .then_some(&protocol_fee.policy)
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Policy {
     /// If the order receives more than limit price, take the protocol fee as a
     /// percentage of the difference. The fee is taken in `sell` token for
@@ -213,6 +240,10 @@ pub enum Policy {
         /// fee.
         factor: FeeFactor,
     },
+    /// Same as `Volume`, but the factor depends on the order's notional, which
+    /// is the native token value of the executed sell amount for `sell` orders
+    /// and of the executed buy amount for `buy` orders.
+    TieredVolume { tiers: VolumeTiers },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Into)]
@@ -246,6 +277,51 @@ impl FromStr for FeeFactor {
     }
 }
 
+/// Volume fee factors for brackets of order notionals.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VolumeTiers(Vec<VolumeTier>);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VolumeTier {
+    /// The smallest order notional the tier applies to.
+    pub min_notional: eth::Ether,
+    /// Percentage of the order's volume taken as a protocol fee.
+    pub factor: FeeFactor,
+}
+
+impl VolumeTiers {
+    /// Tiers need strictly increasing notionals and the first tier has to
+    /// start at zero, so that every notional falls into exactly one tier.
+    pub fn try_new(tiers: Vec<VolumeTier>) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            tiers
+                .first()
+                .is_some_and(|tier| tier.min_notional == eth::Ether::default()),
+            "the first volume tier must start at a notional of zero"
+        );
+        anyhow::ensure!(
+            tiers
+                .windows(2)
+                .all(|pair| pair[0].min_notional < pair[1].min_notional),
+            "volume tiers must have strictly increasing notionals"
+        );
+        Ok(Self(tiers))
+    }
+
+    pub fn tiers(&self) -> &[VolumeTier] {
+        &self.0
+    }
+
+    /// The fee factor of the tier the notional falls into.
+    pub fn factor(&self, notional: eth::Ether) -> FeeFactor {
+        self.0
+            .iter()
+            .rfind(|tier| tier.min_notional <= notional)
+            .unwrap_or(&self.0[0])
+            .factor
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Quote {
     /// The amount of the sell token.
@@ -267,3 +343,40 @@ impl Quote {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tier(min_notional: u64, factor: f64) -> VolumeTier {
+        VolumeTier {
+            min_notional: eth::Ether(min_notional.into()),
+            factor: factor.try_into().unwrap(),
+        }
+    }
+
+    #[test]
+    fn volume_tiers() {
+        let tiers =
+            VolumeTiers::try_new(vec![tier(0, 0.0003), tier(100, 0.0002), tier(1000, 0.0001)])
+                .unwrap();
+        let factor = |notional: u64| f64::from(tiers.factor(eth::Ether(notional.into())));
+
+        assert_eq!(factor(0), 0.0003);
+        assert_eq!(factor(99), 0.0003);
+        assert_eq!(factor(100), 0.0002);
+        assert_eq!(factor(999), 0.0002);
+        assert_eq!(factor(1000), 0.0001);
+        assert_eq!(factor(u64::MAX), 0.0001);
+    }
+
+    #[test]
+    fn invalid_volume_tiers() {
+        assert!(VolumeTiers::try_new(vec![]).is_err());
+        assert!(VolumeTiers::try_new(vec![tier(1, 0.0003)]).is_err());
+        assert!(VolumeTiers::try_new(vec![tier(0, 0.0003), tier(0, 0.0002)]).is_err());
+        assert!(
+            VolumeTiers::try_new(vec![tier(0, 0.0003), tier(10, 0.0002), tier(5, 0.0001)]).is_err()
+        );
+    }
+}
@@ -3,7 +3,7 @@ use crate::{
     boundary,
     domain::{
         self,
-        fee::{FeeFactor, Quote},
+        fee::{FeeFactor, Quote, VolumeTiers},
     },
 };
 
@@ -11,6 +11,7 @@ pub enum Policy {
     Surplus(Surplus),
     PriceImprovement(PriceImprovement),
     Volume(Volume),
+    TieredVolume(TieredVolume),
 }
 
 pub struct Surplus {
@@ -27,6 +28,10 @@ pub struct Volume {
     factor: FeeFactor,
 }
 
+pub struct TieredVolume {
+    tiers: VolumeTiers,
+}
+
 impl From<arguments::FeePolicyKind> for Policy {
     fn from(policy_arg: arguments::FeePolicyKind) -> Self {
         match policy_arg {
@@ -45,6 +50,9 @@ impl From<arguments::FeePolicyKind> for Policy {
                 max_volume_factor,
             }),
             arguments::FeePolicyKind::Volume { factor } => Policy::Volume(Volume { factor }),
+            arguments::FeePolicyKind::TieredVolume { tiers } => {
+                Policy::TieredVolume(TieredVolume { tiers })
+            }
         }
     }
 }
@@ -94,3 +102,15 @@ impl Volume {
         }
     }
 }
+
+impl TieredVolume {
+    pub fn apply(&self, order: &boundary::Order) -> Option<domain::fee::Policy> {
+        match order.metadata.class {
+            boundary::OrderClass::Market => None,
+            boundary::OrderClass::Liquidity => None,
+            boundary::OrderClass::Limit => Some(domain::fee::Policy::TieredVolume {
+                tiers: self.tiers.clone(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,363 @@
+//! Fee policies that target specific markets. Rules are matched against each
+//! order and the protocol fees of the best matching rule replace the default
+//! fee policies.
+
+use {
+    super::{OrderClass, ProtocolFee},
+    crate::{arguments, boundary},
+    primitive_types::{H160, U256},
+    prometheus::core::Number,
+    std::{
+        collections::{BTreeMap, HashSet},
+        sync::{Arc, RwLock},
+    },
+};
+
+/// A set of rules ordered by priority.
+#[derive(Default)]
+pub struct Rules {
+    rules: Vec<Rule>,
+    usd_reference: Option<UsdReference>,
+}
+
+impl Rules {
+    /// Creates a rule set. Rules with a higher priority are matched first,
+    /// rules with the same priority are matched in the specified order.
+    pub fn new(mut rules: Vec<Rule>, usd_reference: Option<UsdReference>) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            usd_reference.is_some()
+                || rules.iter().all(|rule| {
+                    rule.condition.min_notional_usd.is_none()
+                        && rule.condition.max_notional_usd.is_none()
+                }),
+            "USD notional conditions require a USD reference token"
+        );
+        rules.sort_by_key(|rule| std::cmp::Reverse(rule.priority));
+        Ok(Self {
+            rules,
+            usd_reference,
+        })
+    }
+
+    /// Returns the protocol fees of the highest priority rule matching the
+    /// order, if any. Rules for another order class are skipped so that lower
+    /// priority rules still apply.
+    pub(super) fn find(
+        &self,
+        order: &boundary::Order,
+        outside_market_price: bool,
+        prices: &BTreeMap<H160, U256>,
+    ) -> Option<&[ProtocolFee]> {
+        let notional_usd = || self.notional_usd(order, prices);
+        self.rules
+            .iter()
+            .find(|rule| {
+                rule.order_class.includes(outside_market_price)
+                    && rule.condition.matches(order, &notional_usd)
+            })
+            .map(|rule| rule.fee_policies.as_slice())
+    }
+
+    /// The USD value of the order's sell amount, based on the native prices.
+    fn notional_usd(&self, order: &boundary::Order, prices: &BTreeMap<H160, U256>) -> Option<f64> {
+        let usd = self.usd_reference.as_ref()?;
+        let sell_price = prices.get(&order.data.sell_token)?;
+        let usd_price = prices.get(&usd.token).filter(|price| !price.is_zero())?;
+        let usd_atoms = order.data.sell_amount.to_f64_lossy() * sell_price.to_f64_lossy()
+            / usd_price.to_f64_lossy();
+        Some(usd_atoms / 10f64.powi(usd.decimals.into()))
+    }
+}
+
+/// The token whose native price is used to convert order volumes to USD.
+#[derive(Debug, Clone)]
+pub struct UsdReference {
+    pub token: H160,
+    pub decimals: u8,
+}
+
+pub struct Rule {
+    priority: i64,
+    condition: Condition,
+    order_class: OrderClass,
+    fee_policies: Vec<ProtocolFee>,
+}
+
+impl Rule {
+    pub fn new(
+        priority: i64,
+        condition: Condition,
+        order_class: arguments::FeePolicyOrderClass,
+        policies: Vec<arguments::FeePolicyKind>,
+    ) -> Self {
+        Self {
+            priority,
+            condition,
+            order_class: OrderClass::from(order_class.clone()),
+            fee_policies: policies
+                .into_iter()
+                .map(|policy| ProtocolFee {
+                    policy: policy.into(),
+                    order_class: OrderClass::from(order_class.clone()),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Which orders a rule applies to. Unset fields match every order.
+#[derive(Debug, Clone, Default)]
+pub struct Condition {
+    pub sell_tokens: Option<HashSet<H160>>,
+    pub buy_tokens: Option<HashSet<H160>>,
+    pub owners: Option<HashSet<H160>>,
+    /// The `appCode` of the order's app data.
+    pub app_codes: Option<HashSet<String>>,
+    pub min_notional_usd: Option<f64>,
+    pub max_notional_usd: Option<f64>,
+}
+
+impl Condition {
+    fn matches(&self, order: &boundary::Order, notional_usd: impl Fn() -> Option<f64>) -> bool {
+        let contains = |set: &Option<HashSet<H160>>, value: &H160| {
+            set.as_ref().map_or(true, |set| set.contains(value))
+        };
+        if !contains(&self.sell_tokens, &order.data.sell_token)
+            || !contains(&self.buy_tokens, &order.data.buy_token)
+            || !contains(&self.owners, &order.metadata.owner)
+        {
+            return false;
+        }
+
+        if let Some(app_codes) = &self.app_codes {
+            match app_code(order) {
+                Some(app_code) if app_codes.contains(&app_code) => (),
+                _ => return false,
+            }
+        }
+
+        if self.min_notional_usd.is_some() || self.max_notional_usd.is_some() {
+            let Some(notional_usd) = notional_usd() else {
+                return false;
+            };
+            if self.min_notional_usd.is_some_and(|min| notional_usd < min)
+                || self.max_notional_usd.is_some_and(|max| notional_usd >= max)
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+fn app_code(order: &boundary::Order) -> Option<String> {
+    let full_app_data = order.metadata.full_app_data.as_ref()?;
+    let document = serde_json::from_str::<serde_json::Value>(full_app_data).ok()?;
+    document.get("appCode")?.as_str().map(ToOwned::to_owned)
+}
+
+/// Rules that can be replaced while the autopilot is running.
+#[derive(Clone, Default)]
+pub struct Reloadable(Arc<RwLock<Arc<Rules>>>);
+
+impl Reloadable {
+    pub fn current(&self) -> Arc<Rules> {
+        self.0.read().unwrap().clone()
+    }
+
+    pub fn replace(&self, rules: Rules) {
+        *self.0.write().unwrap() = Arc::new(rules);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, maplit::btreemap};
+
+    fn address(byte: u8) -> H160 {
+        H160([byte; 20])
+    }
+
+    fn order(sell_token: u8, buy_token: u8, app_code: Option<&str>) -> boundary::Order {
+        boundary::Order {
+            data: model::order::OrderData {
+                sell_token: address(sell_token),
+                buy_token: address(buy_token),
+                sell_amount: U256::exp10(18),
+                ..Default::default()
+            },
+            metadata: model::order::OrderMetadata {
+                owner: address(0xaa),
+                full_app_data: app_code.map(|app_code| format!(r#"{{"appCode":"{app_code}"}}"#)),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn rule(priority: i64, condition: Condition) -> Rule {
+        rule_for(priority, condition, arguments::FeePolicyOrderClass::Any)
+    }
+
+    fn rule_for(
+        priority: i64,
+        condition: Condition,
+        order_class: arguments::FeePolicyOrderClass,
+    ) -> Rule {
+        Rule::new(
+            priority,
+            condition,
+            order_class,
+            vec![arguments::FeePolicyKind::Volume {
+                factor: 0.001.try_into().unwrap(),
+            }],
+        )
+    }
+
+    fn matched(
+        rules: &Rules,
+        order: &boundary::Order,
+        prices: &BTreeMap<H160, U256>,
+    ) -> Option<i64> {
+        matched_class(rules, order, false, prices)
+    }
+
+    fn matched_class(
+        rules: &Rules,
+        order: &boundary::Order,
+        outside_market_price: bool,
+        prices: &BTreeMap<H160, U256>,
+    ) -> Option<i64> {
+        let fee_policies = rules.find(order, outside_market_price, prices)?;
+        rules
+            .rules
+            .iter()
+            .find(|rule| std::ptr::eq(rule.fee_policies.as_slice(), fee_policies))
+            .map(|rule| rule.priority)
+    }
+
+    #[test]
+    fn matches_highest_priority_rule() {
+        let rules = Rules::new(
+            vec![
+                rule(1, Condition::default()),
+                rule(
+                    3,
+                    Condition {
+                        sell_tokens: Some(HashSet::from([address(1)])),
+                        buy_tokens: Some(HashSet::from([address(2)])),
+                        ..Default::default()
+                    },
+                ),
+                rule(
+                    2,
+                    Condition {
+                        owners: Some(HashSet::from([address(0xaa)])),
+                        app_codes: Some(HashSet::from(["CoW Swap".to_string()])),
+                        ..Default::default()
+                    },
+                ),
+            ],
+            None,
+        )
+        .unwrap();
+        let prices = BTreeMap::new();
+
+        assert_eq!(matched(&rules, &order(1, 2, None), &prices), Some(3));
+        assert_eq!(matched(&rules, &order(2, 1, None), &prices), Some(1));
+        assert_eq!(
+            matched(&rules, &order(2, 1, Some("CoW Swap")), &prices),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn skips_rules_for_other_order_classes() {
+        let rules = Rules::new(
+            vec![
+                rule(1, Condition::default()),
+                rule_for(
+                    2,
+                    Condition::default(),
+                    arguments::FeePolicyOrderClass::Limit,
+                ),
+                rule_for(
+                    3,
+                    Condition {
+                        sell_tokens: Some(HashSet::from([address(1)])),
+                        ..Default::default()
+                    },
+                    arguments::FeePolicyOrderClass::Market,
+                ),
+            ],
+            None,
+        )
+        .unwrap();
+        let prices = BTreeMap::new();
+
+        assert_eq!(
+            matched_class(&rules, &order(1, 2, None), false, &prices),
+            Some(3)
+        );
+        assert_eq!(
+            matched_class(&rules, &order(1, 2, None), true, &prices),
+            Some(2)
+        );
+        // Market orders fall through the limit order rule.
+        assert_eq!(
+            matched_class(&rules, &order(2, 1, None), false, &prices),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn matches_usd_notional() {
+        let usd = address(0xdd);
+        let rules = Rules::new(
+            vec![rule(
+                0,
+                Condition {
+                    min_notional_usd: Some(1000.),
+                    max_notional_usd: Some(10_000.),
+                    ..Default::default()
+                },
+            )],
+            Some(UsdReference {
+                token: usd,
+                decimals: 6,
+            }),
+        )
+        .unwrap();
+
+        // 1 token is worth 0.5 ETH and 1 ETH is worth 4000 USD.
+        let usd_price = U256::exp10(26) * 5 / 2;
+        let price = |eth_price: U256| btreemap! { address(1) => eth_price, usd => usd_price };
+        let order = order(1, 2, None);
+
+        // 2000 USD
+        assert_eq!(
+            matched(&rules, &order, &price(U256::exp10(17) * 5)),
+            Some(0)
+        );
+        // 200 USD
+        assert_eq!(matched(&rules, &order, &price(U256::exp10(17) / 2)), None);
+        // Missing prices never match.
+        assert_eq!(matched(&rules, &order, &BTreeMap::new()), None);
+    }
+
+    #[test]
+    fn notional_conditions_require_usd_reference() {
+        assert!(Rules::new(
+            vec![rule(
+                0,
+                Condition {
+                    min_notional_usd: Some(1.),
+                    ..Default::default()
+                },
+            )],
+            None,
+        )
+        .is_err());
+    }
+}
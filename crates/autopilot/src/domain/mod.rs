@@ -3,7 +3,9 @@ pub mod competition;
 pub mod eth;
 pub mod fee;
 pub mod quote;
+pub mod rewards;
 pub mod settlement;
+pub mod suspensions;
 
 pub use {
     auction::{
@@ -0,0 +1,181 @@
+//! Solver rewards and penalties as specified by CIP-20.
+//!
+//! The winner of an auction gets paid the difference between the quality
+//! observed on-chain and the reference score, which is the score of the runner
+//! up. A winner that doesn't settle the auction before the deadline observes a
+//! quality of zero and therefore gets penalized by the reference score. Both
+//! payouts and penalties are capped.
+
+use {
+    crate::{
+        domain::{auction, eth},
+        infra,
+    },
+    std::time::Duration,
+};
+
+/// How many auctions get accounted for in a single database round trip.
+const BATCH_SIZE: usize = 500;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Caps {
+    /// Upper bound of the payout for a single auction.
+    pub reward: eth::Ether,
+    /// Upper bound of the penalty for a single auction.
+    pub penalty: eth::Ether,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reward {
+    Payout(eth::Ether),
+    Penalty(eth::Ether),
+}
+
+/// The outcome of an auction whose settlement deadline has passed.
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub auction_id: auction::Id,
+    pub solver: eth::Address,
+    pub reference_score: eth::Ether,
+    pub block_deadline: eth::BlockNo,
+    /// Whether the solver settled the auction before the deadline.
+    pub settled: bool,
+    /// The score observed on-chain, i.e. the surplus and protocol fees.
+    pub observed_quality: eth::Ether,
+}
+
+impl Outcome {
+    pub fn reward(&self, caps: &Caps) -> Reward {
+        let observed_quality = if self.settled {
+            self.observed_quality
+        } else {
+            Default::default()
+        };
+        match observed_quality.0.checked_sub(self.reference_score.0) {
+            Some(payout) => Reward::Payout(eth::Ether(payout.min(caps.reward.0))),
+            None => Reward::Penalty(eth::Ether(
+                (self.reference_score.0 - observed_quality.0).min(caps.penalty.0),
+            )),
+        }
+    }
+}
+
+/// An outcome together with its reward.
+#[derive(Debug, Clone)]
+pub struct Accounted {
+    pub outcome: Outcome,
+    pub reward: Reward,
+}
+
+/// The rewards of a solver accumulated over an accounting period.
+#[derive(Debug, Clone)]
+pub struct Summary {
+    pub solver: eth::Address,
+    /// Number of won auctions.
+    pub auctions: u64,
+    /// Number of won auctions that were not settled in time.
+    pub failures: u64,
+    pub payouts: eth::Ether,
+    pub penalties: eth::Ether,
+}
+
+/// Periodically computes the rewards of all auctions whose settlement
+/// deadline has passed and stores them.
+pub struct Accountant {
+    persistence: infra::Persistence,
+    caps: Caps,
+    update_interval: Duration,
+}
+
+impl Accountant {
+    pub fn new(persistence: infra::Persistence, caps: Caps, update_interval: Duration) -> Self {
+        Self {
+            persistence,
+            caps,
+            update_interval,
+        }
+    }
+
+    pub async fn run_forever(self) -> ! {
+        let mut interval = tokio::time::interval(self.update_interval);
+        loop {
+            interval.tick().await;
+            match self.update().await {
+                Ok(accounted) => tracing::debug!(accounted, "accounted solver rewards"),
+                Err(err) => tracing::warn!(?err, "failed to account solver rewards"),
+            }
+        }
+    }
+
+    /// Accounts for all pending auctions. Returns the number of accounted
+    /// auctions.
+    async fn update(&self) -> Result<usize, infra::persistence::DatabaseError> {
+        let mut accounted = 0;
+        loop {
+            let outcomes = self.persistence.unaccounted_auctions(BATCH_SIZE).await?;
+            let done = outcomes.len() < BATCH_SIZE;
+            let rewards = outcomes
+                .into_iter()
+                .map(|outcome| Accounted {
+                    reward: outcome.reward(&self.caps),
+                    outcome,
+                })
+                .collect::<Vec<_>>();
+            self.persistence.save_rewards(&rewards).await?;
+            accounted += rewards.len();
+            if done {
+                return Ok(accounted);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ether(value: u64) -> eth::Ether {
+        eth::Ether(value.into())
+    }
+
+    fn outcome(reference_score: u64, settled: bool, observed_quality: u64) -> Outcome {
+        Outcome {
+            auction_id: 1,
+            solver: Default::default(),
+            reference_score: ether(reference_score),
+            block_deadline: eth::BlockNo(10),
+            settled,
+            observed_quality: ether(observed_quality),
+        }
+    }
+
+    #[test]
+    fn rewards() {
+        let caps = Caps {
+            reward: ether(10),
+            penalty: ether(5),
+        };
+
+        // The payout is the observed quality above the reference score.
+        assert_eq!(outcome(4, true, 7).reward(&caps), Reward::Payout(ether(3)));
+        // Payouts are capped.
+        assert_eq!(
+            outcome(4, true, 100).reward(&caps),
+            Reward::Payout(ether(10))
+        );
+        // Observing less than the reference score results in a penalty.
+        assert_eq!(outcome(4, true, 2).reward(&caps), Reward::Penalty(ether(2)));
+        // Without a runner up, a failed settlement is not penalized.
+        assert_eq!(outcome(0, false, 0).reward(&caps), Reward::Payout(ether(0)));
+        // Failing to settle in time observes no quality and penalties are
+        // capped.
+        assert_eq!(
+            outcome(4, false, 7).reward(&caps),
+            Reward::Penalty(ether(4))
+        );
+        assert_eq!(
+            outcome(100, false, 0).reward(&caps),
+            Reward::Penalty(ether(5))
+        );
+    }
+}
@@ -67,6 +67,25 @@ impl Settlement {
             .sum()
     }
 
+    /// CIP38 score of the settlement, i.e. the surplus and protocol fees of
+    /// all trades. Unlike [`Self::fee_in_ether`], the network fees are not
+    /// included.
+    pub fn score(&self) -> eth::Ether {
+        self.trades
+            .iter()
+            .map(|trade| {
+                trade.score(&self.auction).unwrap_or_else(|err| {
+                    tracing::warn!(
+                        ?err,
+                        trade = %trade.uid(),
+                        "possible incomplete score calculation",
+                    );
+                    num::zero()
+                })
+            })
+            .sum()
+    }
+
     /// Total fee taken for all the trades in the settlement.
     pub fn fee_in_ether(&self) -> eth::Ether {
         self.trades
@@ -592,6 +611,17 @@ mod tests {
             trade.fee_in_ether(&auction.prices).unwrap().0,
             eth::U256::from(6752697350740628u128)
         );
+
+        // Without protocol fees, the whole fee is the network fee, which doesn't
+        // count towards the score.
+        let settlement = super::Settlement {
+            gas: Default::default(),
+            gas_price: Default::default(),
+            block: eth::BlockNo(0),
+            auction,
+            trades: vec![trade],
+        };
+        assert_eq!(settlement.score().0, eth::U256::from(52937525819789126u128));
     }
 
     // https://etherscan.io/tx/0x688508eb59bd20dc8c0d7c0c0b01200865822c889f0fcef10113e28202783243
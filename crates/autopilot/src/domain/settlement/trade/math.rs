@@ -175,11 +175,11 @@ impl Trade {
         let mut total = eth::TokenAmount::default();
         let mut fees = vec![];
         for (i, policy) in policies.iter().enumerate().rev() {
-            let fee = current_trade.protocol_fee(policy)?;
+            let fee = current_trade.protocol_fee(policy, &auction.prices)?;
             // Do not need to calculate the last custom prices because in the last iteration
             // the prices are not used anymore to calculate the protocol fee
             fees.push(ExecutedProtocolFee {
-                policy: *policy,
+                policy: policy.clone(),
                 fee,
             });
             total += fee.amount;
@@ -259,7 +259,11 @@ impl Trade {
     /// Protocol fee is defined by a fee policy attached to the order.
     ///
     /// Denominated in SURPLUS token
-    fn protocol_fee(&self, fee_policy: &fee::Policy) -> Result<eth::Asset, Error> {
+    fn protocol_fee(
+        &self,
+        fee_policy: &fee::Policy,
+        prices: &auction::Prices,
+    ) -> Result<eth::Asset, Error> {
         let amount = match fee_policy {
             fee::Policy::Surplus {
                 factor,
@@ -284,6 +288,10 @@ impl Trade {
                 )
             }
             fee::Policy::Volume { factor } => self.volume_fee((*factor).into())?.amount,
+            fee::Policy::TieredVolume { tiers } => {
+                let factor = tiers.factor(self.native_notional(prices)?);
+                self.volume_fee(factor.into())?.amount
+            }
         };
         Ok(eth::Asset {
             token: self.surplus_token(),
@@ -419,6 +427,20 @@ impl Trade {
         })
     }
 
+    /// The executed volume in the order's target token, which is the sell token
+    /// for sell orders and the buy token for buy orders. Unlike the volume in
+    /// surplus token, it doesn't change when protocol fees are applied.
+    ///
+    /// Denominated in NATIVE token
+    fn native_notional(&self, prices: &auction::Prices) -> Result<eth::Ether, Error> {
+        let token = match self.side {
+            order::Side::Sell => self.sell.token,
+            order::Side::Buy => self.buy.token,
+        };
+        let price = prices.get(&token).ok_or(Error::MissingPrice(token))?;
+        Ok(price.in_eth(self.executed.0.into()))
+    }
+
     /// Protocol fee is defined by fee policies attached to the order.
     fn protocol_fee_in_ether(&self, auction: &settlement::Auction) -> Result<eth::Ether, Error> {
         self.protocol_fees(auction)?
@@ -579,3 +601,92 @@ pub mod error {
         Negative,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::domain::fee::{VolumeTier, VolumeTiers},
+        std::collections::{HashMap, HashSet},
+    };
+
+    fn ether(amount: f64) -> eth::U256 {
+        eth::U256::from_f64_lossy(amount * 1e18)
+    }
+
+    #[test]
+    fn tiered_volume_fee_depends_on_notional() {
+        let uid = domain::OrderUid([1; 56]);
+        let sell_token = eth::TokenAddress(eth::H160([1; 20]));
+        let buy_token = eth::TokenAddress(eth::H160([2; 20]));
+        let tiers = VolumeTiers::try_new(vec![
+            VolumeTier {
+                min_notional: eth::Ether::default(),
+                factor: 0.5.try_into().unwrap(),
+            },
+            VolumeTier {
+                min_notional: ether(50.).into(),
+                factor: 0.2.try_into().unwrap(),
+            },
+        ])
+        .unwrap();
+        let auction = settlement::Auction {
+            id: 0,
+            block: eth::BlockNo(0),
+            orders: HashMap::from([(uid, vec![fee::Policy::TieredVolume { tiers }])]),
+            // Both tokens are worth 1 ETH.
+            prices: HashMap::from([
+                (
+                    sell_token,
+                    auction::Price::try_new(ether(1.).into()).unwrap(),
+                ),
+                (
+                    buy_token,
+                    auction::Price::try_new(ether(1.).into()).unwrap(),
+                ),
+            ]),
+            surplus_capturing_jit_order_owners: HashSet::new(),
+        };
+        // A partially fillable sell order with a limit price of 0.9 that is
+        // executed at a price of 0.95.
+        let score = |executed: f64| {
+            let prices = ClearingPrices {
+                sell: 95.into(),
+                buy: 100.into(),
+            };
+            Trade {
+                uid,
+                sell: eth::Asset {
+                    token: sell_token,
+                    amount: ether(100.).into(),
+                },
+                buy: eth::Asset {
+                    token: buy_token,
+                    amount: ether(90.).into(),
+                },
+                side: order::Side::Sell,
+                executed: order::TargetAmount(ether(executed)),
+                prices: Prices {
+                    uniform: prices,
+                    custom: prices,
+                },
+            }
+            .score(&auction)
+            .unwrap()
+            .0
+        };
+        let assert_approx = |actual: eth::U256, expected: eth::U256| {
+            let diff = if actual > expected {
+                actual - expected
+            } else {
+                expected - actual
+            };
+            assert!(diff < ether(1e-9), "{actual} != {expected}");
+        };
+
+        // 2 ETH surplus and a fee of half the 76 ETH volume before the fee.
+        assert_approx(score(40.), ether(40.));
+        // 2.5 ETH surplus and a fee of 20% of the 59.375 ETH volume before the fee.
+        assert_approx(score(50.), ether(14.375));
+    }
+}
@@ -0,0 +1,380 @@
+//! Automatic suspension of drivers whose winning solutions repeatedly fail to
+//! settle.
+//!
+//! The outcomes of the most recent settlement attempts of every driver are
+//! tracked in a sliding window. Once the window is full and the share of
+//! failed attempts reaches the configured failure rate, the driver gets
+//! excluded from auctions for a cool-down period, unless it's the last driver
+//! that isn't suspended. Suspensions are persisted so that they survive
+//! restarts.
+
+use {
+    crate::{domain::eth, infra},
+    chrono::{DateTime, Utc},
+    std::{
+        collections::{HashMap, VecDeque},
+        num::NonZeroUsize,
+        sync::Mutex,
+        time::Duration,
+    },
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    /// Share of failed settlement attempts from which on a driver gets
+    /// suspended.
+    pub failure_rate: f64,
+    /// Number of most recent settlement attempts the failure rate is computed
+    /// from.
+    pub window: NonZeroUsize,
+    pub cool_down: Duration,
+}
+
+/// The outcome of settling a winning solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The solution got settled before the deadline.
+    Settled,
+    /// The driver's `/settle` request failed.
+    Failed,
+    /// The settlement transaction reverted.
+    Reverted,
+    /// The solution didn't get settled before the deadline.
+    Unsettled,
+}
+
+/// The settlement attempts a suspension is based on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Failures {
+    pub attempts: u64,
+    pub failed: u64,
+    pub reverted: u64,
+    pub unsettled: u64,
+}
+
+impl Failures {
+    fn total(&self) -> u64 {
+        self.failed + self.reverted + self.unsettled
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suspension {
+    pub driver: String,
+    pub solver: eth::Address,
+    pub suspended_at: DateTime<Utc>,
+    pub suspended_until: DateTime<Utc>,
+    pub failures: Failures,
+}
+
+/// The most recent settlement attempts of a driver.
+#[derive(Debug, Default)]
+struct Window(VecDeque<Outcome>);
+
+impl Window {
+    /// Records the outcome and returns the failures in the window if the
+    /// window is full and its failure rate reached the threshold.
+    fn push(&mut self, outcome: Outcome, config: &Config) -> Option<Failures> {
+        self.0.push_back(outcome);
+        if self.0.len() > config.window.get() {
+            self.0.pop_front();
+        }
+        if self.0.len() < config.window.get() {
+            return None;
+        }
+
+        let mut failures = Failures::default();
+        for outcome in &self.0 {
+            failures.attempts += 1;
+            match outcome {
+                Outcome::Settled => (),
+                Outcome::Failed => failures.failed += 1,
+                Outcome::Reverted => failures.reverted += 1,
+                Outcome::Unsettled => failures.unsettled += 1,
+            }
+        }
+        (failures.total() as f64 >= config.failure_rate * failures.attempts as f64)
+            .then_some(failures)
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    /// Drivers are never suspended if unset.
+    config: Option<Config>,
+    /// Number of configured drivers.
+    drivers: usize,
+    windows: HashMap<String, Window>,
+    suspended_until: HashMap<String, DateTime<Utc>>,
+}
+
+impl State {
+    fn new(config: Option<Config>, drivers: usize) -> Self {
+        Self {
+            config,
+            drivers,
+            windows: Default::default(),
+            suspended_until: Default::default(),
+        }
+    }
+
+    fn is_suspended(&self, driver: &str, now: DateTime<Utc>) -> bool {
+        self.suspended_until
+            .get(driver)
+            .is_some_and(|until| *until > now)
+    }
+
+    /// Records the outcome of a settlement attempt of the driver and returns
+    /// the suspension if its failure rate got too high. The last driver that
+    /// isn't suspended never gets suspended, so that auctions keep getting
+    /// solved.
+    fn record(
+        &mut self,
+        driver: &str,
+        solver: eth::Address,
+        outcome: Outcome,
+        now: DateTime<Utc>,
+    ) -> Option<Suspension> {
+        let config = self.config?;
+        let failures = self
+            .windows
+            .entry(driver.to_string())
+            .or_default()
+            .push(outcome, &config)?;
+
+        let suspended = self
+            .suspended_until
+            .iter()
+            .filter(|(name, until)| name.as_str() != driver && **until > now)
+            .count();
+        if suspended + 1 >= self.drivers {
+            tracing::warn!(
+                driver,
+                ?failures,
+                "not suspending the last driver that isn't suspended"
+            );
+            return None;
+        }
+
+        // Start from scratch once the suspension ends.
+        self.windows.remove(driver);
+
+        let suspended_until = chrono::Duration::from_std(config.cool_down)
+            .ok()
+            .and_then(|cool_down| now.checked_add_signed(cool_down))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+        self.suspended_until
+            .insert(driver.to_string(), suspended_until);
+        Some(Suspension {
+            driver: driver.to_string(),
+            solver,
+            suspended_at: now,
+            suspended_until,
+            failures,
+        })
+    }
+}
+
+/// Keeps track of the settlement attempts of all drivers and suspends the
+/// unreliable ones.
+pub struct Suspensions {
+    persistence: infra::Persistence,
+    state: Mutex<State>,
+}
+
+impl Suspensions {
+    /// Restores the suspensions that are still in effect.
+    pub async fn load(
+        config: Option<Config>,
+        drivers: usize,
+        persistence: infra::Persistence,
+    ) -> Self {
+        let mut state = State::new(config, drivers);
+        match persistence.active_solver_suspensions(Utc::now()).await {
+            Ok(suspensions) => {
+                for suspension in suspensions {
+                    tracing::info!(
+                        driver = %suspension.driver,
+                        until = %suspension.suspended_until,
+                        "restored driver suspension"
+                    );
+                    state
+                        .suspended_until
+                        .insert(suspension.driver, suspension.suspended_until);
+                }
+            }
+            Err(err) => tracing::warn!(?err, "failed to load driver suspensions"),
+        }
+        Self {
+            persistence,
+            state: Mutex::new(state),
+        }
+    }
+
+    pub fn is_suspended(&self, driver: &infra::Driver) -> bool {
+        self.state
+            .lock()
+            .unwrap()
+            .is_suspended(&driver.name, Utc::now())
+    }
+
+    /// Records the outcome of a settlement attempt of the driver and suspends
+    /// the driver if its failure rate got too high.
+    pub async fn record(&self, driver: &infra::Driver, outcome: Outcome) {
+        let Some(suspension) = self.state.lock().unwrap().record(
+            &driver.name,
+            driver.submission_address,
+            outcome,
+            Utc::now(),
+        ) else {
+            return;
+        };
+
+        tracing::warn!(
+            driver = %driver.name,
+            failures = ?suspension.failures,
+            until = %suspension.suspended_until,
+            "suspending driver"
+        );
+        if let Err(err) = self.persistence.save_solver_suspension(&suspension).await {
+            tracing::warn!(?err, driver = %driver.name, "failed to save driver suspension");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(failure_rate: f64, window: usize) -> Config {
+        Config {
+            failure_rate,
+            window: window.try_into().unwrap(),
+            cool_down: Duration::from_secs(3600),
+        }
+    }
+
+    #[test]
+    fn suspends_once_window_is_full() {
+        let config = config(0.5, 4);
+        let mut window = Window::default();
+
+        // Failures don't count until enough attempts were made.
+        assert_eq!(window.push(Outcome::Failed, &config), None);
+        assert_eq!(window.push(Outcome::Reverted, &config), None);
+        assert_eq!(window.push(Outcome::Settled, &config), None);
+        assert_eq!(
+            window.push(Outcome::Unsettled, &config),
+            Some(Failures {
+                attempts: 4,
+                failed: 1,
+                reverted: 1,
+                unsettled: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn only_considers_most_recent_attempts() {
+        let config = config(0.5, 3);
+        let mut window = Window::default();
+
+        assert_eq!(window.push(Outcome::Failed, &config), None);
+        assert_eq!(window.push(Outcome::Settled, &config), None);
+        assert_eq!(window.push(Outcome::Settled, &config), None);
+        // The first failure dropped out of the window.
+        assert_eq!(window.push(Outcome::Reverted, &config), None);
+        assert_eq!(
+            window.push(Outcome::Unsettled, &config),
+            Some(Failures {
+                attempts: 3,
+                failed: 0,
+                reverted: 1,
+                unsettled: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn never_suspends_if_disabled() {
+        let mut state = State::new(None, 2);
+        let now = Utc::now();
+        for _ in 0..100 {
+            assert_eq!(
+                state.record("driver", Default::default(), Outcome::Failed, now),
+                None
+            );
+        }
+        assert!(!state.is_suspended("driver", now));
+    }
+
+    #[test]
+    fn suspends_until_cool_down_expires() {
+        let mut state = State::new(Some(config(0.5, 2)), 2);
+        let now = Utc::now();
+
+        assert_eq!(
+            state.record("driver", Default::default(), Outcome::Failed, now),
+            None
+        );
+        let suspension = state
+            .record("driver", Default::default(), Outcome::Reverted, now)
+            .unwrap();
+        assert_eq!(suspension.suspended_at, now);
+        assert_eq!(suspension.suspended_until, now + chrono::Duration::hours(1));
+        assert!(state.is_suspended("driver", now));
+        assert!(!state.is_suspended("other", now));
+
+        // The suspension ends after the cool-down.
+        assert!(state.is_suspended(
+            "driver",
+            suspension.suspended_until - chrono::Duration::seconds(1)
+        ));
+        assert!(!state.is_suspended("driver", suspension.suspended_until));
+    }
+
+    #[test]
+    fn resets_window_after_suspension() {
+        let mut state = State::new(Some(config(0.5, 2)), 2);
+        let now = Utc::now();
+
+        state.record("driver", Default::default(), Outcome::Failed, now);
+        let suspension = state
+            .record("driver", Default::default(), Outcome::Failed, now)
+            .unwrap();
+
+        // After the cool-down, the failures before the suspension don't count
+        // anymore, so a single failure doesn't suspend the driver again.
+        let later = suspension.suspended_until;
+        assert_eq!(
+            state.record("driver", Default::default(), Outcome::Failed, later),
+            None
+        );
+        assert!(state
+            .record("driver", Default::default(), Outcome::Unsettled, later)
+            .is_some());
+    }
+
+    #[test]
+    fn never_suspends_last_driver() {
+        let mut state = State::new(Some(config(0.5, 1)), 2);
+        let now = Utc::now();
+
+        assert!(state
+            .record("first", Default::default(), Outcome::Failed, now)
+            .is_some());
+        // Suspending the second driver would leave no driver to solve auctions.
+        assert_eq!(
+            state.record("second", Default::default(), Outcome::Failed, now),
+            None
+        );
+        assert!(!state.is_suspended("second", now));
+
+        // Once the first driver's suspension ended, the second one can get
+        // suspended.
+        let later = now + chrono::Duration::hours(1);
+        assert!(state
+            .record("second", Default::default(), Outcome::Failed, later)
+            .is_some());
+    }
+}
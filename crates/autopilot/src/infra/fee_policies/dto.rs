@@ -0,0 +1,279 @@
+use {
+    crate::{
+        arguments,
+        domain::{
+            eth,
+            fee::{self, rules},
+        },
+    },
+    anyhow::Context,
+    primitive_types::H160,
+    serde::Deserialize,
+    std::collections::{HashMap, HashSet},
+};
+
+/// The format of the fee policies file.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct File {
+    /// Named lists of tokens that rules can refer to.
+    #[serde(default)]
+    token_lists: HashMap<String, Vec<H160>>,
+    /// Required for rules with USD notional conditions.
+    usd_reference_token: Option<UsdReferenceToken>,
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct UsdReferenceToken {
+    address: H160,
+    decimals: u8,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct Rule {
+    #[serde(default)]
+    priority: i64,
+    #[serde(default)]
+    sell_tokens: Vec<H160>,
+    #[serde(default)]
+    sell_token_lists: Vec<String>,
+    #[serde(default)]
+    buy_tokens: Vec<H160>,
+    #[serde(default)]
+    buy_token_lists: Vec<String>,
+    #[serde(default)]
+    owners: Vec<H160>,
+    #[serde(default)]
+    app_codes: Vec<String>,
+    min_notional_usd: Option<f64>,
+    max_notional_usd: Option<f64>,
+    #[serde(default)]
+    order_class: OrderClass,
+    policies: Vec<Policy>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum OrderClass {
+    Market,
+    Limit,
+    #[default]
+    Any,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum Policy {
+    Surplus {
+        factor: f64,
+        #[serde(rename = "maxVolumeFactor")]
+        max_volume_factor: f64,
+    },
+    PriceImprovement {
+        factor: f64,
+        #[serde(rename = "maxVolumeFactor")]
+        max_volume_factor: f64,
+    },
+    Volume {
+        factor: f64,
+    },
+    TieredVolume {
+        tiers: Vec<VolumeTier>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct VolumeTier {
+    /// Denominated in ETH, e.g. "10.5".
+    min_notional: String,
+    factor: f64,
+}
+
+impl File {
+    pub fn into_domain(self) -> anyhow::Result<rules::Rules> {
+        let tokens = |tokens: Vec<H160>, lists: Vec<String>| {
+            if tokens.is_empty() && lists.is_empty() {
+                return Ok(None);
+            }
+            let mut tokens = tokens.into_iter().collect::<HashSet<_>>();
+            for list in lists {
+                let list = self
+                    .token_lists
+                    .get(&list)
+                    .with_context(|| format!("unknown token list {list:?}"))?;
+                tokens.extend(list);
+            }
+            Ok::<_, anyhow::Error>(Some(tokens))
+        };
+
+        let rules = self
+            .rules
+            .into_iter()
+            .map(|rule| {
+                let condition = rules::Condition {
+                    sell_tokens: tokens(rule.sell_tokens, rule.sell_token_lists)?,
+                    buy_tokens: tokens(rule.buy_tokens, rule.buy_token_lists)?,
+                    owners: (!rule.owners.is_empty()).then(|| rule.owners.into_iter().collect()),
+                    app_codes: (!rule.app_codes.is_empty())
+                        .then(|| rule.app_codes.into_iter().collect()),
+                    min_notional_usd: rule.min_notional_usd,
+                    max_notional_usd: rule.max_notional_usd,
+                };
+                let policies = rule
+                    .policies
+                    .into_iter()
+                    .map(Policy::into_domain)
+                    .collect::<anyhow::Result<_>>()?;
+                Ok(rules::Rule::new(
+                    rule.priority,
+                    condition,
+                    rule.order_class.into_domain(),
+                    policies,
+                ))
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        rules::Rules::new(
+            rules,
+            self.usd_reference_token.map(|token| rules::UsdReference {
+                token: token.address,
+                decimals: token.decimals,
+            }),
+        )
+    }
+}
+
+impl OrderClass {
+    fn into_domain(self) -> arguments::FeePolicyOrderClass {
+        match self {
+            Self::Market => arguments::FeePolicyOrderClass::Market,
+            Self::Limit => arguments::FeePolicyOrderClass::Limit,
+            Self::Any => arguments::FeePolicyOrderClass::Any,
+        }
+    }
+}
+
+impl Policy {
+    fn into_domain(self) -> anyhow::Result<arguments::FeePolicyKind> {
+        Ok(match self {
+            Self::Surplus {
+                factor,
+                max_volume_factor,
+            } => arguments::FeePolicyKind::Surplus {
+                factor: factor.try_into()?,
+                max_volume_factor: max_volume_factor.try_into()?,
+            },
+            Self::PriceImprovement {
+                factor,
+                max_volume_factor,
+            } => arguments::FeePolicyKind::PriceImprovement {
+                factor: factor.try_into()?,
+                max_volume_factor: max_volume_factor.try_into()?,
+            },
+            Self::Volume { factor } => arguments::FeePolicyKind::Volume {
+                factor: factor.try_into()?,
+            },
+            Self::TieredVolume { tiers } => arguments::FeePolicyKind::TieredVolume {
+                tiers: fee::VolumeTiers::try_new(
+                    tiers
+                        .into_iter()
+                        .map(|tier| {
+                            Ok(fee::VolumeTier {
+                                min_notional: eth::Ether(shared::arguments::wei_from_ether(
+                                    &tier.min_notional,
+                                )?),
+                                factor: tier.factor.try_into()?,
+                            })
+                        })
+                        .collect::<anyhow::Result<_>>()?,
+                )?,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_file() {
+        let file: File = serde_json::from_str(
+            r#"{
+                "tokenLists": {
+                    "stables": [
+                        "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                        "0xdac17f958d2ee523a2206206994597c13d831ec7"
+                    ]
+                },
+                "usdReferenceToken": {
+                    "address": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                    "decimals": 6
+                },
+                "rules": [
+                    {
+                        "priority": 10,
+                        "sellTokenLists": ["stables"],
+                        "buyTokenLists": ["stables"],
+                        "policies": []
+                    },
+                    {
+                        "appCodes": ["CoW Swap"],
+                        "minNotionalUsd": 100000,
+                        "orderClass": "limit",
+                        "policies": [
+                            { "kind": "surplus", "factor": 0.5, "maxVolumeFactor": 0.01 },
+                            { "kind": "volume", "factor": 0.0002 }
+                        ]
+                    },
+                    {
+                        "priority": -1,
+                        "policies": [
+                            {
+                                "kind": "tieredVolume",
+                                "tiers": [
+                                    { "minNotional": "0", "factor": 0.0003 },
+                                    { "minNotional": "10", "factor": 0.0001 }
+                                ]
+                            }
+                        ]
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert!(file.into_domain().is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_files() {
+        let parse = |json: &str| {
+            serde_json::from_str::<File>(json)
+                .map_err(anyhow::Error::from)
+                .and_then(File::into_domain)
+        };
+
+        // Unknown token list.
+        assert!(
+            parse(r#"{ "rules": [{ "sellTokenLists": ["stables"], "policies": [] }] }"#).is_err()
+        );
+        // Notional condition without USD reference token.
+        assert!(parse(r#"{ "rules": [{ "maxNotionalUsd": 1000, "policies": [] }] }"#).is_err());
+        // Invalid fee factor.
+        assert!(
+            parse(r#"{ "rules": [{ "policies": [{ "kind": "volume", "factor": 1.5 }] }] }"#)
+                .is_err()
+        );
+        // Volume tiers not starting at zero.
+        assert!(parse(
+            r#"{ "rules": [{ "policies": [{ "kind": "tieredVolume", "tiers": [{ "minNotional": "1", "factor": 0.1 }] }] }] }"#
+        )
+        .is_err());
+        // Unknown field.
+        assert!(parse(r#"{ "rules": [{ "sellToken": [], "policies": [] }] }"#).is_err());
+    }
+}
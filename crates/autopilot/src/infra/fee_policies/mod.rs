@@ -0,0 +1,53 @@
+//! Loads market specific fee policies from a file and reloads them whenever
+//! the file changes, so that fees can be adjusted without a restart.
+
+use {
+    crate::domain::fee::rules,
+    anyhow::Context,
+    std::{path::PathBuf, time::Duration},
+};
+
+mod dto;
+
+/// Loads the rules from the file and keeps reloading them in the background.
+/// Panics if the file can't be loaded initially. Later failures keep the
+/// previously loaded rules in place.
+pub fn watch(path: PathBuf, reload_interval: Duration) -> rules::Reloadable {
+    let contents = std::fs::read(&path).expect("failed to read fee policies file");
+    let reloadable = rules::Reloadable::default();
+    reloadable.replace(parse(&contents).expect("invalid fee policies file"));
+
+    let rules = reloadable.clone();
+    tokio::spawn(async move {
+        let mut loaded = contents;
+        loop {
+            tokio::time::sleep(reload_interval).await;
+            let contents = match tokio::fs::read(&path).await {
+                Ok(contents) => contents,
+                Err(err) => {
+                    tracing::warn!(?err, ?path, "failed to read fee policies file");
+                    continue;
+                }
+            };
+            if contents == loaded {
+                continue;
+            }
+            match parse(&contents) {
+                Ok(new) => {
+                    rules.replace(new);
+                    tracing::info!(?path, "reloaded fee policies");
+                }
+                Err(err) => tracing::warn!(?err, ?path, "invalid fee policies file"),
+            }
+            loaded = contents;
+        }
+    });
+
+    reloadable
+}
+
+fn parse(contents: &[u8]) -> anyhow::Result<rules::Rules> {
+    serde_json::from_slice::<dto::File>(contents)
+        .context("parse file")?
+        .into_domain()
+}
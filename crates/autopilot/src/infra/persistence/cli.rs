@@ -1,6 +1,9 @@
 //! Command line arguments for persistence.
 
-use anyhow::Result;
+use {
+    anyhow::Result,
+    std::{path::PathBuf, time::Duration},
+};
 
 #[derive(clap::Parser, Debug, Clone)]
 pub struct S3 {
@@ -14,6 +17,27 @@ pub struct S3 {
     /// Something like "staging/mainnet/"
     #[clap(long, env)]
     pub s3_instance_upload_filename_prefix: Option<String>,
+
+    /// Local directory in which to store auction instances instead of S3. Can't
+    /// be set together with the s3_instance_upload_* arguments.
+    #[clap(long, env)]
+    pub archive_dir: Option<PathBuf>,
+
+    /// Naming scheme for archived auction instances: "flat" stores them as
+    /// `<id>.json` and "dated" as `<yyyy>/<mm>/<dd>/<id>.json`.
+    #[clap(long, env, default_value = "flat")]
+    pub archive_naming: s3::Naming,
+
+    /// Whether archived auction instances get Gzip compressed. By default
+    /// instances uploaded to S3 get compressed and instances stored in the
+    /// local archive directory don't.
+    #[clap(long, env)]
+    pub archive_gzip: Option<bool>,
+
+    /// Archived auction instances older than this get deleted. Instances are
+    /// kept forever if unset.
+    #[clap(long, env, value_parser = humantime::parse_duration)]
+    pub archive_retention: Option<Duration>,
 }
 
 impl S3 {
@@ -28,13 +52,28 @@ impl S3 {
             all_some || all_none,
             "either set all s3_instance_upload bucket arguments or none"
         );
-        Ok(if all_some {
-            Some(s3::Config {
-                bucket: self.s3_instance_upload_bucket.unwrap(),
-                filename_prefix: self.s3_instance_upload_filename_prefix.unwrap(),
-            })
+        anyhow::ensure!(
+            all_none || self.archive_dir.is_none(),
+            "either set the s3_instance_upload arguments or archive_dir, not both"
+        );
+        let (backend, filename_prefix) = if all_some {
+            (
+                s3::Backend::S3 {
+                    bucket: self.s3_instance_upload_bucket.unwrap(),
+                },
+                self.s3_instance_upload_filename_prefix.unwrap(),
+            )
+        } else if let Some(dir) = self.archive_dir {
+            (s3::Backend::Local { dir }, String::new())
         } else {
-            None
-        })
+            return Ok(None);
+        };
+        Ok(Some(s3::Config::archive(
+            backend,
+            filename_prefix,
+            self.archive_gzip,
+            self.archive_naming,
+            self.archive_retention,
+        )))
     }
 }
@@ -64,8 +64,8 @@ impl Auction {
                 .auction
                 .orders
                 .into_iter()
-                .map(super::order::to_domain)
-                .collect(),
+                .map(super::order::try_to_domain)
+                .collect::<anyhow::Result<_>>()?,
             prices: self
                 .auction
                 .prices
@@ -2,6 +2,7 @@ use {
     crate::{boundary, domain},
     anyhow::Context,
     database::fee_policies::{FeePolicy, FeePolicyKind},
+    number::conversions::{big_decimal_to_u256, u256_to_big_decimal},
 };
 
 pub fn from_domain(
@@ -22,6 +23,8 @@ pub fn from_domain(
             volume_factor: None,
             price_improvement_factor: None,
             price_improvement_max_volume_factor: None,
+            volume_tier_min_notionals: None,
+            volume_tier_factors: None,
         },
         domain::fee::Policy::Volume { factor } => FeePolicy {
             auction_id,
@@ -32,6 +35,8 @@ pub fn from_domain(
             volume_factor: Some(factor.into()),
             price_improvement_factor: None,
             price_improvement_max_volume_factor: None,
+            volume_tier_min_notionals: None,
+            volume_tier_factors: None,
         },
         domain::fee::Policy::PriceImprovement {
             factor,
@@ -46,6 +51,32 @@ pub fn from_domain(
             volume_factor: None,
             price_improvement_factor: Some(factor.into()),
             price_improvement_max_volume_factor: Some(max_volume_factor.into()),
+            volume_tier_min_notionals: None,
+            volume_tier_factors: None,
+        },
+        domain::fee::Policy::TieredVolume { tiers } => FeePolicy {
+            auction_id,
+            order_uid: boundary::database::byte_array::ByteArray(order_uid.0),
+            kind: FeePolicyKind::TieredVolume,
+            surplus_factor: None,
+            surplus_max_volume_factor: None,
+            volume_factor: None,
+            price_improvement_factor: None,
+            price_improvement_max_volume_factor: None,
+            volume_tier_min_notionals: Some(
+                tiers
+                    .tiers()
+                    .iter()
+                    .map(|tier| u256_to_big_decimal(&tier.min_notional.0))
+                    .collect(),
+            ),
+            volume_tier_factors: Some(
+                tiers
+                    .tiers()
+                    .iter()
+                    .map(|tier| f64::from(tier.factor))
+                    .collect(),
+            ),
         },
     }
 }
@@ -71,6 +102,33 @@ pub fn try_into_domain(
                 .context("missing volume_factor")?
                 .try_into()?,
         },
+        FeePolicyKind::TieredVolume => {
+            let min_notionals = policy
+                .volume_tier_min_notionals
+                .context("missing volume_tier_min_notionals")?;
+            let factors = policy
+                .volume_tier_factors
+                .context("missing volume_tier_factors")?;
+            if min_notionals.len() != factors.len() {
+                return Err(anyhow::anyhow!("volume tier columns have different lengths").into());
+            }
+            let tiers = min_notionals
+                .iter()
+                .zip(factors)
+                .map(|(min_notional, factor)| {
+                    Ok(domain::fee::VolumeTier {
+                        min_notional: domain::eth::Ether(
+                            big_decimal_to_u256(min_notional)
+                                .context("invalid volume tier min notional")?,
+                        ),
+                        factor: factor.try_into()?,
+                    })
+                })
+                .collect::<anyhow::Result<_>>()?;
+            domain::fee::Policy::TieredVolume {
+                tiers: domain::fee::VolumeTiers::try_new(tiers)?,
+            }
+        }
         FeePolicyKind::PriceImprovement => domain::fee::Policy::PriceImprovement {
             factor: policy
                 .price_improvement_factor
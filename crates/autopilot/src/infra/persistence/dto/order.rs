@@ -76,8 +76,8 @@ pub fn from_domain(order: domain::Order) -> Order {
     }
 }
 
-pub fn to_domain(order: Order) -> domain::Order {
-    domain::Order {
+pub fn try_to_domain(order: Order) -> anyhow::Result<domain::Order> {
+    Ok(domain::Order {
         uid: order.uid.into(),
         sell: eth::Asset {
             token: order.sell_token.into(),
@@ -90,8 +90,8 @@ pub fn to_domain(order: Order) -> domain::Order {
         protocol_fees: order
             .protocol_fees
             .into_iter()
-            .map(FeePolicy::into_domain)
-            .collect(),
+            .map(FeePolicy::try_into_domain)
+            .collect::<anyhow::Result<_>>()?,
         created: order.created,
         valid_to: order.valid_to,
         side: order.kind.into(),
@@ -110,7 +110,7 @@ pub fn to_domain(order: Order) -> domain::Order {
         app_data: order.app_data.into(),
         signature: order.signature.into(),
         quote: order.quote.map(|q| q.to_domain(order.uid.into())),
-    }
+    })
 }
 
 impl From<boundary::OrderUid> for domain::OrderUid {
@@ -268,6 +268,17 @@ pub enum FeePolicy {
     },
     #[serde(rename_all = "camelCase")]
     Volume { factor: f64 },
+    #[serde(rename_all = "camelCase")]
+    TieredVolume { tiers: Vec<VolumeTier> },
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeTier {
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub min_notional: U256,
+    pub factor: f64,
 }
 
 impl FeePolicy {
@@ -297,25 +308,35 @@ impl FeePolicy {
             domain::fee::Policy::Volume { factor } => Self::Volume {
                 factor: factor.into(),
             },
+            domain::fee::Policy::TieredVolume { tiers } => Self::TieredVolume {
+                tiers: tiers
+                    .tiers()
+                    .iter()
+                    .map(|tier| VolumeTier {
+                        min_notional: tier.min_notional.0,
+                        factor: tier.factor.into(),
+                    })
+                    .collect(),
+            },
         }
     }
 
-    pub fn into_domain(self) -> domain::fee::Policy {
-        match self {
+    pub fn try_into_domain(self) -> anyhow::Result<domain::fee::Policy> {
+        Ok(match self {
             Self::Surplus {
                 factor,
                 max_volume_factor,
             } => domain::fee::Policy::Surplus {
-                factor: FeeFactor::try_from(factor).unwrap(),
-                max_volume_factor: FeeFactor::try_from(max_volume_factor).unwrap(),
+                factor: FeeFactor::try_from(factor)?,
+                max_volume_factor: FeeFactor::try_from(max_volume_factor)?,
             },
             Self::PriceImprovement {
                 factor,
                 max_volume_factor,
                 quote,
             } => domain::fee::Policy::PriceImprovement {
-                factor: FeeFactor::try_from(factor).unwrap(),
-                max_volume_factor: FeeFactor::try_from(max_volume_factor).unwrap(),
+                factor: FeeFactor::try_from(factor)?,
+                max_volume_factor: FeeFactor::try_from(max_volume_factor)?,
                 quote: domain::fee::Quote {
                     sell_amount: quote.sell_amount,
                     buy_amount: quote.buy_amount,
@@ -324,9 +345,22 @@ impl FeePolicy {
                 },
             },
             Self::Volume { factor } => domain::fee::Policy::Volume {
-                factor: FeeFactor::try_from(factor).unwrap(),
+                factor: FeeFactor::try_from(factor)?,
             },
-        }
+            Self::TieredVolume { tiers } => domain::fee::Policy::TieredVolume {
+                tiers: domain::fee::VolumeTiers::try_new(
+                    tiers
+                        .into_iter()
+                        .map(|tier| {
+                            Ok(domain::fee::VolumeTier {
+                                min_notional: tier.min_notional.into(),
+                                factor: FeeFactor::try_from(tier.factor)?,
+                            })
+                        })
+                        .collect::<anyhow::Result<_>>()?,
+                )?,
+            },
+        })
     }
 }
 
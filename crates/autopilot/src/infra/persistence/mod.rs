@@ -6,7 +6,7 @@ use {
         infra::persistence::dto::AuctionId,
     },
     anyhow::Context,
-    bigdecimal::ToPrimitive,
+    bigdecimal::{BigDecimal, ToPrimitive},
     boundary::database::byte_array::ByteArray,
     chrono::{DateTime, Utc},
     database::{
@@ -106,10 +106,10 @@ impl Persistence {
                     .await
                 {
                     Ok(key) => {
-                        tracing::info!(?key, "uploaded auction to s3");
+                        tracing::info!(?key, "archived auction");
                     }
                     Err(err) => {
-                        tracing::warn!(?err, "failed to upload auction to s3");
+                        tracing::warn!(?err, "failed to archive auction");
                     }
                 }
             }
@@ -664,6 +664,7 @@ impl Persistence {
             let gas_price = settlement.gas_price();
             let surplus = settlement.surplus_in_ether();
             let fee = settlement.fee_in_ether();
+            let score = settlement.score();
             let fee_breakdown = settlement.fee_breakdown();
             let jit_orders = settlement.jit_orders();
 
@@ -674,6 +675,7 @@ impl Persistence {
                 ?gas_price,
                 ?surplus,
                 ?fee,
+                ?score,
                 ?fee_breakdown,
                 ?jit_orders,
                 "settlement update",
@@ -688,6 +690,7 @@ impl Persistence {
                     effective_gas_price: u256_to_big_decimal(&gas_price.0 .0),
                     surplus: u256_to_big_decimal(&surplus.0),
                     fee: u256_to_big_decimal(&fee.0),
+                    score: Some(u256_to_big_decimal(&score.0)),
                 },
             )
             .await?;
@@ -797,6 +800,181 @@ impl Persistence {
         ex.commit().await?;
         Ok(())
     }
+
+    /// Returns up to `limit` auctions whose settlement deadline has passed and
+    /// that have not been accounted for in the solver rewards yet.
+    pub async fn unaccounted_auctions(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<domain::rewards::Outcome>, DatabaseError> {
+        let _timer = Metrics::get()
+            .database_queries
+            .with_label_values(&["unaccounted_auctions"])
+            .start_timer();
+
+        let mut ex = self.postgres.pool.acquire().await?;
+        let last_indexed_block =
+            database::last_indexed_blocks::fetch(&mut ex, boundary::events::settlement::INDEX_NAME)
+                .await?
+                .unwrap_or_default();
+        database::solver_rewards::fetch_unaccounted(
+            &mut ex,
+            last_indexed_block,
+            i64::try_from(limit).context("limit overflow")?,
+        )
+        .await?
+        .into_iter()
+        .map(|outcome| {
+            let amount = |value: Option<BigDecimal>| {
+                value
+                    .map(|value| big_decimal_to_u256(&value).context("invalid amount"))
+                    .transpose()
+                    .map(Option::unwrap_or_default)
+            };
+            Ok(domain::rewards::Outcome {
+                auction_id: outcome.auction_id,
+                solver: eth::Address(eth::H160(outcome.winner.0)),
+                reference_score: eth::Ether(amount(Some(outcome.reference_score))?),
+                block_deadline: eth::BlockNo(
+                    u64::try_from(outcome.block_deadline).context("negative block")?,
+                ),
+                settled: outcome.settlement_block.is_some(),
+                // Only unsettled auctions have no score, settled ones get deferred
+                // until their score is observed.
+                observed_quality: eth::Ether(amount(outcome.score)?),
+            })
+        })
+        .collect()
+    }
+
+    pub async fn save_rewards(
+        &self,
+        rewards: &[domain::rewards::Accounted],
+    ) -> Result<(), DatabaseError> {
+        let _timer = Metrics::get()
+            .database_queries
+            .with_label_values(&["save_rewards"])
+            .start_timer();
+
+        let rewards = rewards
+            .iter()
+            .map(|accounted| {
+                let outcome = &accounted.outcome;
+                Ok(database::solver_rewards::Reward {
+                    auction_id: outcome.auction_id,
+                    solver: ByteArray(outcome.solver.0 .0),
+                    block_deadline: i64::try_from(outcome.block_deadline.0)
+                        .context("block overflow")?,
+                    settled: outcome.settled,
+                    observed_quality: u256_to_big_decimal(&outcome.observed_quality.0),
+                    reward: match accounted.reward {
+                        domain::rewards::Reward::Payout(payout) => u256_to_big_decimal(&payout.0),
+                        domain::rewards::Reward::Penalty(penalty) => {
+                            -u256_to_big_decimal(&penalty.0)
+                        }
+                    },
+                })
+            })
+            .collect::<Result<Vec<_>, DatabaseError>>()?;
+
+        let mut ex = self.postgres.pool.acquire().await?;
+        database::solver_rewards::insert(&mut ex, &rewards).await?;
+        Ok(())
+    }
+
+    /// Returns the rewards per solver for all auctions with a settlement
+    /// deadline within the accounting period.
+    pub async fn reward_summaries(
+        &self,
+        period: std::ops::Range<eth::BlockNo>,
+    ) -> Result<Vec<domain::rewards::Summary>, DatabaseError> {
+        let _timer = Metrics::get()
+            .database_queries
+            .with_label_values(&["reward_summaries"])
+            .start_timer();
+
+        let mut ex = self.postgres.pool.acquire().await?;
+        database::solver_rewards::fetch_summaries(
+            &mut ex,
+            i64::try_from(period.start.0).context("block overflow")?,
+            i64::try_from(period.end.0).context("block overflow")?,
+        )
+        .await?
+        .into_iter()
+        .map(|summary| {
+            Ok(domain::rewards::Summary {
+                solver: eth::Address(eth::H160(summary.solver.0)),
+                auctions: u64::try_from(summary.auctions).context("negative count")?,
+                failures: u64::try_from(summary.failures).context("negative count")?,
+                payouts: eth::Ether(
+                    big_decimal_to_u256(&summary.payouts).context("invalid payouts")?,
+                ),
+                penalties: eth::Ether(
+                    big_decimal_to_u256(&summary.penalties).context("invalid penalties")?,
+                ),
+            })
+        })
+        .collect()
+    }
+
+    pub async fn save_solver_suspension(
+        &self,
+        suspension: &domain::suspensions::Suspension,
+    ) -> Result<(), DatabaseError> {
+        let _timer = Metrics::get()
+            .database_queries
+            .with_label_values(&["save_solver_suspension"])
+            .start_timer();
+
+        let failures = &suspension.failures;
+        let count = |count: u64| i64::try_from(count).context("count overflow");
+        let suspension = database::solver_suspensions::Suspension {
+            driver: suspension.driver.clone(),
+            solver: ByteArray(suspension.solver.0 .0),
+            suspended_at: suspension.suspended_at,
+            suspended_until: suspension.suspended_until,
+            attempts: count(failures.attempts)?,
+            failed: count(failures.failed)?,
+            reverted: count(failures.reverted)?,
+            unsettled: count(failures.unsettled)?,
+        };
+
+        let mut ex = self.postgres.pool.acquire().await?;
+        database::solver_suspensions::insert(&mut ex, &suspension).await?;
+        Ok(())
+    }
+
+    /// Returns the driver suspensions that are still in effect at `now`.
+    pub async fn active_solver_suspensions(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<domain::suspensions::Suspension>, DatabaseError> {
+        let _timer = Metrics::get()
+            .database_queries
+            .with_label_values(&["active_solver_suspensions"])
+            .start_timer();
+
+        let mut ex = self.postgres.pool.acquire().await?;
+        database::solver_suspensions::fetch_active(&mut ex, now)
+            .await?
+            .into_iter()
+            .map(|suspension| {
+                let count = |count: i64| u64::try_from(count).context("negative count");
+                Ok(domain::suspensions::Suspension {
+                    driver: suspension.driver,
+                    solver: eth::Address(eth::H160(suspension.solver.0)),
+                    suspended_at: suspension.suspended_at,
+                    suspended_until: suspension.suspended_until,
+                    failures: domain::suspensions::Failures {
+                        attempts: count(suspension.attempts)?,
+                        failed: count(suspension.failed)?,
+                        reverted: count(suspension.reverted)?,
+                        unsettled: count(suspension.unsettled)?,
+                    },
+                })
+            })
+            .collect()
+    }
 }
 
 #[derive(prometheus_metric_storage::MetricStorage)]
@@ -0,0 +1,34 @@
+use {
+    crate::domain::eth,
+    serde::{Deserialize, Serialize},
+    serde_with::serde_as,
+};
+
+#[serde_as]
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// Auction ID whose settlement should be cancelled.
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    pub auction_id: i64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    pub status: Status,
+    /// The settlement transaction hash if the settlement got executed before
+    /// it could be cancelled.
+    pub tx_hash: Option<eth::H256>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Status {
+    NotFound,
+    NotSubmitted,
+    NotCancellable,
+    Cancelled,
+    Settled,
+    Failed,
+}
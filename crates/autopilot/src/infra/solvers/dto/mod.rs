@@ -1,6 +1,7 @@
 //! Types for communicating with drivers as defined in
 //! `crates/driver/openapi.yml`.
 
+pub mod cancel;
 pub mod reveal;
 pub mod settle;
 pub mod solve;
@@ -1,5 +1,5 @@
 use {
-    serde::Serialize,
+    serde::{Deserialize, Serialize},
     serde_with::{serde_as, skip_serializing_none},
 };
 
@@ -16,3 +16,11 @@ pub struct Request {
     #[serde_as(as = "serde_with::DisplayFromStr")]
     pub auction_id: i64,
 }
+
+/// Body of unsuccessful responses.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Error {
+    pub kind: String,
+    pub description: String,
+}
@@ -1,5 +1,5 @@
 use {
-    self::dto::{reveal, settle, solve},
+    self::dto::{cancel, reveal, settle, solve},
     crate::{arguments::Account, domain::eth, util},
     anyhow::{anyhow, Context, Result},
     reqwest::{Client, StatusCode},
@@ -32,6 +32,14 @@ pub enum Error {
     FailedToBuildClient(#[source] reqwest::Error),
 }
 
+#[derive(Error, Debug)]
+pub enum SettleError {
+    #[error("the settlement transaction reverted")]
+    Reverted,
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
 impl Driver {
     pub async fn try_new(
         url: Url,
@@ -81,11 +89,17 @@ impl Driver {
         self.request_response("reveal", request, None).await
     }
 
+    /// Asks the driver to cancel the settlement of an auction and returns the
+    /// final status of the settlement.
+    pub async fn cancel(&self, request: &cancel::Request) -> Result<cancel::Response> {
+        self.request_response("cancel", request, None).await
+    }
+
     pub async fn settle(
         &self,
         request: &settle::Request,
         timeout: std::time::Duration,
-    ) -> Result<()> {
+    ) -> Result<(), SettleError> {
         let url = util::join(&self.url, "settle");
         tracing::trace!(
             path=&url.path(),
@@ -108,7 +122,11 @@ impl Driver {
 
         if status != StatusCode::OK {
             let text = response.text().await.context("read error response body")?;
-            return Err(anyhow!("bad status {status}: {text}"));
+            if serde_json::from_str::<settle::Error>(&text).is_ok_and(|err| err.kind == "Reverted")
+            {
+                return Err(SettleError::Reverted);
+            }
+            return Err(anyhow!("bad status {status}: {text}").into());
         }
         Ok(())
     }
@@ -1,6 +1,6 @@
 use {
     crate::{
-        arguments::Arguments,
+        arguments::{self, Arguments},
         boundary,
         database::{
             ethflow_events::event_retriever::EthFlowRefundRetriever,
@@ -112,6 +112,15 @@ pub async fn start(args: impl Iterator<Item = String>) {
     tracing::info!("running autopilot with validated arguments:\n{}", args);
     observe::metrics::setup_registry(Some("gp_v2_autopilot".into()), None);
 
+    if let Some(arguments::Command::Rewards {
+        from_block,
+        to_block,
+    }) = args.command
+    {
+        print_rewards(&args, from_block..to_block).await;
+        return;
+    }
+
     if args.drivers.is_empty() {
         panic!("colocation is enabled but no drivers are configured");
     }
@@ -429,6 +438,10 @@ pub async fn run(args: Arguments) {
         args.price_estimation.quote_verification,
     ));
 
+    let fee_rules = match args.fee_policies_file.clone() {
+        Some(path) => infra::fee_policies::watch(path, args.fee_policies_reload_interval),
+        None => Default::default(),
+    };
     let solvable_orders_cache = SolvableOrdersCache::new(
         args.min_order_validity_period,
         persistence.clone(),
@@ -444,7 +457,11 @@ pub async fn run(args: Arguments) {
         args.limit_order_price_factor
             .try_into()
             .expect("limit order price factor can't be converted to BigDecimal"),
-        domain::ProtocolFees::new(&args.fee_policies, args.fee_policy_max_partner_fee),
+        domain::ProtocolFees::new(
+            &args.fee_policies,
+            args.fee_policy_max_partner_fee,
+            fee_rules,
+        ),
         cow_amm_registry.clone(),
         args.run_loop_native_price_timeout,
     );
@@ -467,6 +484,20 @@ pub async fn run(args: Arguments) {
             .instrument(tracing::info_span!("order_events_cleaner")),
     );
 
+    let rewards_accountant = domain::rewards::Accountant::new(
+        persistence.clone(),
+        domain::rewards::Caps {
+            reward: args.solver_reward_cap.into(),
+            penalty: args.solver_penalty_cap.into(),
+        },
+        args.solver_rewards_update_interval,
+    );
+    tokio::task::spawn(
+        rewards_accountant
+            .run_forever()
+            .instrument(tracing::info_span!("rewards_accountant")),
+    );
+
     let market_makable_token_list_configuration = TokenListConfiguration {
         url: args.trusted_tokens_url,
         update_interval: args.trusted_tokens_update_interval,
@@ -546,6 +577,7 @@ pub async fn run(args: Arguments) {
         solve_deadline: args.solve_deadline,
         max_run_loop_delay: args.max_run_loop_delay,
         max_winners_per_auction: args.max_winners_per_auction,
+        winner_selection: args.winner_selection.into(),
         max_solutions_per_solver: args.max_solutions_per_solver,
     };
     let drivers_futures = args
@@ -564,11 +596,23 @@ pub async fn run(args: Arguments) {
         })
         .collect::<Vec<_>>();
 
-    let drivers = futures::future::join_all(drivers_futures)
+    let drivers: Vec<_> = futures::future::join_all(drivers_futures)
         .await
         .into_iter()
         .collect();
 
+    let suspensions = domain::suspensions::Suspensions::load(
+        args.solver_suspension_failure_rate
+            .map(|failure_rate| domain::suspensions::Config {
+                failure_rate,
+                window: args.solver_suspension_window,
+                cool_down: args.solver_suspension_cool_down,
+            }),
+        drivers.len(),
+        persistence.clone(),
+    )
+    .await;
+
     let run = RunLoop::new(
         run_loop_config,
         eth,
@@ -578,10 +622,44 @@ pub async fn run(args: Arguments) {
         trusted_tokens,
         liveness.clone(),
         Arc::new(maintenance),
+        suspensions,
     );
     run.run_forever().await;
 }
 
+/// Prints the rewards of all solvers for the accounting period, which is a
+/// range of blocks in which the settlement deadlines of the auctions lie.
+async fn print_rewards(args: &Arguments, period: std::ops::Range<u64>) {
+    let db = Postgres::new(args.db_url.as_str(), args.insert_batch_size)
+        .await
+        .unwrap();
+    let persistence = infra::persistence::Persistence::new(None, Arc::new(db)).await;
+    let summaries = persistence
+        .reward_summaries(period.start.into()..period.end.into())
+        .await
+        .expect("failed to fetch solver rewards");
+
+    let rewards = summaries
+        .into_iter()
+        .map(|summary| {
+            let net = if summary.payouts >= summary.penalties {
+                (summary.payouts.0 - summary.penalties.0).to_string()
+            } else {
+                format!("-{}", summary.penalties.0 - summary.payouts.0)
+            };
+            serde_json::json!({
+                "solver": summary.solver.0,
+                "auctions": summary.auctions,
+                "failures": summary.failures,
+                "payouts": summary.payouts.0.to_string(),
+                "penalties": summary.penalties.0.to_string(),
+                "net": net,
+            })
+        })
+        .collect::<Vec<_>>();
+    println!("{}", serde_json::to_string_pretty(&rewards).unwrap());
+}
+
 async fn shadow_mode(args: Arguments) -> ! {
     let http_factory = HttpClientFactory::new(&args.http_client);
 
@@ -4,13 +4,14 @@ use {
         domain::{
             self,
             auction::Id,
-            competition::{self, Solution, SolutionError, TradedOrder, Unranked},
+            competition::{self, winner_selection, Solution, SolutionError, TradedOrder, Unranked},
             eth::{self, TxId},
+            suspensions::{self, Suspensions},
             OrderUid,
         },
         infra::{
             self,
-            solvers::dto::{settle, solve},
+            solvers::dto::{cancel, settle, solve},
         },
         maintenance::Maintenance,
         run::Liveness,
@@ -51,6 +52,7 @@ pub struct Config {
     /// by waiting for the next block to appear.
     pub max_run_loop_delay: Duration,
     pub max_winners_per_auction: usize,
+    pub winner_selection: winner_selection::Mode,
     pub max_solutions_per_solver: usize,
 }
 
@@ -66,6 +68,7 @@ pub struct RunLoop {
     /// Maintenance tasks that should run before every runloop to have
     /// the most recent data available.
     maintenance: Arc<Maintenance>,
+    suspensions: Suspensions,
 }
 
 impl RunLoop {
@@ -79,6 +82,7 @@ impl RunLoop {
         trusted_tokens: AutoUpdatingTokenList,
         liveness: Arc<Liveness>,
         maintenance: Arc<Maintenance>,
+        suspensions: Suspensions,
     ) -> Self {
         Self {
             config,
@@ -90,6 +94,7 @@ impl RunLoop {
             in_flight_orders: Default::default(),
             liveness,
             maintenance,
+            suspensions,
         }
     }
 
@@ -330,10 +335,17 @@ impl RunLoop {
                         submission_start.elapsed(),
                     );
                     tracing::debug!(?tx_hash, driver = %driver_.name, ?solver, "solution settled");
+                    self_
+                        .suspensions
+                        .record(&driver_, suspensions::Outcome::Settled)
+                        .await;
                 }
                 Err(err) => {
                     Metrics::settle_err(&driver_, submission_start.elapsed(), &err);
                     tracing::warn!(?err, driver = %driver_.name, "settlement failed");
+                    if let Some(outcome) = err.outcome() {
+                        self_.suspensions.record(&driver_, outcome).await;
+                    }
                 }
             }
             Metrics::single_run_completed(single_run_start.elapsed());
@@ -351,22 +363,45 @@ impl RunLoop {
         block_deadline: u64,
     ) -> Result<()> {
         let start = Instant::now();
+        let wrapped_native_token = self.eth.contracts().wrapped_native_token();
+        let candidates = solutions
+            .iter()
+            .map(|participant| {
+                winner_selection::Candidate::new(participant.solution(), wrapped_native_token)
+            })
+            .collect::<Vec<_>>();
+        let is_winner = solutions
+            .iter()
+            .map(|participant| participant.is_winner())
+            .collect::<Vec<_>>();
+        let reference_scores = winner_selection::reference_scores(
+            self.config.winner_selection,
+            &candidates,
+            self.config.max_winners_per_auction,
+            &is_winner,
+        );
+        let winners = solutions
+            .iter()
+            .zip(reference_scores)
+            .filter_map(|(participant, reference_score)| {
+                Some((participant.solution(), reference_score?))
+            })
+            .collect::<Vec<_>>();
+        for (solution, reference_score) in &winners {
+            tracing::debug!(
+                solver = ?solution.solver(),
+                score = %solution.score().get().0,
+                %reference_score,
+                "winner reference score"
+            );
+        }
         // TODO: Support multiple winners
         // https://github.com/cowprotocol/services/issues/3021
-        let Some(winning_solution) = solutions
-            .iter()
-            .find(|participant| participant.is_winner())
-            .map(|participant| participant.solution())
-        else {
+        let Some((winning_solution, reference_score)) = winners.first().copied() else {
             return Err(anyhow::anyhow!("no winners found"));
         };
         let winner = winning_solution.solver().into();
         let winning_score = winning_solution.score().get().0;
-        let reference_score = solutions
-            // todo multiple winners per auction
-            .get(1)
-            .map(|participant| participant.solution().score().get().0)
-            .unwrap_or_default();
         let participants = solutions
             .iter()
             .map(|participant| participant.solution().solver().into())
@@ -499,6 +534,13 @@ impl RunLoop {
         let mut solutions = futures::future::join_all(
             self.drivers
                 .iter()
+                .filter(|driver| {
+                    let suspended = self.suspensions.is_suspended(driver);
+                    if suspended {
+                        tracing::debug!(driver = %driver.name, "skipping suspended driver");
+                    }
+                    !suspended
+                })
                 .map(|driver| self.solve(driver.clone(), request)),
         )
         .await
@@ -556,39 +598,27 @@ impl RunLoop {
                 }
             });
 
-        // Winners are selected one by one, starting from the best solution,
-        // until `max_winners_per_auction` are selected. The solution is a winner
-        // if it swaps tokens that are not yet swapped by any previously processed
-        // solution.
+        // Winners are selected among the remaining solutions according to the
+        // configured mode, selecting at most `max_winners_per_auction` winners.
         let wrapped_native_token = self.eth.contracts().wrapped_native_token();
-        let mut already_swapped_tokens = HashSet::new();
-        let mut winners = 0;
-        let solutions = solutions
-            .cloned()
+        let solutions = solutions.cloned().collect::<Vec<_>>();
+        let candidates = solutions
+            .iter()
             .map(|participant| {
-                let swapped_tokens = participant
-                    .solution()
-                    .orders()
-                    .iter()
-                    .flat_map(|(_, order)| {
-                        [
-                            order.sell.token.as_erc20(wrapped_native_token),
-                            order.buy.token.as_erc20(wrapped_native_token),
-                        ]
-                    })
-                    .collect::<HashSet<_>>();
-
-                let is_winner = swapped_tokens.is_disjoint(&already_swapped_tokens)
-                    && winners < self.config.max_winners_per_auction;
-
-                already_swapped_tokens.extend(swapped_tokens);
-                winners += usize::from(is_winner);
-
-                participant.rank(is_winner)
+                winner_selection::Candidate::new(participant.solution(), wrapped_native_token)
             })
-            .collect();
+            .collect::<Vec<_>>();
+        let winners = winner_selection::select(
+            self.config.winner_selection,
+            &candidates,
+            self.config.max_winners_per_auction,
+        );
 
         solutions
+            .into_iter()
+            .zip(winners)
+            .map(|(participant, is_winner)| participant.rank(is_winner))
+            .collect()
     }
 
     /// Returns true if solution is fair to other solutions
@@ -768,10 +798,9 @@ impl RunLoop {
     ) -> Result<TxId, SettleError> {
         let settle = async move {
             let current_block = self.eth.current_block().borrow().number;
-            anyhow::ensure!(
-                current_block < submission_deadline_latest_block,
-                "submission deadline was missed"
-            );
+            if current_block >= submission_deadline_latest_block {
+                return Err(SettleError::DeadlineMissed);
+            }
 
             let request = settle::Request {
                 solution_id,
@@ -781,6 +810,10 @@ impl RunLoop {
             driver
                 .settle(&request, self.config.max_settlement_transaction_wait)
                 .await
+                .map_err(|err| match err {
+                    infra::solvers::SettleError::Reverted => SettleError::Reverted,
+                    infra::solvers::SettleError::Other(err) => SettleError::Failure(err),
+                })
         }
         .boxed();
 
@@ -791,11 +824,19 @@ impl RunLoop {
         // Wait for either the settlement transaction to be mined or the driver returned
         // a result.
         let result = match futures::future::select(wait_for_settlement_transaction, settle).await {
+            futures::future::Either::Left((Err(SettleError::Unsettled), _)) => {
+                // The driver is still trying to settle the solution although the
+                // deadline passed, so make sure it stops.
+                match self.cancel(driver, auction_id).await {
+                    Some(tx_hash) => Ok(tx_hash),
+                    None => Err(SettleError::Unsettled),
+                }
+            }
             futures::future::Either::Left((res, _)) => res,
             futures::future::Either::Right((driver_result, wait_for_settlement_transaction)) => {
                 match driver_result {
                     Ok(_) => wait_for_settlement_transaction.await,
-                    Err(err) => Err(SettleError::Failure(err)),
+                    Err(err) => Err(err),
                 }
             }
         };
@@ -809,6 +850,25 @@ impl RunLoop {
         result
     }
 
+    /// Asks the driver to cancel the settlement of the auction. Returns the
+    /// settlement transaction if it got executed before it could be cancelled.
+    async fn cancel(&self, driver: &infra::Driver, auction_id: i64) -> Option<TxId> {
+        let request = cancel::Request { auction_id };
+        match driver.cancel(&request).await {
+            Ok(response) => {
+                tracing::debug!(driver = %driver.name, status = ?response.status, "cancelled settlement");
+                response
+                    .tx_hash
+                    .filter(|_| response.status == cancel::Status::Settled)
+                    .map(TxId)
+            }
+            Err(err) => {
+                tracing::warn!(?err, driver = %driver.name, "failed to cancel settlement");
+                None
+            }
+        }
+    }
+
     /// Tries to find a `settle` contract call with calldata ending in `tag` and
     /// originated from the `solver`.
     ///
@@ -848,9 +908,7 @@ impl RunLoop {
                 break;
             }
         }
-        Err(SettleError::Failure(anyhow::anyhow!(
-            "settlement transaction await reached deadline"
-        )))
+        Err(SettleError::Unsettled)
     }
 
     /// Removes orders that are currently being settled to avoid solvers trying
@@ -891,10 +949,30 @@ enum SolveError {
 
 #[derive(Debug, thiserror::Error)]
 enum SettleError {
+    #[error("the settlement transaction reverted")]
+    Reverted,
+    #[error("settlement transaction await reached deadline")]
+    Unsettled,
+    #[error("submission deadline was missed")]
+    DeadlineMissed,
     #[error(transparent)]
     Failure(anyhow::Error),
 }
 
+impl SettleError {
+    /// The outcome of the driver's settlement attempt. Missing the deadline
+    /// before the settlement was even requested is not the driver's fault, so
+    /// it doesn't count as an attempt.
+    fn outcome(&self) -> Option<suspensions::Outcome> {
+        match self {
+            Self::Reverted => Some(suspensions::Outcome::Reverted),
+            Self::Unsettled => Some(suspensions::Outcome::Unsettled),
+            Self::DeadlineMissed => None,
+            Self::Failure(_) => Some(suspensions::Outcome::Failed),
+        }
+    }
+}
+
 #[derive(prometheus_metric_storage::MetricStorage)]
 #[metric(subsystem = "runloop")]
 struct Metrics {
@@ -1019,7 +1097,9 @@ impl Metrics {
 
     fn settle_err(driver: &infra::Driver, elapsed: Duration, err: &SettleError) {
         let label = match err {
-            SettleError::Failure(_) => "error",
+            SettleError::Reverted => "reverted",
+            SettleError::Unsettled => "unsettled",
+            SettleError::DeadlineMissed | SettleError::Failure(_) => "error",
         };
         Self::get()
             .settle
@@ -1107,8 +1187,8 @@ pub mod observe {
 
     /// Records metrics for the matched but unsettled orders.
     pub fn unsettled(solutions: &[domain::competition::Participant], auction: &domain::Auction) {
-        let Some(winner) = solutions.first() else {
-            // no solutions means nothing to report
+        let Some(winner) = solutions.iter().find(|p| p.is_winner()) else {
+            // no winners means nothing to report
             return;
         };
 
@@ -1131,3 +1211,27 @@ pub mod observe {
         super::Metrics::matched_unsettled(winner.driver(), non_winning_orders);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settle_error_outcome() {
+        assert_eq!(
+            SettleError::Reverted.outcome(),
+            Some(suspensions::Outcome::Reverted)
+        );
+        assert_eq!(
+            SettleError::Unsettled.outcome(),
+            Some(suspensions::Outcome::Unsettled)
+        );
+        assert_eq!(
+            SettleError::Failure(anyhow::anyhow!("driver error")).outcome(),
+            Some(suspensions::Outcome::Failed)
+        );
+        // Missing the deadline before requesting the settlement is not the
+        // driver's fault.
+        assert_eq!(SettleError::DeadlineMissed.outcome(), None);
+    }
+}
@@ -274,8 +274,12 @@ impl SolvableOrdersCache {
                         .quotes
                         .get(&order.metadata.uid.into())
                         .cloned();
-                    self.protocol_fees
-                        .apply(order, quote, &surplus_capturing_jit_order_owners)
+                    self.protocol_fees.apply(
+                        order,
+                        quote,
+                        &surplus_capturing_jit_order_owners,
+                        &prices,
+                    )
                 })
                 .collect(),
             prices: prices
@@ -1,5 +1,6 @@
 use {
     crate::{auction::AuctionId, OrderUid},
+    bigdecimal::BigDecimal,
     sqlx::{PgConnection, QueryBuilder},
     std::collections::HashMap,
 };
@@ -16,6 +17,8 @@ pub struct FeePolicy {
     pub volume_factor: Option<f64>,
     pub price_improvement_factor: Option<f64>,
     pub price_improvement_max_volume_factor: Option<f64>,
+    pub volume_tier_min_notionals: Option<Vec<BigDecimal>>,
+    pub volume_tier_factors: Option<Vec<f64>>,
 }
 
 #[derive(Debug, Clone, PartialEq, sqlx::Type)]
@@ -24,6 +27,7 @@ pub enum FeePolicyKind {
     Surplus,
     Volume,
     PriceImprovement,
+    TieredVolume,
 }
 
 pub async fn insert_batch(
@@ -38,7 +42,7 @@ pub async fn insert_batch(
     let mut query_builder = QueryBuilder::new(
         "INSERT INTO fee_policies (auction_id, order_uid, kind, surplus_factor, \
          surplus_max_volume_factor, volume_factor, price_improvement_factor, \
-         price_improvement_max_volume_factor)",
+         price_improvement_max_volume_factor, volume_tier_min_notionals, volume_tier_factors)",
     );
 
     query_builder.push_values(fee_policies, |mut b, fee_policy| {
@@ -49,7 +53,9 @@ pub async fn insert_batch(
             .push_bind(fee_policy.surplus_max_volume_factor)
             .push_bind(fee_policy.volume_factor)
             .push_bind(fee_policy.price_improvement_factor)
-            .push_bind(fee_policy.price_improvement_max_volume_factor);
+            .push_bind(fee_policy.price_improvement_max_volume_factor)
+            .push_bind(fee_policy.volume_tier_min_notionals)
+            .push_bind(fee_policy.volume_tier_factors);
     });
 
     query_builder.build().execute(ex).await.map(|_| ())
@@ -124,6 +130,8 @@ mod tests {
             volume_factor: None,
             price_improvement_factor: None,
             price_improvement_max_volume_factor: None,
+            volume_tier_min_notionals: None,
+            volume_tier_factors: None,
         };
         // surplus fee policy with caps
         let fee_policy_2 = FeePolicy {
@@ -135,6 +143,8 @@ mod tests {
             volume_factor: None,
             price_improvement_factor: None,
             price_improvement_max_volume_factor: None,
+            volume_tier_min_notionals: None,
+            volume_tier_factors: None,
         };
         // volume based fee policy
         let fee_policy_3 = FeePolicy {
@@ -146,6 +156,8 @@ mod tests {
             volume_factor: Some(0.06),
             price_improvement_factor: None,
             price_improvement_max_volume_factor: None,
+            volume_tier_min_notionals: None,
+            volume_tier_factors: None,
         };
         // price improvement fee policy
         let fee_policy_4 = FeePolicy {
@@ -157,6 +169,21 @@ mod tests {
             volume_factor: None,
             price_improvement_factor: Some(0.1),
             price_improvement_max_volume_factor: Some(0.99999),
+            volume_tier_min_notionals: None,
+            volume_tier_factors: None,
+        };
+        // tiered volume based fee policy
+        let fee_policy_5 = FeePolicy {
+            auction_id: auction_id_b,
+            order_uid: order_uid_b,
+            kind: FeePolicyKind::TieredVolume,
+            surplus_factor: None,
+            surplus_max_volume_factor: None,
+            volume_factor: None,
+            price_improvement_factor: None,
+            price_improvement_max_volume_factor: None,
+            volume_tier_min_notionals: Some(vec![0.into(), 1_000_000_000_000_000_000u64.into()]),
+            volume_tier_factors: Some(vec![0.0003, 0.0001]),
         };
 
         let fee_policies = vec![
@@ -164,6 +191,7 @@ mod tests {
             fee_policy_2.clone(),
             fee_policy_3.clone(),
             fee_policy_4.clone(),
+            fee_policy_5.clone(),
         ];
         insert_batch(&mut db, fee_policies.clone()).await.unwrap();
 
@@ -179,7 +207,7 @@ mod tests {
 
         expected.insert(
             (auction_id_b, order_uid_b),
-            vec![fee_policy_2, fee_policy_3],
+            vec![fee_policy_2, fee_policy_3, fee_policy_5],
         );
         let output = fetch_all(
             &mut db,
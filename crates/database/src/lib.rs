@@ -20,6 +20,8 @@ pub mod settlement_observations;
 pub mod settlement_scores;
 pub mod settlements;
 pub mod solver_competition;
+pub mod solver_rewards;
+pub mod solver_suspensions;
 pub mod surplus_capturing_jit_order_owners;
 pub mod trades;
 
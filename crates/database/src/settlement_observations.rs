@@ -6,22 +6,25 @@ pub struct Observation {
     pub effective_gas_price: BigDecimal,
     pub surplus: BigDecimal,
     pub fee: BigDecimal,
+    /// Surplus and protocol fees, i.e. excluding network fees.
+    pub score: Option<BigDecimal>,
     pub block_number: i64,
     pub log_index: i64,
 }
 
 pub async fn upsert(ex: &mut PgConnection, observation: Observation) -> Result<(), sqlx::Error> {
     const QUERY: &str = r#"
-INSERT INTO settlement_observations (gas_used, effective_gas_price, surplus, fee, block_number, log_index)
-VALUES ($1, $2, $3, $4, $5, $6)
+INSERT INTO settlement_observations (gas_used, effective_gas_price, surplus, fee, score, block_number, log_index)
+VALUES ($1, $2, $3, $4, $5, $6, $7)
 ON CONFLICT (block_number, log_index) DO UPDATE 
-SET gas_used = $1, effective_gas_price = $2, surplus = $3, fee = $4
+SET gas_used = $1, effective_gas_price = $2, surplus = $3, fee = $4, score = $5
     ;"#;
     sqlx::query(QUERY)
         .bind(observation.gas_used)
         .bind(observation.effective_gas_price)
         .bind(observation.surplus)
         .bind(observation.fee)
+        .bind(observation.score)
         .bind(observation.block_number)
         .bind(observation.log_index)
         .execute(ex)
@@ -76,6 +79,7 @@ mod tests {
             effective_gas_price: 2.into(),
             surplus: 3.into(),
             fee: 4.into(),
+            score: Some(5.into()),
             block_number: 1,
             log_index: 1,
         };
@@ -98,6 +102,7 @@ mod tests {
             effective_gas_price: 6.into(),
             surplus: 7.into(),
             fee: 8.into(),
+            score: Some(9.into()),
             block_number: 1,
             log_index: 1,
         };
@@ -0,0 +1,431 @@
+use {
+    crate::{auction::AuctionId, Address},
+    bigdecimal::BigDecimal,
+    sqlx::{PgConnection, QueryBuilder},
+};
+
+/// The outcome of an auction that has not been accounted for yet.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Outcome {
+    pub auction_id: AuctionId,
+    pub winner: Address,
+    pub reference_score: BigDecimal,
+    pub block_deadline: i64,
+    /// Block of the winner's settlement, if it settled before the deadline.
+    pub settlement_block: Option<i64>,
+    /// Observed score of the winner's settlement, i.e. its surplus and protocol
+    /// fees.
+    pub score: Option<BigDecimal>,
+}
+
+/// Returns up to `limit` auctions, whose deadline is at or before
+/// `last_indexed_block` and that don't have a reward yet.
+///
+/// Auctions are only returned once all settlements up to their deadline are
+/// associated with their auction, so that no settlement gets missed. Settled
+/// auctions are also deferred until the score of their settlement is observed,
+/// which never happens for settlements observed before scores were stored.
+pub async fn fetch_unaccounted(
+    ex: &mut PgConnection,
+    last_indexed_block: i64,
+    limit: i64,
+) -> Result<Vec<Outcome>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT * FROM (
+SELECT DISTINCT ON (ss.auction_id)
+    ss.auction_id, ss.winner, ss.reference_score, ss.block_deadline,
+    s.block_number AS settlement_block, so.score
+FROM settlement_scores ss
+LEFT JOIN settlements s
+    ON s.auction_id = ss.auction_id AND s.solver = ss.winner AND s.block_number <= ss.block_deadline
+LEFT JOIN settlement_observations so
+    ON so.block_number = s.block_number AND so.log_index = s.log_index
+WHERE
+    ss.block_deadline <= $1
+    AND NOT EXISTS (SELECT 1 FROM solver_rewards sr WHERE sr.auction_id = ss.auction_id)
+    AND NOT EXISTS (
+        SELECT 1 FROM settlements pending
+        WHERE pending.auction_id IS NULL AND pending.block_number <= ss.block_deadline
+    )
+ORDER BY ss.auction_id ASC, s.block_number ASC
+) outcomes
+WHERE settlement_block IS NULL OR score IS NOT NULL
+ORDER BY auction_id ASC
+LIMIT $2
+    ;"#;
+    sqlx::query_as(QUERY)
+        .bind(last_indexed_block)
+        .bind(limit)
+        .fetch_all(ex)
+        .await
+}
+
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Reward {
+    pub auction_id: AuctionId,
+    pub solver: Address,
+    pub block_deadline: i64,
+    pub settled: bool,
+    pub observed_quality: BigDecimal,
+    /// Negative for penalties.
+    pub reward: BigDecimal,
+}
+
+pub async fn insert(ex: &mut PgConnection, rewards: &[Reward]) -> Result<(), sqlx::Error> {
+    if rewards.is_empty() {
+        return Ok(());
+    }
+
+    let mut query_builder = QueryBuilder::new(
+        "INSERT INTO solver_rewards (auction_id, solver, block_deadline, settled, \
+         observed_quality, reward) ",
+    );
+    query_builder.push_values(rewards, |mut builder, reward| {
+        builder
+            .push_bind(reward.auction_id)
+            .push_bind(reward.solver)
+            .push_bind(reward.block_deadline)
+            .push_bind(reward.settled)
+            .push_bind(&reward.observed_quality)
+            .push_bind(&reward.reward);
+    });
+    query_builder.push(" ON CONFLICT (auction_id) DO NOTHING");
+
+    query_builder.build().execute(ex).await.map(|_| ())
+}
+
+pub async fn fetch(
+    ex: &mut PgConnection,
+    auction_id: AuctionId,
+) -> Result<Option<Reward>, sqlx::Error> {
+    const QUERY: &str = r#"SELECT * FROM solver_rewards WHERE auction_id = $1"#;
+    sqlx::query_as(QUERY)
+        .bind(auction_id)
+        .fetch_optional(ex)
+        .await
+}
+
+/// The rewards of a solver accumulated over an accounting period.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Summary {
+    pub solver: Address,
+    /// Number of won auctions.
+    pub auctions: i64,
+    /// Number of won auctions that were not settled in time.
+    pub failures: i64,
+    pub payouts: BigDecimal,
+    pub penalties: BigDecimal,
+}
+
+/// Sums up the rewards per solver for all auctions with a deadline in
+/// `[from_block, to_block)`.
+pub async fn fetch_summaries(
+    ex: &mut PgConnection,
+    from_block: i64,
+    to_block: i64,
+) -> Result<Vec<Summary>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT
+    solver,
+    COUNT(*) AS auctions,
+    COUNT(*) FILTER (WHERE NOT settled) AS failures,
+    COALESCE(SUM(reward) FILTER (WHERE reward > 0), 0) AS payouts,
+    COALESCE(-SUM(reward) FILTER (WHERE reward < 0), 0) AS penalties
+FROM solver_rewards
+WHERE block_deadline >= $1 AND block_deadline < $2
+GROUP BY solver
+ORDER BY solver
+    ;"#;
+    sqlx::query_as(QUERY)
+        .bind(from_block)
+        .bind(to_block)
+        .fetch_all(ex)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            byte_array::ByteArray,
+            events::{EventIndex, Settlement},
+            settlement_observations::Observation,
+            settlement_scores::Score,
+        },
+        sqlx::Connection,
+    };
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_roundtrip() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let input = Reward {
+            auction_id: 1,
+            solver: ByteArray([2; 20]),
+            block_deadline: 10,
+            settled: false,
+            observed_quality: 0.into(),
+            reward: (-5).into(),
+        };
+        insert(&mut db, &[input.clone()]).await.unwrap();
+        // Inserting the same auction again is a no-op.
+        insert(
+            &mut db,
+            &[Reward {
+                reward: 5.into(),
+                ..input.clone()
+            }],
+        )
+        .await
+        .unwrap();
+
+        let output = fetch(&mut db, 1).await.unwrap().unwrap();
+        assert_eq!(input, output);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_unaccounted_outcomes() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let winner = ByteArray([1; 20]);
+        for auction_id in [1, 2, 3] {
+            crate::settlement_scores::insert(
+                &mut db,
+                Score {
+                    auction_id,
+                    winner,
+                    winning_score: 10.into(),
+                    reference_score: 5.into(),
+                    block_deadline: auction_id * 10,
+                    simulation_block: 0,
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        // Auction 1 gets settled in time.
+        let index = EventIndex {
+            block_number: 5,
+            log_index: 0,
+        };
+        crate::events::insert_settlement(
+            &mut db,
+            &index,
+            &Settlement {
+                solver: winner,
+                transaction_hash: ByteArray([2; 32]),
+            },
+        )
+        .await
+        .unwrap();
+        crate::settlements::update_settlement_auction(&mut db, 5, 0, 1)
+            .await
+            .unwrap();
+        crate::settlement_observations::upsert(
+            &mut db,
+            Observation {
+                // The fee includes a network fee of 2, which is not part of the
+                // score.
+                surplus: 6.into(),
+                fee: 3.into(),
+                score: Some(7.into()),
+                block_number: 5,
+                log_index: 0,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        // Auction 3 is still running.
+        let outcomes = fetch_unaccounted(&mut db, 25, 10).await.unwrap();
+        assert_eq!(
+            outcomes,
+            vec![
+                Outcome {
+                    auction_id: 1,
+                    winner,
+                    reference_score: 5.into(),
+                    block_deadline: 10,
+                    settlement_block: Some(5),
+                    score: Some(7.into()),
+                },
+                Outcome {
+                    auction_id: 2,
+                    winner,
+                    reference_score: 5.into(),
+                    block_deadline: 20,
+                    settlement_block: None,
+                    score: None,
+                },
+            ]
+        );
+
+        // A settlement without an auction blocks the accounting of all later
+        // auctions.
+        crate::events::insert_settlement(
+            &mut db,
+            &EventIndex {
+                block_number: 15,
+                log_index: 0,
+            },
+            &Settlement {
+                solver: winner,
+                transaction_hash: ByteArray([3; 32]),
+            },
+        )
+        .await
+        .unwrap();
+        let outcomes = fetch_unaccounted(&mut db, 25, 10).await.unwrap();
+        assert_eq!(outcomes.len(), 1);
+
+        insert(
+            &mut db,
+            &[Reward {
+                auction_id: 1,
+                solver: winner,
+                block_deadline: 10,
+                settled: true,
+                observed_quality: 7.into(),
+                reward: 2.into(),
+            }],
+        )
+        .await
+        .unwrap();
+        assert!(fetch_unaccounted(&mut db, 25, 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_unaccounted_outcomes_without_score() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let winner = ByteArray([1; 20]);
+        for auction_id in [1, 2] {
+            crate::settlement_scores::insert(
+                &mut db,
+                Score {
+                    auction_id,
+                    winner,
+                    winning_score: 10.into(),
+                    reference_score: 5.into(),
+                    block_deadline: auction_id * 10,
+                    simulation_block: 0,
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        // Auction 1 gets settled in time, but the settlement was observed
+        // without a score.
+        crate::events::insert_settlement(
+            &mut db,
+            &EventIndex {
+                block_number: 5,
+                log_index: 0,
+            },
+            &Settlement {
+                solver: winner,
+                transaction_hash: ByteArray([2; 32]),
+            },
+        )
+        .await
+        .unwrap();
+        crate::settlements::update_settlement_auction(&mut db, 5, 0, 1)
+            .await
+            .unwrap();
+        let observation = Observation {
+            surplus: 6.into(),
+            fee: 3.into(),
+            score: None,
+            block_number: 5,
+            log_index: 0,
+            ..Default::default()
+        };
+        crate::settlement_observations::upsert(&mut db, observation.clone())
+            .await
+            .unwrap();
+
+        // The settled auction without a score gets deferred, while the unsettled
+        // one is accounted.
+        let outcomes = fetch_unaccounted(&mut db, 25, 10).await.unwrap();
+        assert_eq!(
+            outcomes,
+            vec![Outcome {
+                auction_id: 2,
+                winner,
+                reference_score: 5.into(),
+                block_deadline: 20,
+                settlement_block: None,
+                score: None,
+            }]
+        );
+
+        // Once the score is observed, the settled auction gets accounted too.
+        crate::settlement_observations::upsert(
+            &mut db,
+            Observation {
+                score: Some(7.into()),
+                ..observation
+            },
+        )
+        .await
+        .unwrap();
+        let outcomes = fetch_unaccounted(&mut db, 25, 10).await.unwrap();
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(outcomes[0].settlement_block, Some(5));
+        assert_eq!(outcomes[0].score, Some(7.into()));
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_summaries() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let solver = ByteArray([1; 20]);
+        let reward = |auction_id, block_deadline, reward: i64| Reward {
+            auction_id,
+            solver,
+            block_deadline,
+            settled: reward >= 0,
+            observed_quality: 0.into(),
+            reward: reward.into(),
+        };
+        insert(
+            &mut db,
+            &[
+                reward(1, 10, 3),
+                reward(2, 20, -2),
+                reward(3, 30, 4),
+                reward(4, 40, 100),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let summaries = fetch_summaries(&mut db, 10, 40).await.unwrap();
+        assert_eq!(
+            summaries,
+            vec![Summary {
+                solver,
+                auctions: 3,
+                failures: 1,
+                payouts: 7.into(),
+                penalties: 2.into(),
+            }]
+        );
+    }
+}
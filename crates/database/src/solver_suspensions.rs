@@ -0,0 +1,97 @@
+use {
+    crate::Address,
+    chrono::{DateTime, Utc},
+    sqlx::PgConnection,
+};
+
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Suspension {
+    pub driver: String,
+    pub solver: Address,
+    pub suspended_at: DateTime<Utc>,
+    pub suspended_until: DateTime<Utc>,
+    pub attempts: i64,
+    pub failed: i64,
+    pub reverted: i64,
+    pub unsettled: i64,
+}
+
+pub async fn insert(ex: &mut PgConnection, suspension: &Suspension) -> Result<(), sqlx::Error> {
+    const QUERY: &str = r#"
+INSERT INTO solver_suspensions (driver, solver, suspended_at, suspended_until, attempts, failed, reverted, unsettled)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+ON CONFLICT (driver, suspended_at) DO NOTHING
+    ;"#;
+    sqlx::query(QUERY)
+        .bind(&suspension.driver)
+        .bind(suspension.solver)
+        .bind(suspension.suspended_at)
+        .bind(suspension.suspended_until)
+        .bind(suspension.attempts)
+        .bind(suspension.failed)
+        .bind(suspension.reverted)
+        .bind(suspension.unsettled)
+        .execute(ex)
+        .await
+        .map(|_| ())
+}
+
+/// Returns the suspensions that are still in effect at `now`, ordered by the
+/// time they end.
+pub async fn fetch_active(
+    ex: &mut PgConnection,
+    now: DateTime<Utc>,
+) -> Result<Vec<Suspension>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT * FROM solver_suspensions
+WHERE suspended_at <= $1 AND suspended_until > $1
+ORDER BY suspended_until ASC, driver ASC
+    ;"#;
+    sqlx::query_as(QUERY).bind(now).fetch_all(ex).await
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::byte_array::ByteArray,
+        chrono::{Duration, TimeZone},
+        sqlx::Connection,
+    };
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_roundtrip() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let now = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let suspension = |driver: &str, suspended_at, suspended_until| Suspension {
+            driver: driver.to_string(),
+            solver: ByteArray([1; 20]),
+            suspended_at,
+            suspended_until,
+            attempts: 20,
+            failed: 4,
+            reverted: 3,
+            unsettled: 5,
+        };
+        let expired = suspension("a", now - Duration::hours(2), now - Duration::hours(1));
+        let active = suspension(
+            "b",
+            now - Duration::minutes(30),
+            now + Duration::minutes(30),
+        );
+        insert(&mut db, &expired).await.unwrap();
+        insert(&mut db, &active).await.unwrap();
+        // Inserting the same suspension again is a no-op.
+        insert(&mut db, &active).await.unwrap();
+
+        assert_eq!(fetch_active(&mut db, now).await.unwrap(), vec![active]);
+        assert!(fetch_active(&mut db, now + Duration::hours(1))
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
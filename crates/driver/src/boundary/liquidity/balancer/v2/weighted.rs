@@ -6,6 +6,7 @@ use {
             liquidity::{self, balancer},
         },
     },
+    anyhow::Context,
     shared::sources::balancer_v2::pool_fetching::WeightedPoolVersion,
     solver::liquidity::{balancer_v2, WeightedProductOrder},
 };
@@ -15,6 +16,27 @@ use {
 const GAS_PER_SWAP: u64 = 88_892;
 
 pub fn to_domain(id: liquidity::Id, pool: WeightedProductOrder) -> Result<liquidity::Liquidity> {
+    let weight_update = match &pool.weight_update {
+        Some(update) => Some(balancer::v2::weighted::WeightUpdate {
+            start: update.start_time,
+            end: update.end_time,
+            end_weights: pool
+                .reserves
+                .keys()
+                .map(|token| {
+                    let weight = update
+                        .end_weights
+                        .get(token)
+                        .context("missing liquidity bootstrapping pool end weight")?;
+                    Ok(balancer::v2::weighted::Weight::from_raw(
+                        weight.as_uint256(),
+                    ))
+                })
+                .collect::<Result<_>>()?,
+        }),
+        None => None,
+    };
+
     Ok(liquidity::Liquidity {
         id,
         gas: GAS_PER_SWAP.into(),
@@ -45,6 +67,7 @@ pub fn to_domain(id: liquidity::Id, pool: WeightedProductOrder) -> Result<liquid
                 WeightedPoolVersion::V0 => balancer::v2::weighted::Version::V0,
                 WeightedPoolVersion::V3Plus => balancer::v2::weighted::Version::V3Plus,
             },
+            weight_update,
         }),
     })
 }
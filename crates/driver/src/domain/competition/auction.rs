@@ -133,6 +133,105 @@ impl Auction {
     }
 }
 
+/// A per-solver policy restricting which orders of an auction get sent to the
+/// solver.
+#[derive(Debug, Clone)]
+pub struct Policy {
+    /// If set, only orders trading exclusively these tokens are sent.
+    pub allowed_tokens: Option<HashSet<eth::TokenAddress>>,
+    /// Orders trading any of these tokens are never sent.
+    pub denied_tokens: HashSet<eth::TokenAddress>,
+    /// If set, only orders trading one of these pairs are sent.
+    pub allowed_pairs: Option<HashSet<liquidity::TokenPair>>,
+    /// Orders trading any of these pairs are never sent.
+    pub denied_pairs: HashSet<liquidity::TokenPair>,
+    /// Orders whose sell amount is worth more than this are not sent. Since
+    /// their worth is unknown, orders whose sell token has no native price
+    /// are not sent either.
+    pub max_order_notional: Option<eth::Ether>,
+    pub market_orders: bool,
+    pub limit_orders: bool,
+    /// Liquidity orders are the template orders of surplus capturing JIT
+    /// order owners, like CoW AMMs.
+    pub liquidity_orders: bool,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self {
+            allowed_tokens: None,
+            denied_tokens: Default::default(),
+            allowed_pairs: None,
+            denied_pairs: Default::default(),
+            max_order_notional: None,
+            market_orders: true,
+            limit_orders: true,
+            liquidity_orders: true,
+        }
+    }
+}
+
+impl Policy {
+    /// Removes all orders from the auction which the policy doesn't allow.
+    pub fn apply(&self, mut auction: Auction) -> Auction {
+        let (orders, removed): (Vec<_>, Vec<_>) = std::mem::take(&mut auction.orders)
+            .into_iter()
+            .partition(|order| self.allows(order, &auction));
+        auction.orders = orders;
+        if !removed.is_empty() {
+            tracing::debug!(
+                orders = ?removed.iter().map(|order| order.uid).collect_vec(),
+                "ignored orders excluded by the solver policy"
+            );
+        }
+        auction
+    }
+
+    fn allows(&self, order: &competition::Order, auction: &Auction) -> bool {
+        let tokens = [order.sell.token, order.buy.token];
+        if tokens
+            .iter()
+            .any(|token| self.denied_tokens.contains(token))
+        {
+            return false;
+        }
+        if let Some(allowed) = &self.allowed_tokens {
+            if !tokens.iter().all(|token| allowed.contains(token)) {
+                return false;
+            }
+        }
+
+        if let Ok(pair) = liquidity::TokenPair::try_new(order.sell.token, order.buy.token) {
+            if self.denied_pairs.contains(&pair) {
+                return false;
+            }
+            if let Some(allowed) = &self.allowed_pairs {
+                if !allowed.contains(&pair) {
+                    return false;
+                }
+            }
+        }
+
+        if let Some(max) = self.max_order_notional {
+            let Some(price) = auction.tokens.get(order.sell.token).price else {
+                return false;
+            };
+            if price.in_eth(order.sell.amount) > max {
+                return false;
+            }
+        }
+
+        let is_liquidity = auction
+            .surplus_capturing_jit_order_owners
+            .contains(&order.trader().into());
+        match order.kind {
+            _ if is_liquidity => self.liquidity_orders,
+            order::Kind::Market => self.market_orders,
+            order::Kind::Limit => self.limit_orders,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct AuctionProcessor(Arc<Mutex<Inner>>);
 
@@ -720,3 +819,196 @@ pub enum Error {
     #[error("blockchain error: {0:?}")]
     Blockchain(#[from] blockchain::Error),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(address: u64) -> eth::TokenAddress {
+        eth::H160::from_low_u64_be(address).into()
+    }
+
+    fn order(sell: u64, buy: u64, kind: order::Kind, trader: u64) -> competition::Order {
+        competition::Order {
+            uid: Default::default(),
+            receiver: Default::default(),
+            created: util::Timestamp(100),
+            valid_to: util::Timestamp(u32::MAX),
+            buy: eth::Asset {
+                token: token(buy),
+                amount: eth::U256::exp10(18).into(),
+            },
+            sell: eth::Asset {
+                token: token(sell),
+                amount: eth::U256::exp10(18).into(),
+            },
+            side: order::Side::Sell,
+            kind,
+            app_data: Default::default(),
+            partial: order::Partial::No,
+            pre_interactions: Default::default(),
+            post_interactions: Default::default(),
+            sell_token_balance: order::SellTokenBalance::Erc20,
+            buy_token_balance: order::BuyTokenBalance::Erc20,
+            signature: order::Signature {
+                scheme: order::signature::Scheme::PreSign,
+                data: Default::default(),
+                signer: eth::H160::from_low_u64_be(trader).into(),
+            },
+            protocol_fees: Default::default(),
+            quote: Default::default(),
+        }
+    }
+
+    /// An auction in which token 1 is worth 1 ETH, token 2 is worth 2 ETH and
+    /// token 3 has no native price. Trader 100 is a surplus capturing JIT
+    /// order owner.
+    fn auction(orders: Vec<competition::Order>) -> Auction {
+        let tokens = [(1, Some(1)), (2, Some(2)), (3, None)]
+            .into_iter()
+            .map(|(address, price): (u64, Option<u64>)| Token {
+                decimals: None,
+                symbol: None,
+                address: token(address),
+                price: price
+                    .map(|price| Price::try_new((eth::U256::exp10(18) * price).into()).unwrap()),
+                available_balance: Default::default(),
+                trusted: false,
+            })
+            .map(|token| (token.address, token))
+            .collect();
+        Auction {
+            id: None,
+            orders,
+            tokens: Tokens(tokens),
+            gas_price: eth::GasPrice::new(
+                eth::U256::one().into(),
+                eth::U256::one().into(),
+                eth::U256::one().into(),
+            ),
+            deadline: Default::default(),
+            surplus_capturing_jit_order_owners: HashSet::from([
+                eth::H160::from_low_u64_be(100).into()
+            ]),
+        }
+    }
+
+    fn allows(policy: &Policy, order: competition::Order) -> bool {
+        let auction = auction(vec![]);
+        policy.allows(&order, &auction)
+    }
+
+    #[test]
+    fn allows_everything_by_default() {
+        let policy = Policy::default();
+        assert!(allows(&policy, order(1, 2, order::Kind::Market, 1)));
+        assert!(allows(&policy, order(3, 1, order::Kind::Limit, 1)));
+        assert!(allows(&policy, order(1, 2, order::Kind::Limit, 100)));
+    }
+
+    #[test]
+    fn filters_tokens() {
+        let policy = Policy {
+            denied_tokens: HashSet::from([token(2)]),
+            ..Default::default()
+        };
+        assert!(!allows(&policy, order(1, 2, order::Kind::Market, 1)));
+        assert!(!allows(&policy, order(2, 3, order::Kind::Market, 1)));
+        assert!(allows(&policy, order(1, 3, order::Kind::Market, 1)));
+
+        let policy = Policy {
+            allowed_tokens: Some(HashSet::from([token(1), token(2)])),
+            ..Default::default()
+        };
+        assert!(allows(&policy, order(1, 2, order::Kind::Market, 1)));
+        assert!(!allows(&policy, order(1, 3, order::Kind::Market, 1)));
+    }
+
+    #[test]
+    fn filters_pairs_in_either_direction() {
+        let pair = |a, b| liquidity::TokenPair::try_new(token(a), token(b)).unwrap();
+
+        let policy = Policy {
+            denied_pairs: HashSet::from([pair(1, 2)]),
+            ..Default::default()
+        };
+        assert!(!allows(&policy, order(1, 2, order::Kind::Market, 1)));
+        assert!(!allows(&policy, order(2, 1, order::Kind::Market, 1)));
+        assert!(allows(&policy, order(1, 3, order::Kind::Market, 1)));
+
+        let policy = Policy {
+            allowed_pairs: Some(HashSet::from([pair(1, 2)])),
+            ..Default::default()
+        };
+        assert!(allows(&policy, order(2, 1, order::Kind::Market, 1)));
+        assert!(!allows(&policy, order(1, 3, order::Kind::Market, 1)));
+    }
+
+    #[test]
+    fn filters_order_notional() {
+        let policy = Policy {
+            max_order_notional: Some(eth::U256::exp10(18).into()),
+            ..Default::default()
+        };
+        // Sells 1 ETH worth of token 1.
+        assert!(allows(&policy, order(1, 2, order::Kind::Market, 1)));
+        // Sells 2 ETH worth of token 2.
+        assert!(!allows(&policy, order(2, 1, order::Kind::Market, 1)));
+        // Token 3 has no native price, so the notional is unknown.
+        assert!(!allows(&policy, order(3, 1, order::Kind::Market, 1)));
+    }
+
+    #[test]
+    fn filters_order_classes() {
+        let policy = Policy {
+            market_orders: false,
+            ..Default::default()
+        };
+        assert!(!allows(&policy, order(1, 2, order::Kind::Market, 1)));
+        assert!(allows(&policy, order(1, 2, order::Kind::Limit, 1)));
+
+        let policy = Policy {
+            limit_orders: false,
+            ..Default::default()
+        };
+        assert!(allows(&policy, order(1, 2, order::Kind::Market, 1)));
+        assert!(!allows(&policy, order(1, 2, order::Kind::Limit, 1)));
+
+        // Liquidity orders are only filtered by their own flag.
+        let policy = Policy {
+            liquidity_orders: false,
+            ..Default::default()
+        };
+        assert!(!allows(&policy, order(1, 2, order::Kind::Limit, 100)));
+        assert!(allows(&policy, order(1, 2, order::Kind::Limit, 1)));
+        let policy = Policy {
+            limit_orders: false,
+            ..Default::default()
+        };
+        assert!(allows(&policy, order(1, 2, order::Kind::Limit, 100)));
+    }
+
+    #[test]
+    fn apply_removes_disallowed_orders() {
+        let policy = Policy {
+            denied_tokens: HashSet::from([token(3)]),
+            ..Default::default()
+        };
+        let auction = policy.apply(auction(vec![
+            order(1, 2, order::Kind::Market, 1),
+            order(3, 1, order::Kind::Market, 2),
+            order(2, 1, order::Kind::Limit, 3),
+        ]));
+        assert_eq!(
+            auction
+                .orders
+                .iter()
+                .map(|order| eth::Address::from(order.trader()))
+                .collect_vec(),
+            vec![
+                eth::H160::from_low_u64_be(1).into(),
+                eth::H160::from_low_u64_be(3).into(),
+            ],
+        );
+    }
+}
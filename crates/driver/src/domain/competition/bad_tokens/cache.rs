@@ -28,6 +28,15 @@ struct CacheEntry {
     is_supported: bool,
 }
 
+/// A cached decision on the quality of a token.
+#[derive(Debug, Clone, Copy)]
+pub struct Entry {
+    pub token: eth::TokenAddress,
+    pub is_supported: bool,
+    /// How long ago the decision was made.
+    pub age: Duration,
+}
+
 impl Cache {
     /// Creates a new instance which evicts cached values after a period of
     /// time.
@@ -58,6 +67,33 @@ impl Cache {
             });
     }
 
+    /// Returns all cached decisions that have not expired yet.
+    pub fn entries(&self, now: Instant) -> Vec<Entry> {
+        self.0
+            .cache
+            .iter()
+            .map(|entry| Entry {
+                token: *entry.key(),
+                is_supported: entry.is_supported,
+                age: now.duration_since(entry.last_updated),
+            })
+            .filter(|entry| entry.age < self.0.max_age)
+            .collect()
+    }
+
+    /// Restores previously cached decisions, for example after a restart.
+    /// Expired decisions are ignored.
+    pub fn restore(&self, entries: impl IntoIterator<Item = Entry>, now: Instant) {
+        for entry in entries {
+            let Some(last_updated) = now.checked_sub(entry.age) else {
+                continue;
+            };
+            if entry.age < self.0.max_age {
+                self.update_quality(entry.token, entry.is_supported, last_updated);
+            }
+        }
+    }
+
     pub fn evict_outdated_entries(&self) {
         let now = Instant::now();
         self.0
@@ -71,7 +107,7 @@ impl Cache {
         let Some(token) = self.0.cache.get(token) else {
             return Quality::Unknown;
         };
-        let still_valid = now.duration_since(token.last_updated) > self.0.max_age;
+        let still_valid = now.duration_since(token.last_updated) < self.0.max_age;
         match (still_valid, token.is_supported) {
             (false, _) => Quality::Unknown,
             (true, true) => Quality::Supported,
@@ -79,3 +115,29 @@ impl Cache {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, ethcontract::H160};
+
+    /// Tests that cached decisions are only used until they expire.
+    #[test]
+    fn cached_quality_expires() {
+        const MAX_AGE: Duration = Duration::from_secs(60);
+        let cache = Cache::new(MAX_AGE);
+        let supported = eth::TokenAddress(eth::ContractAddress(H160([1; 20])));
+        let unsupported = eth::TokenAddress(eth::ContractAddress(H160([2; 20])));
+
+        let now = Instant::now();
+        cache.update_quality(supported, true, now);
+        cache.update_quality(unsupported, false, now);
+
+        let later = now + MAX_AGE / 2;
+        assert_eq!(cache.get_quality(&supported, later), Quality::Supported);
+        assert_eq!(cache.get_quality(&unsupported, later), Quality::Unsupported);
+
+        let expired = now + MAX_AGE * 2;
+        assert_eq!(cache.get_quality(&supported, expired), Quality::Unknown);
+        assert_eq!(cache.get_quality(&unsupported, expired), Quality::Unknown);
+    }
+}
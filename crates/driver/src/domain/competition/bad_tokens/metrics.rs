@@ -18,6 +18,16 @@ struct TokenStatistics {
     flagged_unsupported_at: Option<Instant>,
 }
 
+/// The statistics of a token tracked by the [`Detector`].
+#[derive(Debug, Clone, Copy)]
+pub struct Entry {
+    pub token: eth::TokenAddress,
+    pub attempts: u32,
+    pub fails: u32,
+    /// How long ago the token was flagged as unsupported.
+    pub flagged_unsupported: Option<Duration>,
+}
+
 /// Monitors tokens to determine whether they are considered "unsupported" based
 /// on the ratio of failing to total settlement encoding attempts. A token must
 /// have participated in at least `REQUIRED_MEASUREMENTS` attempts to be
@@ -83,6 +93,37 @@ impl Detector {
         }
     }
 
+    /// Returns the statistics of all tracked tokens.
+    pub fn entries(&self, now: Instant) -> Vec<Entry> {
+        self.counter
+            .iter()
+            .map(|entry| Entry {
+                token: *entry.key(),
+                attempts: entry.attempts,
+                fails: entry.fails,
+                flagged_unsupported: entry
+                    .flagged_unsupported_at
+                    .map(|at| now.duration_since(at)),
+            })
+            .collect()
+    }
+
+    /// Restores previously tracked statistics, for example after a restart.
+    pub fn restore(&self, entries: impl IntoIterator<Item = Entry>, now: Instant) {
+        for entry in entries {
+            self.counter.insert(
+                entry.token,
+                TokenStatistics {
+                    attempts: entry.attempts,
+                    fails: entry.fails,
+                    flagged_unsupported_at: entry
+                        .flagged_unsupported
+                        .and_then(|age| now.checked_sub(age)),
+                },
+            );
+        }
+    }
+
     /// Updates the tokens that participated in settlements by
     /// incrementing their attempt count.
     /// `failure` indicates whether the settlement was successful or not.
@@ -172,4 +213,29 @@ mod tests {
         detector.update_tokens(&[(token_a, token_b)], true);
         assert_eq!(token_quality(), Quality::Unsupported);
     }
+
+    /// Tests that restored statistics keep frozen tokens frozen.
+    #[test]
+    fn restore_frozen_tokens() {
+        let detector = || {
+            Detector::new(
+                0.5,
+                2,
+                false,
+                Duration::from_secs(60),
+                solver::Name("mysolver".to_string()),
+            )
+        };
+        let token_a = eth::TokenAddress(eth::ContractAddress(H160([1; 20])));
+        let token_b = eth::TokenAddress(eth::ContractAddress(H160([2; 20])));
+
+        let original = detector();
+        original.update_tokens(&[(token_a, token_b)], true);
+        original.update_tokens(&[(token_a, token_b)], true);
+
+        let now = Instant::now();
+        let restored = detector();
+        restored.restore(original.entries(now), now);
+        assert_eq!(restored.get_quality(&token_a, now), Quality::Unsupported);
+    }
 }
@@ -1,8 +1,13 @@
 use {
     crate::domain::{competition::Auction, eth},
+    dashmap::DashMap,
     futures::future::join_all,
     itertools::{Either, Itertools},
-    std::{collections::HashMap, fmt, time::Instant},
+    std::{
+        collections::HashMap,
+        fmt,
+        time::{Duration, Instant},
+    },
 };
 
 pub mod cache;
@@ -30,6 +35,9 @@ pub enum Quality {
 
 #[derive(Default)]
 pub struct Detector {
+    /// Tokens marked as (un)supported at runtime. These have the highest
+    /// precedence and may expire.
+    overrides: DashMap<eth::TokenAddress, Override>,
     /// manually configured list of supported and unsupported tokens. Only
     /// tokens that get detected incorrectly by the automatic detectors get
     /// listed here and therefore have a higher precedence.
@@ -61,6 +69,56 @@ impl Detector {
         self
     }
 
+    /// Marks a token as (un)supported at runtime until the TTL expires, or
+    /// indefinitely without a TTL. Marking a token as [`Quality::Unknown`]
+    /// removes the override.
+    pub fn set_override(
+        &self,
+        token: eth::TokenAddress,
+        quality: Quality,
+        ttl: Option<Duration>,
+        now: Instant,
+    ) {
+        match quality {
+            Quality::Unknown => {
+                self.overrides.remove(&token);
+            }
+            quality => {
+                self.overrides.insert(
+                    token,
+                    Override {
+                        quality,
+                        expires_at: ttl.map(|ttl| now + ttl),
+                    },
+                );
+            }
+        }
+    }
+
+    /// Returns all token overrides that did not expire yet along with their
+    /// remaining TTL.
+    pub fn overrides(&self, now: Instant) -> Vec<(eth::TokenAddress, Quality, Option<Duration>)> {
+        self.overrides
+            .iter()
+            .filter(|entry| !entry.is_expired(now))
+            .map(|entry| {
+                (
+                    *entry.key(),
+                    entry.quality,
+                    entry.expires_at.map(|at| at.duration_since(now)),
+                )
+            })
+            .collect()
+    }
+
+    pub fn simulation_detector(&self) -> Option<&simulation::Detector> {
+        self.simulation_detector.as_ref()
+    }
+
+    pub fn metrics_detector(&self) -> Option<&metrics::Detector> {
+        self.metrics.as_ref()
+    }
+
     /// Removes all unsupported orders from the auction.
     pub async fn filter_unsupported_orders_in_auction(&self, mut auction: Auction) -> Auction {
         let now = Instant::now();
@@ -121,6 +179,12 @@ impl Detector {
     }
 
     fn get_token_quality(&self, token: eth::TokenAddress, now: Instant) -> Quality {
+        if let Some(entry) = self.overrides.get(&token) {
+            if !entry.is_expired(now) {
+                return entry.quality;
+            }
+        }
+
         match self.hardcoded.get(&token) {
             None | Some(Quality::Unknown) => (),
             Some(quality) => return *quality,
@@ -144,9 +208,22 @@ impl Detector {
     }
 }
 
+/// A token quality set at runtime.
+struct Override {
+    quality: Quality,
+    expires_at: Option<Instant>,
+}
+
+impl Override {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at.is_some_and(|at| at <= now)
+    }
+}
+
 impl fmt::Debug for Detector {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Detector")
+            .field("overrides", &self.overrides(Instant::now()))
             .field("hardcoded", &self.hardcoded)
             .finish()
     }
@@ -5,7 +5,7 @@ use {
         Mempools,
     },
     crate::{
-        domain::{competition::solution::Settlement, eth, time::DeadlineExceeded},
+        domain::{competition::solution::Settlement, eth, mempools, time::DeadlineExceeded},
         infra::{
             self,
             blockchain::Ethereum,
@@ -25,7 +25,7 @@ use {
         sync::{Arc, Mutex},
     },
     tap::TapFallible,
-    tokio::sync::{mpsc, oneshot},
+    tokio::sync::{mpsc, oneshot, watch},
     tracing::Instrument,
 };
 
@@ -59,6 +59,8 @@ pub struct Competition {
     pub settlements: Mutex<VecDeque<Settlement>>,
     pub bad_tokens: Arc<bad_tokens::Detector>,
     settle_queue: mpsc::Sender<SettleRequest>,
+    /// The settlement that is currently being executed, if any.
+    in_flight: Mutex<Option<InFlight>>,
 }
 
 impl Competition {
@@ -81,6 +83,7 @@ impl Competition {
             settlements: Default::default(),
             settle_queue: settle_sender,
             bad_tokens,
+            in_flight: Default::default(),
         });
 
         let competition_clone = Arc::clone(&competition);
@@ -95,6 +98,7 @@ impl Competition {
 
     /// Solve an auction as part of this competition.
     pub async fn solve(&self, auction: Auction) -> Result<Option<Solved>, Error> {
+        let auction = self.solver.policy().apply(auction);
         let auction = &self
             .bad_tokens
             .filter_unsupported_orders_in_auction(auction)
@@ -160,6 +164,8 @@ impl Competition {
                 let id = solution.id().clone();
                 let token_pairs = solution.token_pairs();
                 observe::encoding(&id);
+                let persistence = self.solver.persistence();
+                persistence.archive_solution(auction.id(), &solution);
                 let settlement = solution
                     .encode(
                         auction,
@@ -168,6 +174,7 @@ impl Competition {
                         self.solver.solver_native_token(),
                     )
                     .await;
+                persistence.archive_settlement(auction.id(), &id, &settlement);
                 (id, token_pairs, settlement)
             })
             .collect::<FuturesUnordered<_>>()
@@ -177,8 +184,12 @@ impl Competition {
                         self.bad_tokens.encoding_succeeded(&token_pairs);
                         Some(solution)
                     }
-                    // don't report on errors coming from solution merging
-                    Err(_err) if id.solutions().len() > 1 => None,
+                    // don't report on errors coming from solution merging, the
+                    // individual solutions get encoded on their own
+                    Err(err) if id.solutions().len() > 1 => {
+                        observe::merged_encoding_failed(self.solver.name(), &id, &err);
+                        None
+                    }
                     Err(err) => {
                         self.bad_tokens.encoding_failed(&token_pairs);
                         observe::encoding_failed(self.solver.name(), &id, &err);
@@ -369,6 +380,48 @@ impl Competition {
         })?
     }
 
+    /// Cancels the settlement of the auction. Settlements that were not
+    /// submitted yet won't be anymore and a pending settlement transaction gets
+    /// replaced. Settlements that were only submitted as bundles can't be
+    /// cancelled. Returns the final status of the settlement.
+    pub async fn cancel(&self, auction_id: auction::Id) -> Cancellation {
+        // Remove the settlements of the auction first, so that queued `/settle`
+        // requests fail. Settlements get marked as in flight before they are
+        // removed for execution, so the in flight settlement can't be missed.
+        let removed = {
+            let mut lock = self.settlements.lock().unwrap();
+            let before = lock.len();
+            lock.retain(|settlement| settlement.auction_id != auction_id);
+            before - lock.len()
+        };
+
+        let outcome = match self
+            .in_flight
+            .lock()
+            .unwrap()
+            .as_ref()
+            .filter(|in_flight| in_flight.auction_id == auction_id)
+        {
+            None => None,
+            Some(_) if !self.mempools.cancellable() => return Cancellation::NotCancellable,
+            Some(in_flight) => {
+                in_flight.cancellation.send_replace(true);
+                Some(in_flight.outcome.clone())
+            }
+        };
+        let Some(mut outcome) = outcome else {
+            return match removed {
+                0 => Cancellation::NotFound,
+                _ => Cancellation::NotSubmitted,
+            };
+        };
+
+        match outcome.wait_for(Option::is_some).await {
+            Ok(outcome) => outcome.clone().unwrap_or(Cancellation::Failed),
+            Err(_) => Cancellation::Failed,
+        }
+    }
+
     pub fn ensure_settle_queue_capacity(&self) -> Result<(), Error> {
         if self.settle_queue.capacity() == 0 {
             tracing::warn!("settlement queue is full; auction is rejected");
@@ -421,6 +474,35 @@ impl Competition {
         auction_id: auction::Id,
         solution_id: u64,
         submission_deadline: BlockNo,
+    ) -> Result<Settled, Error> {
+        let (cancellation, cancelled) = watch::channel(false);
+        let (outcome, outcome_receiver) = watch::channel(None);
+        *self.in_flight.lock().unwrap() = Some(InFlight {
+            auction_id,
+            cancellation,
+            outcome: outcome_receiver,
+        });
+
+        let result = self
+            .execute_settlement(auction_id, solution_id, submission_deadline, cancelled)
+            .await;
+
+        self.in_flight.lock().unwrap().take();
+        outcome.send_replace(Some(match &result {
+            Ok(settled) => Cancellation::Settled(settled.tx_hash.clone()),
+            Err(Error::SolutionNotAvailable) => Cancellation::NotSubmitted,
+            Err(Error::Cancelled) => Cancellation::Cancelled,
+            Err(_) => Cancellation::Failed,
+        }));
+        result
+    }
+
+    async fn execute_settlement(
+        &self,
+        auction_id: auction::Id,
+        solution_id: u64,
+        submission_deadline: BlockNo,
+        cancellation: watch::Receiver<bool>,
     ) -> Result<Settled, Error> {
         let settlement = {
             let mut lock = self.settlements.lock().unwrap();
@@ -435,7 +517,7 @@ impl Competition {
 
         let executed = self
             .mempools
-            .execute(&self.solver, &settlement, submission_deadline)
+            .execute(&self.solver, &settlement, submission_deadline, cancellation)
             .await;
         notify::executed(
             &self.solver,
@@ -443,9 +525,14 @@ impl Competition {
             settlement.solution(),
             &executed,
         );
+        self.solver.persistence().archive_execution(
+            settlement.auction_id,
+            settlement.solution(),
+            &executed,
+        );
 
         match executed {
-            Err(_) => Err(Error::SubmissionError),
+            Err(err) => Err(err.into()),
             Ok(tx_hash) => Ok(Settled {
                 internalized_calldata: settlement
                     .transaction(settlement::Internalization::Enable)
@@ -490,43 +577,82 @@ impl Competition {
 
 const MAX_SOLUTIONS_TO_MERGE: usize = 10;
 
-/// Creates a vector with all possible combinations of the given solutions.
-/// The result is sorted descending by score.
+/// Builds the best-scoring non-conflicting combination of the given solutions.
+/// Returns the individual solutions along with that combination if it scores
+/// better than every individual solution, sorted descending by score. All of
+/// them get simulated during encoding, so the individual solutions serve as a
+/// fallback for a combination that reverts.
 fn merge(solutions: impl Iterator<Item = Solution>, auction: &Auction) -> Vec<Solution> {
-    let mut merged: Vec<Solution> = Vec::new();
-    // Limit the number of solutions to merge to avoid combinatorial explosion
-    // (2^MAX_SOLUTIONS).
-    for solution in solutions.take(MAX_SOLUTIONS_TO_MERGE) {
-        let mut extension = vec![];
-        for already_merged in merged.iter() {
-            match solution.merge(already_merged) {
-                Ok(merged) => {
-                    observe::merged(&solution, already_merged, &merged);
-                    extension.push(merged);
-                }
-                Err(err) => {
-                    observe::not_merged(&solution, already_merged, err);
-                }
+    let score = |solution: &Solution| {
+        solution
+            .scoring(
+                &auction.prices(),
+                auction.surplus_capturing_jit_order_owners(),
+            )
+            .map(|score| score.0)
+            .unwrap_or_default()
+    };
+    let mut solutions = solutions.collect_vec();
+    solutions.sort_by_cached_key(|solution| Reverse(score(solution)));
+
+    // Limit the number of solutions to merge to bound the number of
+    // combinations that need to be tried.
+    let candidates = &solutions[..solutions.len().min(MAX_SOLUTIONS_TO_MERGE)];
+    let combination = best_combination(
+        candidates,
+        |combination, solution| match combination.merge(solution) {
+            Ok(result) => {
+                observe::merged(combination, solution, &result);
+                Some(result)
             }
-        }
-        // At least insert the current solution
-        extension.push(solution);
-        merged.extend(extension);
-    }
+            Err(err) => {
+                observe::not_merged(combination, solution, err);
+                None
+            }
+        },
+        score,
+    )
+    .filter(|combination| {
+        solutions
+            .first()
+            .is_none_or(|best| score(combination) > score(best))
+    });
+    solutions.extend(combination);
 
     // Sort merged solutions descending by score.
-    merged.sort_by_key(|solution| {
-        Reverse(
-            solution
-                .scoring(
-                    &auction.prices(),
-                    auction.surplus_capturing_jit_order_owners(),
-                )
-                .map(|score| score.0)
-                .unwrap_or_default(),
-        )
-    });
-    merged
+    solutions.sort_by_cached_key(|solution| Reverse(score(solution)));
+    solutions
+}
+
+/// Returns the best-scoring combination of at least two of the candidates.
+/// Combinations are built by merging candidates in order, so once two of them
+/// can't be merged, none of the combinations containing both are tried.
+fn best_combination<T: Clone, S: Ord>(
+    candidates: &[T],
+    mut merge: impl FnMut(&T, &T) -> Option<T>,
+    score: impl Fn(&T) -> S,
+) -> Option<T> {
+    let mut best: Option<(S, T)> = None;
+    // Combinations along with the index of the first candidate that may still
+    // be merged into them.
+    let mut stack = candidates
+        .iter()
+        .enumerate()
+        .map(|(i, candidate)| (candidate.clone(), i + 1))
+        .collect_vec();
+    while let Some((combination, next)) = stack.pop() {
+        for (i, candidate) in candidates.iter().enumerate().skip(next) {
+            let Some(merged) = merge(&combination, candidate) else {
+                continue;
+            };
+            let merged_score = score(&merged);
+            if best.as_ref().is_none_or(|(best, _)| merged_score > *best) {
+                best = Some((merged_score, merged.clone()));
+            }
+            stack.push((merged, i + 1));
+        }
+    }
+    best.map(|(_, combination)| combination)
 }
 
 struct SettleRequest {
@@ -580,6 +706,32 @@ pub struct Revealed {
     pub uninternalized_calldata: Bytes<Vec<u8>>,
 }
 
+/// The final status of a settlement after its cancellation was requested.
+#[derive(Debug, Clone)]
+pub enum Cancellation {
+    /// No settlement of the auction is queued or being executed.
+    NotFound,
+    /// The settlement was not submitted and won't be anymore.
+    NotSubmitted,
+    /// The settlement was submitted in a way that can't be cancelled, e.g. as
+    /// a bundle, and keeps being executed.
+    NotCancellable,
+    /// The pending settlement transaction was cancelled.
+    Cancelled,
+    /// The settlement got executed before it could be cancelled.
+    Settled(eth::TxId),
+    /// The settlement failed for a reason other than the cancellation.
+    Failed,
+}
+
+/// A settlement that is currently being executed.
+#[derive(Debug)]
+struct InFlight {
+    auction_id: auction::Id,
+    cancellation: watch::Sender<bool>,
+    outcome: watch::Receiver<Option<Cancellation>>,
+}
+
 #[derive(Debug)]
 pub struct Settled {
     /// The transaction hash in which the solution was submitted.
@@ -604,6 +756,55 @@ pub enum Error {
     Solver(#[from] solver::Error),
     #[error("failed to submit the solution")]
     SubmissionError,
+    #[error("the settlement transaction reverted")]
+    Reverted,
     #[error("too many pending settlements for the same solver")]
     TooManyPendingSettlements,
+    #[error("the settlement was cancelled")]
+    Cancelled,
+}
+
+impl From<mempools::Error> for Error {
+    fn from(value: mempools::Error) -> Self {
+        match value {
+            mempools::Error::Cancelled => Self::Cancelled,
+            mempools::Error::Revert { .. } => Self::Reverted,
+            _ => Self::SubmissionError,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A candidate with a score that uses the given pools.
+    type Candidate = (u32, Vec<u8>);
+
+    fn merge((score_a, pools_a): &Candidate, (score_b, pools_b): &Candidate) -> Option<Candidate> {
+        pools_a
+            .iter()
+            .all(|pool| !pools_b.contains(pool))
+            .then(|| (score_a + score_b, [&pools_a[..], &pools_b[..]].concat()))
+    }
+
+    #[test]
+    fn finds_best_combination() {
+        // Greedily merging into the best candidate scores 11, while combining
+        // all other candidates scores 13.
+        let candidates = [(10, vec![1, 2]), (6, vec![1]), (6, vec![2]), (1, vec![3])];
+        assert_eq!(
+            best_combination(&candidates, merge, |(score, _)| *score),
+            Some((13, vec![1, 2, 3]))
+        );
+    }
+
+    #[test]
+    fn no_combination_without_compatible_candidates() {
+        let candidates = [(10, vec![1, 2]), (6, vec![1]), (6, vec![2, 1])];
+        assert_eq!(
+            best_combination(&candidates, merge, |(score, _)| *score),
+            None
+        );
+    }
 }
@@ -1,4 +1,4 @@
-use crate::domain::competition::order;
+use crate::domain::{competition::order, eth};
 
 #[derive(Clone, Debug)]
 pub enum FeePolicy {
@@ -44,4 +44,86 @@ pub enum FeePolicy {
         /// fee.
         factor: f64,
     },
+    /// Same as `Volume`, but the factor depends on the order's notional, which
+    /// is the native token value of the executed sell amount for `sell` orders
+    /// and of the executed buy amount for `buy` orders.
+    TieredVolume {
+        /// Tiers sorted by ascending notional.
+        tiers: Vec<VolumeTier>,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub struct VolumeTier {
+    /// The smallest order notional the tier applies to.
+    pub min_notional: eth::Ether,
+    /// Percentage of the order's volume should be taken as a protocol fee.
+    pub factor: f64,
+}
+
+/// The fee factor of the tier the notional falls into. Notionals below the
+/// first tier are charged with the factor of the first tier.
+pub fn tiered_volume_factor(tiers: &[VolumeTier], notional: eth::Ether) -> f64 {
+    tiers
+        .iter()
+        .rfind(|tier| tier.min_notional <= notional)
+        .or(tiers.first())
+        .map_or(0.0, |tier| tier.factor)
+}
+
+/// The highest fee factor an execution with a notional of at most
+/// `max_notional` can be charged with. All tiers are considered if the
+/// notional is unknown.
+pub fn max_tiered_volume_factor(
+    tiers: &[VolumeTier],
+    max_notional: Option<eth::Ether>,
+) -> Option<f64> {
+    tiers
+        .iter()
+        .enumerate()
+        .filter(|(i, tier)| *i == 0 || max_notional.map_or(true, |max| tier.min_notional <= max))
+        .map(|(_, tier)| tier.factor)
+        .reduce(f64::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tiers() -> Vec<VolumeTier> {
+        [(0u64, 0.0001), (100, 0.0003), (1000, 0.0002)]
+            .into_iter()
+            .map(|(min_notional, factor)| VolumeTier {
+                min_notional: eth::U256::from(min_notional).into(),
+                factor,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tier_boundaries() {
+        let factor =
+            |notional: u64| tiered_volume_factor(&tiers(), eth::U256::from(notional).into());
+
+        assert_eq!(factor(0), 0.0001);
+        assert_eq!(factor(99), 0.0001);
+        assert_eq!(factor(100), 0.0003);
+        assert_eq!(factor(999), 0.0003);
+        assert_eq!(factor(1000), 0.0002);
+        assert_eq!(factor(u64::MAX), 0.0002);
+        assert_eq!(tiered_volume_factor(&[], eth::U256::zero().into()), 0.0);
+    }
+
+    #[test]
+    fn max_factor_up_to_notional() {
+        let factor = |notional: Option<u64>| {
+            max_tiered_volume_factor(&tiers(), notional.map(|n| eth::U256::from(n).into()))
+        };
+
+        assert_eq!(factor(Some(99)), Some(0.0001));
+        assert_eq!(factor(Some(100)), Some(0.0003));
+        assert_eq!(factor(Some(5000)), Some(0.0003));
+        assert_eq!(factor(None), Some(0.0003));
+        assert_eq!(max_tiered_volume_factor(&[], None), None);
+    }
 }
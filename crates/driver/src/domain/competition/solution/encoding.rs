@@ -9,7 +9,7 @@ use {
             eth::{self, allowance, Ether},
             liquidity,
         },
-        infra::{self, solver::ManageNativeToken},
+        infra::{self, blockchain::contracts::FlashloanLender, solver::ManageNativeToken},
         util::Bytes,
     },
     allowance::Allowance,
@@ -24,6 +24,10 @@ pub enum Error {
     MissingAuctionId,
     #[error("invalid clearing price: {0:?}")]
     InvalidClearingPrice(eth::TokenAddress),
+    #[error("flashloans from unsupported lender: {0:?}")]
+    UnsupportedFlashloanLender(eth::ContractAddress),
+    #[error("settlements can only be wrapped in a single flashloan")]
+    MultipleFlashloans,
     #[error(transparent)]
     Math(#[from] Math),
 }
@@ -197,6 +201,30 @@ pub fn tx(
         interactions.push(unwrap(native_unwrap, contracts.weth()));
     }
 
+    // Encode flashloan transfers. The borrowed tokens are passed on to the
+    // borrower before any other pre-interaction, and the lender is repaid from
+    // the settlement contract's balance after all other post-interactions.
+    let flashloan = match solution.flashloans() {
+        [] => None,
+        [flashloan] => {
+            let lender = contracts
+                .flashloan_lender(flashloan.lender)
+                .ok_or(Error::UnsupportedFlashloanLender(flashloan.lender))?;
+            let repayment = flashloan.repayment().ok_or(Math::Overflow)?;
+            pre_interactions = borrow(flashloan, lender, contracts.settlement().address().into())
+                .into_iter()
+                .chain(pre_interactions)
+                .collect();
+            post_interactions.push(transfer(
+                flashloan.token,
+                lender.wrapper.address().into(),
+                repayment,
+            ));
+            Some((flashloan, lender))
+        }
+        _ => return Err(Error::MultipleFlashloans),
+    };
+
     let tx = contracts
         .settlement()
         .settle(
@@ -215,9 +243,26 @@ pub fn tx(
     let mut calldata = tx.data.unwrap().0;
     calldata.extend(auction.id().ok_or(Error::MissingAuctionId)?.to_be_bytes());
 
+    // Wrap the settlement in a call to the flashloan wrapper, which takes out
+    // the loan and calls the settlement contract with the borrowed funds.
+    let (to, calldata) = match flashloan {
+        None => (contracts.settlement().address().into(), calldata),
+        Some((flashloan, lender)) => {
+            let tx = lender
+                .wrapper
+                .flash_loan_and_settle(
+                    flashloan.lender.0,
+                    (flashloan.token.into(), flashloan.amount.into()),
+                    ethcontract::Bytes(calldata),
+                )
+                .into_inner();
+            (lender.wrapper.address().into(), tx.data.unwrap().0)
+        }
+    };
+
     Ok(eth::Tx {
         from: solution.solver().address(),
-        to: contracts.settlement().address().into(),
+        to,
         input: calldata.into(),
         value: Ether(0.into()),
         access_list: Default::default(),
@@ -274,6 +319,88 @@ pub fn approve(allowance: &Allowance) -> eth::Interaction {
     }
 }
 
+/// Returns the interactions for passing the borrowed tokens on from the
+/// flashloan wrapper to the borrower. Only the settlement contract may approve
+/// spending the wrapper's tokens, so this needs to happen as part of the
+/// settlement.
+fn borrow(
+    flashloan: &competition::solution::Flashloan,
+    lender: &FlashloanLender,
+    settlement: eth::Address,
+) -> [eth::Interaction; 2] {
+    let tx = lender
+        .wrapper
+        .approve(
+            flashloan.token.into(),
+            settlement.0,
+            flashloan.amount.into(),
+        )
+        .into_inner();
+    [
+        eth::Interaction {
+            target: tx.to.unwrap().into(),
+            value: Ether(0.into()),
+            call_data: tx.data.unwrap().0.into(),
+        },
+        transfer_from(
+            flashloan.token,
+            lender.wrapper.address().into(),
+            flashloan.borrower,
+            flashloan.amount,
+        ),
+    ]
+}
+
+fn transfer(
+    token: eth::TokenAddress,
+    to: eth::Address,
+    amount: eth::TokenAmount,
+) -> eth::Interaction {
+    let mut amount_bytes = [0u8; 32];
+    let selector = hex_literal::hex!("a9059cbb");
+    amount.0.to_big_endian(&mut amount_bytes);
+    eth::Interaction {
+        target: token.into(),
+        value: eth::U256::zero().into(),
+        // selector (4 bytes) + to (20 byte address padded to 32 bytes) + amount (32 bytes)
+        call_data: [
+            selector.as_slice(),
+            [0; 12].as_slice(),
+            to.0.as_bytes(),
+            &amount_bytes,
+        ]
+        .concat()
+        .into(),
+    }
+}
+
+fn transfer_from(
+    token: eth::TokenAddress,
+    from: eth::Address,
+    to: eth::Address,
+    amount: eth::TokenAmount,
+) -> eth::Interaction {
+    let mut amount_bytes = [0u8; 32];
+    let selector = hex_literal::hex!("23b872dd");
+    amount.0.to_big_endian(&mut amount_bytes);
+    eth::Interaction {
+        target: token.into(),
+        value: eth::U256::zero().into(),
+        // selector (4 bytes) + from and to (20 byte addresses padded to 32 bytes) + amount (32
+        // bytes)
+        call_data: [
+            selector.as_slice(),
+            [0; 12].as_slice(),
+            from.0.as_bytes(),
+            [0; 12].as_slice(),
+            to.0.as_bytes(),
+            &amount_bytes,
+        ]
+        .concat()
+        .into(),
+    }
+}
+
 fn unwrap(amount: eth::TokenAmount, weth: &contracts::WETH9) -> eth::Interaction {
     let tx = weth.withdraw(amount.into()).into_inner();
     eth::Interaction {
@@ -426,4 +553,19 @@ mod test {
         );
         assert_eq!(interaction.call_data.0.as_slice(), hex!("095ea7b3000000000000000000000000000000000022d473030f116ddee9f6b43ac78ba3ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"));
     }
+
+    #[test]
+    fn test_transfer_from() {
+        let interaction = transfer_from(
+            eth::H160::from_slice(&hex!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2")).into(),
+            eth::H160::from_low_u64_be(1).into(),
+            eth::H160::from_low_u64_be(2).into(),
+            eth::U256::from(3).into(),
+        );
+        assert_eq!(
+            interaction.target,
+            eth::H160::from_slice(&hex!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2")).into(),
+        );
+        assert_eq!(interaction.call_data.0.as_slice(), hex!("23b872dd000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000003"));
+    }
 }
@@ -29,8 +29,8 @@ use {
     },
     crate::domain::{
         competition::{
-            order,
-            order::{FeePolicy, Side},
+            auction,
+            order::{self, fees, FeePolicy, Side},
             solution::error::Trade,
             PriceLimits,
         },
@@ -42,10 +42,15 @@ use {
 impl Fulfillment {
     /// Applies the protocol fees to the existing fulfillment creating a new
     /// one.
-    pub fn with_protocol_fees(&self, prices: ClearingPrices) -> Result<Self, Error> {
+    pub fn with_protocol_fees(
+        &self,
+        prices: ClearingPrices,
+        native_prices: &auction::Prices,
+    ) -> Result<Self, Error> {
         let mut current_fulfillment = self.clone();
         for protocol_fee in &self.order().protocol_fees {
-            current_fulfillment = current_fulfillment.with_protocol_fee(prices, protocol_fee)?;
+            current_fulfillment =
+                current_fulfillment.with_protocol_fee(prices, native_prices, protocol_fee)?;
         }
         Ok(current_fulfillment)
     }
@@ -54,9 +59,10 @@ impl Fulfillment {
     fn with_protocol_fee(
         &self,
         prices: ClearingPrices,
+        native_prices: &auction::Prices,
         protocol_fee: &FeePolicy,
     ) -> Result<Self, Error> {
-        let protocol_fee = self.protocol_fee_in_sell_token(prices, protocol_fee)?;
+        let protocol_fee = self.protocol_fee_in_sell_token(prices, native_prices, protocol_fee)?;
 
         // Increase the fee by the protocol fee
         let fee = match self.surplus_fee() {
@@ -92,6 +98,7 @@ impl Fulfillment {
     fn protocol_fee(
         &self,
         prices: ClearingPrices,
+        native_prices: &auction::Prices,
         protocol_fee: &FeePolicy,
     ) -> Result<eth::TokenAmount, Error> {
         match protocol_fee {
@@ -131,9 +138,37 @@ impl Fulfillment {
                 tracing::debug!(uid=?self.order().uid, ?fee_from_volume, executed=?self.executed(), surplus_fee=?self.surplus_fee(), "calculated protocol fee");
                 Ok(fee_from_volume)
             }
+            FeePolicy::TieredVolume { tiers } => {
+                let notional = self.native_notional(native_prices)?;
+                let factor = fees::tiered_volume_factor(tiers, notional);
+                let fee_from_volume = self.fee_from_volume(prices, factor)?;
+                tracing::debug!(uid=?self.order().uid, ?notional, ?factor, ?fee_from_volume, executed=?self.executed(), surplus_fee=?self.surplus_fee(), "calculated protocol fee");
+                Ok(fee_from_volume)
+            }
         }
     }
 
+    /// The executed volume in the order's target token including all fees,
+    /// which doesn't change when protocol fees are applied.
+    ///
+    /// Denominated in NATIVE token
+    fn native_notional(&self, native_prices: &auction::Prices) -> Result<eth::Ether, Error> {
+        let (token, amount) = match self.order().side {
+            Side::Sell => (
+                self.order().sell.token,
+                self.executed()
+                    .0
+                    .checked_add(self.fee().0)
+                    .ok_or(Math::Overflow)?,
+            ),
+            Side::Buy => (self.order().buy.token, self.executed().0),
+        };
+        let price = native_prices
+            .get(&token)
+            .ok_or(Error::MissingPrice(token))?;
+        Ok(price.in_eth(amount.into()))
+    }
+
     /// Computes protocol fee compared to the given limit amounts taken from
     /// the order or a quote.
     ///
@@ -192,12 +227,13 @@ impl Fulfillment {
     fn protocol_fee_in_sell_token(
         &self,
         prices: ClearingPrices,
+        native_prices: &auction::Prices,
         protocol_fee: &FeePolicy,
     ) -> Result<eth::TokenAmount, Error> {
         let fee_in_sell_token = match self.order().side {
-            Side::Buy => self.protocol_fee(prices, protocol_fee)?,
+            Side::Buy => self.protocol_fee(prices, native_prices, protocol_fee)?,
             Side::Sell => self
-                .protocol_fee(prices, protocol_fee)?
+                .protocol_fee(prices, native_prices, protocol_fee)?
                 .0
                 .checked_mul(prices.buy)
                 .ok_or(Math::Overflow)?
@@ -290,6 +326,8 @@ pub fn adjust_quote_to_order_limits(order: Order, quote: Quote) -> Result<PriceL
 pub enum Error {
     #[error("orders with non solver determined gas cost fees are not supported")]
     ProtocolFeeOnStaticOrder,
+    #[error("missing native price for token {0:?}")]
+    MissingPrice(eth::TokenAddress),
     #[error(transparent)]
     Math(#[from] Math),
     #[error(transparent)]
@@ -0,0 +1,58 @@
+use crate::domain::eth;
+
+/// A flashloan taken out for the duration of a settlement. The borrowed tokens
+/// get transferred to the borrower before the settlement's pre-interactions
+/// and the lender gets repaid the borrowed amount plus its fee after the
+/// settlement's post-interactions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Flashloan {
+    /// The contract lending the tokens.
+    pub lender: eth::ContractAddress,
+    /// The account receiving the borrowed tokens.
+    pub borrower: eth::Address,
+    pub token: eth::TokenAddress,
+    pub amount: eth::TokenAmount,
+    /// The fee charged by the lender, repaid on top of the borrowed amount.
+    pub fee: eth::TokenAmount,
+}
+
+impl Flashloan {
+    /// The fee charged by a lender with a fee in basis points for lending the
+    /// specified amount. The fee is rounded up in favour of the lender.
+    /// Returns `None` on overflow.
+    pub fn fee(amount: eth::TokenAmount, fee_bps: u32) -> Option<eth::TokenAmount> {
+        let fee = amount
+            .0
+            .checked_mul(fee_bps.into())?
+            .checked_add(eth::U256::from(9_999))?
+            / eth::U256::from(10_000);
+        Some(fee.into())
+    }
+
+    /// The amount that needs to be repaid to the lender. Returns `None` on
+    /// overflow.
+    pub fn repayment(&self) -> Option<eth::TokenAmount> {
+        Some(self.amount.0.checked_add(self.fee.0)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repayment_rounds_fee_up() {
+        let amount = eth::U256::from(1_000_001).into();
+        assert_eq!(Flashloan::fee(amount, 0), Some(eth::U256::zero().into()));
+        assert_eq!(Flashloan::fee(amount, 9), Some(eth::U256::from(901).into()));
+
+        let loan = Flashloan {
+            lender: eth::H160::from_low_u64_be(1).into(),
+            borrower: eth::H160::from_low_u64_be(2).into(),
+            token: eth::H160::from_low_u64_be(3).into(),
+            amount,
+            fee: Flashloan::fee(amount, 9).unwrap(),
+        };
+        assert_eq!(loan.repayment(), Some(eth::U256::from(1_000_902).into()));
+    }
+}
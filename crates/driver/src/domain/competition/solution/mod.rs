@@ -6,12 +6,13 @@ use {
         domain::{
             competition::{self, order},
             eth::{self, TokenAddress},
+            liquidity,
         },
         infra::{
             blockchain::{self, Ethereum},
             config::file::FeeHandler,
             simulator,
-            solver::{dto::Flashloan, ManageNativeToken, Solver},
+            solver::{ManageNativeToken, Solver},
             Simulator,
         },
     },
@@ -28,16 +29,33 @@ use {
 
 pub mod encoding;
 pub mod fee;
+pub mod flashloan;
 pub mod interaction;
 pub mod scoring;
 pub mod settlement;
 pub mod slippage;
 pub mod trade;
 
-pub use {error::Error, interaction::Interaction, settlement::Settlement, trade::Trade};
+pub use {
+    error::Error,
+    flashloan::Flashloan,
+    interaction::Interaction,
+    settlement::Settlement,
+    trade::Trade,
+};
 
 type Prices = HashMap<eth::TokenAddress, eth::U256>;
 
+/// State that gets changed by the interactions of a solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum State {
+    Liquidity(liquidity::Id),
+    /// A contract whose state may be changed. The same pool can be indexed by
+    /// multiple liquidity sources and custom interactions can call any
+    /// contract, so they get compared by address.
+    Contract(eth::Address),
+}
+
 // TODO Add a constructor and ensure that the clearing prices are included for
 // each trade
 /// A solution represents a set of orders which the solver has found an optimal
@@ -71,6 +89,7 @@ impl Solution {
         gas: Option<eth::Gas>,
         fee_handler: FeeHandler,
         surplus_capturing_jit_order_owners: &HashSet<eth::Address>,
+        native_prices: &auction::Prices,
         flashloans: Vec<Flashloan>,
     ) -> Result<Self, error::Solution> {
         // Surplus capturing JIT orders behave like Fulfillment orders. They capture
@@ -153,7 +172,7 @@ impl Solution {
                         buy: solution.prices
                             [&fulfillment.order().buy.token.as_erc20(solution.weth)],
                     };
-                    let fulfillment = fulfillment.with_protocol_fees(prices)?;
+                    let fulfillment = fulfillment.with_protocol_fees(prices, native_prices)?;
                     trades.push(Trade::Fulfillment(fulfillment))
                 }
                 Trade::Jit(_) => trades.push(trade),
@@ -207,6 +226,11 @@ impl Solution {
         self.gas
     }
 
+    /// Flashloans taken out for the duration of this solution's settlement.
+    pub fn flashloans(&self) -> &[Flashloan] {
+        &self.flashloans
+    }
+
     fn trade_count_for_scorable(
         &self,
         trade: &Trade,
@@ -257,7 +281,23 @@ impl Solution {
         }
 
         let scoring = scoring::Scoring::new(trades);
-        scoring.score(prices).map_err(error::Scoring::from)
+        let score = scoring.score(prices).map_err(error::Scoring::from)?;
+
+        // The lender fees of flashloans are paid out of the settlement.
+        let mut flashloan_fees = eth::U256::zero();
+        for flashloan in self.flashloans.iter().filter(|f| !f.fee.0.is_zero()) {
+            let price = prices
+                .get(&flashloan.token)
+                .ok_or(error::Scoring::MissingPrice(flashloan.token))?;
+            flashloan_fees = flashloan_fees
+                .checked_add(price.in_eth(flashloan.fee).0)
+                .ok_or(error::Math::Overflow)?;
+        }
+        Ok(score
+            .0
+            .checked_sub(flashloan_fees)
+            .ok_or(error::Math::Negative)?
+            .into())
     }
 
     /// Approval interactions necessary for encoding the settlement.
@@ -296,13 +336,28 @@ impl Solution {
             return Err(error::Merge::Incompatible("Solvers"));
         }
 
-        // Solutions should not settle the same order twice
-        let uids: HashSet<_> = self.user_trades().map(|t| t.order().uid).collect();
-        let other_uids: HashSet<_> = other.user_trades().map(|t| t.order().uid).collect();
+        // A settlement can only be wrapped in a single flashloan
+        if !self.flashloans.is_empty() && !other.flashloans.is_empty() {
+            return Err(error::Merge::Incompatible("Flashloans"));
+        }
+
+        // Solutions should not settle the same order twice. This includes JIT
+        // orders, which can't be filled twice either.
+        let uids: HashSet<_> = self.trades.iter().map(|t| t.uid()).collect();
+        let other_uids: HashSet<_> = other.trades.iter().map(|t| t.uid()).collect();
         if !uids.is_disjoint(&other_uids) {
             return Err(error::Merge::DuplicateTrade);
         }
 
+        // Solutions should not change the state of the same liquidity or
+        // contracts, since the amounts computed by one solution would no longer
+        // hold once the other solution's interactions have been executed.
+        if !touched_state(&self.interactions, &self.prices)
+            .is_disjoint(&touched_state(&other.interactions, &other.prices))
+        {
+            return Err(error::Merge::SharedLiquidity);
+        }
+
         // Solution prices need to be congruent, i.e. there needs to be a unique factor
         // to scale all common tokens from one solution into the other.
         let factor =
@@ -359,7 +414,7 @@ impl Solution {
                 (None, Some(gas)) => Some(gas),
                 (None, None) => None,
             },
-            flashloans: self.flashloans.clone(),
+            flashloans: [self.flashloans.clone(), other.flashloans.clone()].concat(),
         })
     }
 
@@ -492,11 +547,54 @@ impl std::fmt::Debug for Solution {
             .field("pre_interactions", &self.pre_interactions)
             .field("interactions", &self.interactions)
             .field("post_interactions", &self.post_interactions)
+            .field("flashloans", &self.flashloans)
             .field("solver", &self.solver.name())
             .finish()
     }
 }
 
+/// The state that gets changed by the given interactions. Since the effects of
+/// custom interactions are unknown, their target and the spenders of their
+/// allowances are assumed to be changed. Calls to the traded tokens, like
+/// transfers, are the exception since the clearing prices already account for
+/// the token balances.
+fn touched_state(interactions: &[Interaction], prices: &Prices) -> HashSet<State> {
+    interactions
+        .iter()
+        .flat_map(|interaction| -> Vec<State> {
+            let liquidity = match interaction {
+                Interaction::Liquidity(interaction) => &interaction.liquidity,
+                Interaction::Custom(custom) => {
+                    let target = eth::Address::from(custom.target);
+                    let traded = prices.contains_key(&eth::TokenAddress::from(target.0));
+                    return (!traded)
+                        .then_some(target)
+                        .into_iter()
+                        .chain(
+                            custom
+                                .allowances
+                                .iter()
+                                .map(|allowance| allowance.0.spender),
+                        )
+                        .map(State::Contract)
+                        .collect();
+                }
+            };
+            let pool = match &liquidity.kind {
+                liquidity::Kind::UniswapV2(pool) => Some(pool.address),
+                liquidity::Kind::UniswapV3(pool) => Some(pool.address.into()),
+                liquidity::Kind::BalancerV2Stable(pool) => Some(pool.id.address().into()),
+                liquidity::Kind::BalancerV2Weighted(pool) => Some(pool.id.address().into()),
+                liquidity::Kind::Swapr(pool) => Some(pool.base.address),
+                liquidity::Kind::ZeroEx(_) => None,
+            };
+            std::iter::once(State::Liquidity(liquidity.id))
+                .chain(pool.map(State::Contract))
+                .collect()
+        })
+        .collect()
+}
+
 /// Given two solutions returns the factors with
 /// which prices of the second solution would have to be multiplied so that the
 /// given token would have the same price in both solutions.
@@ -587,6 +685,8 @@ pub mod error {
         DuplicateTrade,
         #[error("incongruent prices")]
         IncongruentPrices,
+        #[error("interactions change the state of the same liquidity")]
+        SharedLiquidity,
         #[error("math error: {0:?}")]
         Math(anyhow::Error),
     }
@@ -673,6 +773,83 @@ pub mod error {
 mod tests {
     use super::*;
 
+    fn swap(id: usize, pool: u64) -> Interaction {
+        let asset = |token: u64| eth::Asset {
+            token: eth::H160::from_low_u64_be(token).into(),
+            amount: 1000u128.into(),
+        };
+        Interaction::Liquidity(interaction::Liquidity {
+            liquidity: liquidity::Liquidity {
+                id: liquidity::Id(id),
+                gas: eth::Gas(100_000u64.into()),
+                kind: liquidity::Kind::UniswapV2(liquidity::uniswap::v2::Pool {
+                    address: eth::H160::from_low_u64_be(pool).into(),
+                    router: eth::H160::from_low_u64_be(0xdead).into(),
+                    reserves: liquidity::uniswap::v2::Reserves::try_new(asset(1), asset(2))
+                        .unwrap(),
+                }),
+            },
+            input: asset(1),
+            output: asset(2),
+            internalize: false,
+        })
+    }
+
+    fn custom(target: u64, spenders: &[u64]) -> Interaction {
+        Interaction::Custom(interaction::Custom {
+            target: eth::H160::from_low_u64_be(target).into(),
+            value: 0.into(),
+            call_data: Default::default(),
+            allowances: spenders
+                .iter()
+                .map(|&spender| {
+                    eth::allowance::Required(eth::allowance::Allowance {
+                        token: eth::H160::from_low_u64_be(1).into(),
+                        spender: eth::H160::from_low_u64_be(spender).into(),
+                        amount: 1000.into(),
+                    })
+                })
+                .collect(),
+            inputs: Default::default(),
+            outputs: Default::default(),
+            internalize: false,
+        })
+    }
+
+    fn conflict(first: &[Interaction], second: &[Interaction]) -> bool {
+        let prices = Prices::from([(eth::H160::from_low_u64_be(1).into(), 1.into())]);
+        !touched_state(first, &prices).is_disjoint(&touched_state(second, &prices))
+    }
+
+    #[test]
+    fn shared_liquidity_conflicts() {
+        // The same liquidity.
+        assert!(conflict(&[swap(1, 10)], &[swap(1, 10)]));
+        // Different liquidity for the same pool.
+        assert!(conflict(&[swap(1, 10)], &[swap(2, 10), swap(3, 11)]));
+        // Different pools.
+        assert!(!conflict(&[swap(1, 10)], &[swap(2, 11)]));
+    }
+
+    #[test]
+    fn custom_interaction_conflicts() {
+        // The same target.
+        assert!(conflict(&[custom(10, &[])], &[custom(10, &[])]));
+        // A target that the other solution swaps through.
+        assert!(conflict(&[swap(1, 10)], &[custom(10, &[])]));
+        // A target that the other solution approves.
+        assert!(conflict(&[custom(10, &[])], &[custom(11, &[10])]));
+        // The same spender.
+        assert!(conflict(&[custom(10, &[12])], &[custom(11, &[12])]));
+        // Calls to a traded token.
+        assert!(!conflict(&[custom(1, &[])], &[custom(1, &[])]));
+        // Different contracts.
+        assert!(!conflict(
+            &[custom(10, &[12])],
+            &[custom(11, &[13]), swap(1, 14)]
+        ));
+    }
+
     /// Tests that constructor ensures unique ids.
     #[test]
     fn solution_id_unique() {
@@ -16,7 +16,7 @@ use {
         domain::{
             competition::{
                 auction,
-                order::FeePolicy,
+                order::{fees, FeePolicy},
                 solution::{
                     error,
                     fee::{self, adjust_quote_to_order_limits},
@@ -172,12 +172,12 @@ impl Trade {
     /// Protocol fees are defined by fee policies attached to the order.
     ///
     /// Denominated in SURPLUS token
-    fn protocol_fees(&self) -> Result<Vec<eth::Asset>, Error> {
+    fn protocol_fees(&self, prices: &auction::Prices) -> Result<Vec<eth::Asset>, Error> {
         let mut current_trade = self.clone();
         let mut total = eth::TokenAmount::default();
         let mut fees = vec![];
         for (i, protocol_fee) in self.policies.iter().enumerate().rev() {
-            let fee = current_trade.protocol_fee(protocol_fee)?;
+            let fee = current_trade.protocol_fee(protocol_fee, prices)?;
             // Do not need to calculate the last custom prices because in the last iteration
             // the prices are not used anymore to calculate the protocol fee
             fees.push(fee);
@@ -258,7 +258,11 @@ impl Trade {
     /// Protocol fee is defined by a fee policy attached to the order.
     ///
     /// Denominated in SURPLUS token
-    fn protocol_fee(&self, fee_policy: &FeePolicy) -> Result<eth::Asset, Error> {
+    fn protocol_fee(
+        &self,
+        fee_policy: &FeePolicy,
+        prices: &auction::Prices,
+    ) -> Result<eth::Asset, Error> {
         let amount = match fee_policy {
             FeePolicy::Surplus {
                 factor,
@@ -282,6 +286,10 @@ impl Trade {
                 )
             }
             FeePolicy::Volume { factor } => self.volume_fee(*factor)?.amount,
+            FeePolicy::TieredVolume { tiers } => {
+                let factor = fees::tiered_volume_factor(tiers, self.native_notional(prices)?);
+                self.volume_fee(factor)?.amount
+            }
         };
         Ok(eth::Asset {
             token: self.surplus_token(),
@@ -401,11 +409,25 @@ impl Trade {
         })
     }
 
+    /// The executed volume in the order's target token, which is the sell token
+    /// for sell orders and the buy token for buy orders. Unlike the volume in
+    /// surplus token, it doesn't change when protocol fees are applied.
+    ///
+    /// Denominated in NATIVE token
+    fn native_notional(&self, prices: &auction::Prices) -> Result<eth::Ether, Error> {
+        let token = match self.side {
+            Side::Sell => self.sell.token,
+            Side::Buy => self.buy.token,
+        };
+        let price = prices.get(&token).ok_or(Error::MissingPrice(token))?;
+        Ok(price.in_eth(self.executed.0.into()))
+    }
+
     /// Protocol fee is defined by fee policies attached to the order.
     ///
     /// Denominated in NATIVE token
     fn native_protocol_fee(&self, prices: &auction::Prices) -> Result<eth::Ether, Error> {
-        self.protocol_fees()?
+        self.protocol_fees(prices)?
             .into_iter()
             .map(|fee| {
                 let price = prices
@@ -433,3 +455,76 @@ pub enum Error {
     #[error("scoring: failed to calculate custom price for the applied fee policy {0:?}")]
     Scoring(#[source] error::Scoring),
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::domain::competition::order::fees::VolumeTier};
+
+    fn ether(amount: f64) -> eth::U256 {
+        eth::U256::from_f64_lossy(amount * 1e18)
+    }
+
+    #[test]
+    fn tiered_volume_fee_depends_on_notional() {
+        let sell_token = eth::TokenAddress::from(eth::H160([1; 20]));
+        let buy_token = eth::TokenAddress::from(eth::H160([2; 20]));
+        // Both tokens are worth 1 ETH.
+        let prices = auction::Prices::from([
+            (
+                sell_token,
+                auction::Price::try_new(ether(1.).into()).unwrap(),
+            ),
+            (
+                buy_token,
+                auction::Price::try_new(ether(1.).into()).unwrap(),
+            ),
+        ]);
+        let tiers = vec![
+            VolumeTier {
+                min_notional: 0.into(),
+                factor: 0.5,
+            },
+            VolumeTier {
+                min_notional: ether(50.).into(),
+                factor: 0.2,
+            },
+        ];
+        // A partially fillable sell order with a limit price of 0.9 that is
+        // executed at a price of 0.95.
+        let score = |executed: f64| {
+            let trade = Trade::new(
+                eth::Asset {
+                    token: sell_token,
+                    amount: ether(100.).into(),
+                },
+                eth::Asset {
+                    token: buy_token,
+                    amount: ether(90.).into(),
+                },
+                Side::Sell,
+                order::TargetAmount(ether(executed)),
+                CustomClearingPrices {
+                    sell: 95.into(),
+                    buy: 100.into(),
+                },
+                vec![FeePolicy::TieredVolume {
+                    tiers: tiers.clone(),
+                }],
+            );
+            Scoring::new(vec![trade]).score(&prices).unwrap().0
+        };
+        let assert_approx = |actual: eth::U256, expected: eth::U256| {
+            let diff = if actual > expected {
+                actual - expected
+            } else {
+                expected - actual
+            };
+            assert!(diff < ether(1e-9), "{actual} != {expected}");
+        };
+
+        // 2 ETH surplus and a fee of half the 76 ETH volume before the fee.
+        assert_approx(score(40.), ether(40.));
+        // 2.5 ETH surplus and a fee of 20% of the 59.375 ETH volume before the fee.
+        assert_approx(score(50.), ether(14.375));
+    }
+}
@@ -41,6 +41,14 @@ impl Pool {
             self, input, output, receiver,
         ))
     }
+
+    /// Returns `true` if this is a composable stable pool. These pools hold
+    /// their own pool token (BPT) in their reserves, which can't be swapped
+    /// like the pool's other tokens.
+    pub fn is_composable(&self) -> bool {
+        let bpt = eth::TokenAddress(self.id.address());
+        self.reserves.tokens().any(|token| token == bpt)
+    }
 }
 
 /// Balancer stable pool reserves.
@@ -14,7 +14,10 @@ use {
 /// - Liquidity Bootstrapping Pools [^3]
 ///
 /// Both of these pools have an identical representation, and are therefore
-/// modelled by the same type.
+/// modelled by the same type. The weights of liquidity bootstrapping pools
+/// change over time, their reserves hold the weights at the block the
+/// liquidity was fetched at and their [`WeightUpdate`] how they change from
+/// there.
 ///
 /// [^1]: <https://docs.balancer.fi/concepts/math/weighted-math>
 /// [^2]: <https://docs.balancer.fi/products/balancer-pools/weighted-pools>
@@ -26,6 +29,9 @@ pub struct Pool {
     pub reserves: Reserves,
     pub fee: Fee,
     pub version: Version,
+    /// The weight update of liquidity bootstrapping pools, `None` for regular
+    /// weighted pools.
+    pub weight_update: Option<WeightUpdate>,
 }
 
 impl Pool {
@@ -114,6 +120,20 @@ pub struct Reserve {
     pub weight: Weight,
 }
 
+/// The gradual weight update of a liquidity bootstrapping pool. The token
+/// weights change linearly from the reserve weights at `start` to the end
+/// weights at `end`, and stay constant outside of that period.
+#[derive(Clone, Debug)]
+pub struct WeightUpdate {
+    /// The Unix timestamp in seconds at which the weights start changing.
+    pub start: u64,
+    /// The Unix timestamp in seconds at which the weights reach their end
+    /// values.
+    pub end: u64,
+    /// The end weights, in the same order as the pool reserves.
+    pub end_weights: Vec<Weight>,
+}
+
 /// A Balancer token weight.
 #[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub struct Weight(pub eth::U256);
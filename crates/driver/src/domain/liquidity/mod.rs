@@ -21,7 +21,7 @@ pub struct Liquidity {
     pub kind: Kind,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, From, Into)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, From, Into)]
 pub struct Id(pub usize);
 
 impl PartialEq<usize> for Id {
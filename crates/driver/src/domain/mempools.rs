@@ -15,6 +15,7 @@ use {
     ethrpc::block_stream::into_stream,
     futures::{future::select_ok, FutureExt, StreamExt},
     thiserror::Error,
+    tokio::sync::watch,
     tracing::Instrument,
 };
 
@@ -41,18 +42,37 @@ impl Mempools {
         }
     }
 
-    /// Publish a settlement to the mempools.
+    /// Whether pending settlements can be cancelled. Bundles can't be recalled
+    /// once they were sent to the relays, so this requires at least one mempool
+    /// that doesn't submit bundles.
+    pub fn cancellable(&self) -> bool {
+        self.mempools
+            .iter()
+            .any(|mempool| !mempool.submits_bundles())
+    }
+
+    /// Publish a settlement to the mempools. The pending transaction gets
+    /// cancelled as soon as `cancellation` is set, but the settlement is only
+    /// reported as cancelled once it can no longer be included.
     pub async fn execute(
         &self,
         solver: &Solver,
         settlement: &Settlement,
         submission_deadline: BlockNo,
+        cancellation: watch::Receiver<bool>,
     ) -> Result<eth::TxId, Error> {
         let (tx_hash, _remaining_futures) =
             select_ok(self.mempools.iter().cloned().map(|mempool| {
+                let cancellation = cancellation.clone();
                 async move {
                     let result = self
-                        .submit(&mempool, solver, settlement, submission_deadline)
+                        .submit(
+                            &mempool,
+                            solver,
+                            settlement,
+                            submission_deadline,
+                            cancellation,
+                        )
                         .instrument(tracing::info_span!("mempool", kind = mempool.to_string()))
                         .await;
                     observe::mempool_executed(&mempool, settlement, &result);
@@ -89,7 +109,12 @@ impl Mempools {
         solver: &Solver,
         settlement: &Settlement,
         submission_deadline: BlockNo,
+        mut cancellation: watch::Receiver<bool>,
     ) -> Result<eth::TxId, Error> {
+        if *cancellation.borrow() {
+            return Err(Error::Cancelled);
+        }
+
         // Don't submit risky transactions if revert protection is
         // enabled and the settlement may revert in this mempool.
         if settlement.may_revert()
@@ -128,9 +153,32 @@ impl Mempools {
         let hash = mempool.submit(tx.clone(), settlement.gas, solver).await?;
         tracing::debug!(?hash, "submitted tx to the mempool");
 
-        // Wait for the transaction to be mined, expired or failing.
+        // Set once a cancellation was requested, holding the cancellation tx. Bundles
+        // get cancelled by no longer resubmitting them, so they have none.
+        let mut cancelled: Option<Option<TxId>> = None;
+
+        // Wait for the transaction to be mined, expired, failing or cancelled.
         let result = async {
-            while let Some(block) = block_stream.next().await {
+            loop {
+                let block = tokio::select! {
+                    block = block_stream.next() => match block {
+                        Some(block) => block,
+                        None => break,
+                    },
+                    Ok(_) = cancellation.wait_for(|cancelled| *cancelled), if cancelled.is_none() => {
+                        let cancellation_tx_hash = self
+                            .cancel(mempool, settlement.gas.price, solver)
+                            .await
+                            .context("cancellation tx due to cancel request failed")?;
+                        tracing::info!(
+                            settle_tx_hash = ?hash,
+                            ?cancellation_tx_hash,
+                            "settlement cancellation requested, cancelling",
+                        );
+                        cancelled = Some(cancellation_tx_hash);
+                        continue;
+                    }
+                };
                 tracing::debug!(?hash, "checking if tx is confirmed");
                 let receipt = self
                     .ethereum
@@ -149,6 +197,22 @@ impl Mempools {
                         })
                     }
                     TxStatus::Pending => {
+                        // A cancelled settlement may still get included until the cancellation
+                        // tx takes its nonce, or until the block targeted by the last bundle.
+                        if let Some(cancellation_tx_hash) = &cancelled {
+                            let superseded = match cancellation_tx_hash {
+                                Some(cancellation_tx_hash) => !matches!(
+                                    self.ethereum.transaction_status(cancellation_tx_hash).await,
+                                    Ok(TxStatus::Pending) | Err(_)
+                                ),
+                                None => true,
+                            };
+                            if superseded {
+                                return Err(Error::Cancelled);
+                            }
+                            tracing::debug!(?hash, "waiting for the cancellation tx");
+                            continue;
+                        }
                         // Check if the current block reached the submission deadline block number
                         if block.number >= submission_deadline {
                             let cancellation_tx_hash = self
@@ -182,6 +246,15 @@ impl Mempools {
                                 tracing::warn!(?hash, ?err, "couldn't re-simulate tx");
                             }
                         }
+                        // Bundles only target a single block, so they need to be resubmitted
+                        // for every block until they get included.
+                        if mempool.submits_bundles() {
+                            if let Err(err) =
+                                mempool.submit(tx.clone(), settlement.gas, solver).await
+                            {
+                                tracing::warn!(?hash, ?err, "failed to resubmit bundle");
+                            }
+                        }
                     }
                 }
             }
@@ -203,13 +276,19 @@ impl Mempools {
     }
 
     /// Cancel a pending settlement by sending a transaction to self with a
-    /// slightly higher gas price than the existing one.
+    /// slightly higher gas price than the existing one. Bundles are cancelled
+    /// by no longer resubmitting them, so no cancellation transaction is sent
+    /// for them.
     async fn cancel(
         &self,
         mempool: &infra::mempool::Mempool,
         pending: eth::GasPrice,
         solver: &Solver,
-    ) -> Result<TxId, Error> {
+    ) -> Result<Option<TxId>, Error> {
+        if mempool.submits_bundles() {
+            return Ok(None);
+        }
+
         let cancellation = eth::Tx {
             from: solver.address(),
             to: solver.address(),
@@ -222,7 +301,7 @@ impl Mempools {
             limit: CANCELLATION_GAS_AMOUNT.into(),
             price: pending * GAS_PRICE_BUMP,
         };
-        mempool.submit(cancellation, gas, solver).await
+        mempool.submit(cancellation, gas, solver).await.map(Some)
     }
 }
 
@@ -251,6 +330,8 @@ pub enum Error {
     Expired,
     #[error("Strategy disabled for this tx")]
     Disabled,
+    #[error("Settlement got cancelled")]
+    Cancelled,
     #[error("Failed to submit: {0:?}")]
     Other(#[from] anyhow::Error),
 }
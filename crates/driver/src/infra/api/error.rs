@@ -21,6 +21,8 @@ enum Kind {
     InvalidAmounts,
     QuoteSameTokens,
     FailedToSubmit,
+    Reverted,
+    Cancelled,
 }
 
 #[derive(Debug, Serialize)]
@@ -52,7 +54,9 @@ impl From<Kind> for (hyper::StatusCode, axum::Json<Error>) {
                  or sell amount"
             }
             Kind::FailedToSubmit => "Could not submit the solution to the blockchain",
+            Kind::Reverted => "The settlement transaction reverted",
             Kind::TooManyPendingSettlements => "Settlement queue is full",
+            Kind::Cancelled => "The settlement was cancelled",
         };
         (
             hyper::StatusCode::BAD_REQUEST,
@@ -85,7 +89,9 @@ impl From<competition::Error> for (hyper::StatusCode, axum::Json<Error>) {
             competition::Error::DeadlineExceeded(_) => Kind::DeadlineExceeded,
             competition::Error::Solver(_) => Kind::SolverFailed,
             competition::Error::SubmissionError => Kind::FailedToSubmit,
+            competition::Error::Reverted => Kind::Reverted,
             competition::Error::TooManyPendingSettlements => Kind::TooManyPendingSettlements,
+            competition::Error::Cancelled => Kind::Cancelled,
         };
         error.into()
     }
@@ -112,3 +118,31 @@ impl From<api::routes::OrderError> for (hyper::StatusCode, axum::Json<Error>) {
         error.into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::domain::{eth, mempools},
+    };
+
+    /// The autopilot relies on the error kind to tell reverted settlements
+    /// apart from other submission failures.
+    #[test]
+    fn reverted_settlement_kind() {
+        let error = competition::Error::from(mempools::Error::Revert {
+            tx_id: eth::TxId(Default::default()),
+            block_number: 1,
+        });
+        let (status, axum::Json(error)) = error.into();
+        assert_eq!(status, hyper::StatusCode::BAD_REQUEST);
+        assert_eq!(serde_json::to_value(error).unwrap()["kind"], "Reverted");
+
+        let error = competition::Error::from(mempools::Error::Expired);
+        let (_, axum::Json(error)) = error.into();
+        assert_eq!(
+            serde_json::to_value(error).unwrap()["kind"],
+            "FailedToSubmit"
+        );
+    }
+}
@@ -15,8 +15,10 @@ use {
             Simulator,
         },
     },
+    anyhow::Context,
     error::Error,
     futures::Future,
+    itertools::Itertools,
     std::{net::SocketAddr, sync::Arc},
     tokio::sync::oneshot,
 };
@@ -24,6 +26,8 @@ use {
 mod error;
 mod routes;
 
+pub use routes::{ArchivedAuction, ReplayReport};
+
 const REQUEST_BODY_LIMIT: usize = 10 * 1024 * 1024;
 
 pub struct Api {
@@ -34,6 +38,8 @@ pub struct Api {
     pub mempools: Mempools,
     pub addr: SocketAddr,
     pub bad_token_detector: bad_tokens::simulation::Detector,
+    /// Persists the state of the bad token detectors if set.
+    pub bad_token_store: Option<infra::bad_tokens::Store>,
     /// If this channel is specified, the bound address will be sent to it. This
     /// allows the driver to bind to 0.0.0.0:0 during testing.
     pub addr_sender: Option<oneshot::Sender<SocketAddr>>,
@@ -55,12 +61,7 @@ impl Api {
                 .layer(tower_http::trace::TraceLayer::new_for_http()),
         );
 
-        let tokens = tokens::Fetcher::new(&self.eth);
-        let pre_processor = domain::competition::AuctionProcessor::new(
-            &self.eth,
-            order_priority_strategies,
-            app_data_retriever,
-        );
+        let states = self.states(order_priority_strategies, app_data_retriever);
 
         // Add the metrics and healthz endpoints.
         app = routes::metrics(app);
@@ -70,47 +71,18 @@ impl Api {
         // on the same driver so only one liquidity collector collects the liquidity
         // for all of them. This is important because liquidity collection is
         // computationally expensive for the Ethereum node.
-        for solver in self.solvers {
-            let name = solver.name().clone();
+        for state in states {
+            let name = state.solver().name().clone();
             let router = axum::Router::new();
             let router = routes::info(router);
             let router = routes::quote(router);
             let router = routes::solve(router);
             let router = routes::reveal(router);
             let router = routes::settle(router);
+            let router = routes::cancel(router);
+            let router = routes::bad_tokens(router);
 
-            let bad_token_config = solver.bad_token_detection();
-            let mut bad_tokens =
-                bad_tokens::Detector::new(bad_token_config.tokens_supported.clone());
-            if bad_token_config.enable_simulation_strategy {
-                bad_tokens.with_simulation_detector(self.bad_token_detector.clone());
-            }
-
-            if bad_token_config.enable_metrics_strategy {
-                bad_tokens.with_metrics_detector(bad_tokens::metrics::Detector::new(
-                    bad_token_config.metrics_strategy_failure_ratio,
-                    bad_token_config.metrics_strategy_required_measurements,
-                    bad_token_config.metrics_strategy_log_only,
-                    bad_token_config.metrics_strategy_token_freeze_time,
-                    name.clone(),
-                ));
-            }
-
-            let router = router.with_state(State(Arc::new(Inner {
-                eth: self.eth.clone(),
-                solver: solver.clone(),
-                competition: domain::Competition::new(
-                    solver,
-                    self.eth.clone(),
-                    self.liquidity.clone(),
-                    self.simulator.clone(),
-                    self.mempools.clone(),
-                    Arc::new(bad_tokens),
-                ),
-                liquidity: self.liquidity.clone(),
-                tokens: tokens.clone(),
-                pre_processor: pre_processor.clone(),
-            })));
+            let router = router.with_state(state);
             let path = format!("/{name}");
             infra::observe::mounting_solver(&name, &path);
             app = app
@@ -129,6 +101,96 @@ impl Api {
         }
         server.with_graceful_shutdown(shutdown).await
     }
+
+    /// Replays an archived auction for the specified solver through the same
+    /// pipeline as the `/solve` route and reports the differences to the
+    /// originally reported response.
+    pub async fn replay(
+        self,
+        solver: &str,
+        auction: ArchivedAuction,
+        reported: Option<serde_json::Value>,
+        deadline: chrono::DateTime<chrono::Utc>,
+        order_priority_strategies: Vec<OrderPriorityStrategy>,
+        app_data_retriever: Option<AppDataRetriever>,
+    ) -> anyhow::Result<ReplayReport> {
+        let state = self
+            .states(order_priority_strategies, app_data_retriever)
+            .into_iter()
+            .find(|state| state.solver().name().as_str() == solver)
+            .with_context(|| format!("unknown solver {solver}"))?;
+        routes::replay(&state, auction, reported, deadline).await
+    }
+
+    /// Builds the state backing the routes of each solver.
+    fn states(
+        &self,
+        order_priority_strategies: Vec<OrderPriorityStrategy>,
+        app_data_retriever: Option<AppDataRetriever>,
+    ) -> Vec<State> {
+        let tokens = tokens::Fetcher::new(&self.eth);
+        let pre_processor = domain::competition::AuctionProcessor::new(
+            &self.eth,
+            order_priority_strategies,
+            app_data_retriever,
+        );
+
+        let states = self
+            .solvers
+            .iter()
+            .map(|solver| {
+                let bad_token_config = solver.bad_token_detection();
+                let mut bad_tokens =
+                    bad_tokens::Detector::new(bad_token_config.tokens_supported.clone());
+                if bad_token_config.enable_simulation_strategy {
+                    bad_tokens.with_simulation_detector(self.bad_token_detector.clone());
+                }
+
+                if bad_token_config.enable_metrics_strategy {
+                    bad_tokens.with_metrics_detector(bad_tokens::metrics::Detector::new(
+                        bad_token_config.metrics_strategy_failure_ratio,
+                        bad_token_config.metrics_strategy_required_measurements,
+                        bad_token_config.metrics_strategy_log_only,
+                        bad_token_config.metrics_strategy_token_freeze_time,
+                        solver.name().clone(),
+                    ));
+                }
+
+                State(Arc::new(Inner {
+                    eth: self.eth.clone(),
+                    solver: solver.clone(),
+                    competition: domain::Competition::new(
+                        solver.clone(),
+                        self.eth.clone(),
+                        self.liquidity.clone(),
+                        self.simulator.clone(),
+                        self.mempools.clone(),
+                        Arc::new(bad_tokens),
+                    ),
+                    liquidity: self.liquidity.clone(),
+                    tokens: tokens.clone(),
+                    pre_processor: pre_processor.clone(),
+                }))
+            })
+            .collect_vec();
+
+        if let Some(store) = &self.bad_token_store {
+            store.clone().spawn(
+                self.bad_token_detector.clone(),
+                states
+                    .iter()
+                    .map(|state| {
+                        (
+                            state.solver().name().clone(),
+                            state.competition().bad_tokens.clone(),
+                        )
+                    })
+                    .collect(),
+            );
+        }
+
+        states
+    }
 }
 
 #[derive(Clone)]
@@ -0,0 +1,52 @@
+use {
+    crate::{
+        domain::{competition::bad_tokens, eth},
+        infra::api::State,
+    },
+    serde::Deserialize,
+    std::time::{Duration, Instant},
+};
+
+pub(in crate::infra::api) fn bad_tokens(router: axum::Router<State>) -> axum::Router<State> {
+    router.route("/bad_tokens", axum::routing::post(route))
+}
+
+/// Marks a token as (un)supported for the solver at runtime, taking
+/// precedence over the configured and detected token qualities.
+async fn route(
+    state: axum::extract::State<State>,
+    req: axum::Json<OverrideRequest>,
+) -> hyper::StatusCode {
+    let token = eth::TokenAddress(eth::ContractAddress(req.token));
+    let quality = match req.quality {
+        Quality::Supported => bad_tokens::Quality::Supported,
+        Quality::Unsupported => bad_tokens::Quality::Unsupported,
+        Quality::Unknown => bad_tokens::Quality::Unknown,
+    };
+    state
+        .competition()
+        .bad_tokens
+        .set_override(token, quality, req.ttl, Instant::now());
+    tracing::info!(solver = %state.solver().name(), ?token, ?quality, ttl = ?req.ttl, "overrode token quality");
+    hyper::StatusCode::OK
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct OverrideRequest {
+    token: eth::H160,
+    /// Marking a token as unknown removes a previous override.
+    quality: Quality,
+    /// How long the override lasts, e.g. "1h". Lasts until it is replaced if
+    /// unset.
+    #[serde(default, with = "humantime_serde")]
+    ttl: Option<Duration>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum Quality {
+    Supported,
+    Unsupported,
+    Unknown,
+}
@@ -0,0 +1,10 @@
+use {serde::Deserialize, serde_with::serde_as};
+
+#[serde_as]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelRequest {
+    /// Auction ID whose settlement should be cancelled.
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    pub auction_id: i64,
+}
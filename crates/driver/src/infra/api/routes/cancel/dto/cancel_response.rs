@@ -0,0 +1,40 @@
+use {
+    crate::domain::{competition, eth},
+    serde::Serialize,
+    serde_with::skip_serializing_none,
+};
+
+impl CancelResponse {
+    pub fn new(cancellation: competition::Cancellation) -> Self {
+        let (status, tx_hash) = match cancellation {
+            competition::Cancellation::NotFound => (Status::NotFound, None),
+            competition::Cancellation::NotSubmitted => (Status::NotSubmitted, None),
+            competition::Cancellation::NotCancellable => (Status::NotCancellable, None),
+            competition::Cancellation::Cancelled => (Status::Cancelled, None),
+            competition::Cancellation::Settled(tx_id) => (Status::Settled, Some(tx_id.0)),
+            competition::Cancellation::Failed => (Status::Failed, None),
+        };
+        Self { status, tx_hash }
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelResponse {
+    status: Status,
+    /// The hash of the settlement transaction if it got executed before it
+    /// could be cancelled.
+    tx_hash: Option<eth::H256>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+enum Status {
+    NotFound,
+    NotSubmitted,
+    NotCancellable,
+    Cancelled,
+    Settled,
+    Failed,
+}
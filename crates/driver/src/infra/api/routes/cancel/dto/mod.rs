@@ -0,0 +1,4 @@
+mod cancel_request;
+mod cancel_response;
+
+pub use {cancel_request::CancelRequest, cancel_response::CancelResponse};
@@ -0,0 +1,34 @@
+mod dto;
+
+use {
+    crate::{
+        domain::competition::auction,
+        infra::{
+            api::{self, Error, State},
+            observe,
+        },
+    },
+    tracing::Instrument,
+};
+
+pub(in crate::infra::api) fn cancel(router: axum::Router<State>) -> axum::Router<State> {
+    router.route("/cancel", axum::routing::post(route))
+}
+
+async fn route(
+    state: axum::extract::State<State>,
+    req: axum::Json<dto::CancelRequest>,
+) -> Result<axum::Json<dto::CancelResponse>, (hyper::StatusCode, axum::Json<Error>)> {
+    let auction_id =
+        auction::Id::try_from(req.auction_id).map_err(api::routes::AuctionError::from)?;
+    let handle_request = async {
+        observe::cancelling();
+        let result = state.competition().cancel(auction_id).await;
+        observe::cancelled(state.solver().name(), &result);
+        Ok(axum::Json(dto::CancelResponse::new(result)))
+    };
+
+    handle_request
+        .instrument(tracing::info_span!("/cancel", solver = %state.solver().name(), %auction_id))
+        .await
+}
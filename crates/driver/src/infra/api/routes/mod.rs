@@ -1,3 +1,5 @@
+mod bad_tokens;
+mod cancel;
 mod healthz;
 mod info;
 mod metrics;
@@ -6,12 +8,15 @@ mod reveal;
 mod settle;
 mod solve;
 
+pub use solve::{ArchivedAuction, ReplayReport};
 pub(super) use {
+    bad_tokens::bad_tokens,
+    cancel::cancel,
     healthz::healthz,
     info::info,
     metrics::metrics,
     quote::{quote, OrderError},
     reveal::reveal,
     settle::settle,
-    solve::{solve, AuctionError},
+    solve::{replay, solve, AuctionError},
 };
@@ -1,7 +1,9 @@
+mod replay_report;
 mod solve_request;
 mod solve_response;
 
 pub use {
-    solve_request::{Error as AuctionError, SolveRequest},
+    replay_report::ReplayReport,
+    solve_request::{ArchivedAuction, Error as AuctionError, SolveRequest},
     solve_response::SolveResponse,
 };
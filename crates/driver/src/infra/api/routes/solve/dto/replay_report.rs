@@ -0,0 +1,126 @@
+use {
+    super::{solve_response, SolveResponse},
+    crate::{domain::eth, util::serialize},
+    serde::Serialize,
+    serde_with::serde_as,
+    std::collections::{BTreeMap, BTreeSet},
+};
+
+impl ReplayReport {
+    /// Compares the best solution of a replayed auction with the best solution
+    /// that was originally reported for it.
+    pub fn new(
+        auction_id: i64,
+        block: u64,
+        replayed: &SolveResponse,
+        reported: Option<&SolveResponse>,
+    ) -> Self {
+        let replayed = best(replayed);
+        let reported = reported.and_then(best);
+        let score_difference = match (replayed, reported) {
+            (Some(replayed), Some(reported)) if replayed.score >= reported.score => {
+                Some((replayed.score - reported.score).to_string())
+            }
+            (Some(replayed), Some(reported)) => {
+                Some(format!("-{}", reported.score - replayed.score))
+            }
+            _ => None,
+        };
+
+        let executed = |solution: Option<&solve_response::Solution>, uid: &OrderId| {
+            solution
+                .and_then(|solution| solution.orders.get(uid))
+                .map(|order| Executed {
+                    sell: order.executed_sell,
+                    buy: order.executed_buy,
+                })
+        };
+        let uids = replayed
+            .into_iter()
+            .chain(reported)
+            .flat_map(|solution| solution.orders.keys())
+            .collect::<BTreeSet<_>>();
+        let orders = uids
+            .into_iter()
+            .filter_map(|uid| {
+                let diff = OrderDiff {
+                    reported: executed(reported, uid),
+                    replayed: executed(replayed, uid),
+                };
+                (diff.reported != diff.replayed).then_some((*uid, diff))
+            })
+            .collect();
+
+        Self {
+            auction_id,
+            block,
+            reported: reported.map(Summary::new),
+            replayed: replayed.map(Summary::new),
+            score_difference,
+            orders,
+        }
+    }
+}
+
+fn best(response: &SolveResponse) -> Option<&solve_response::Solution> {
+    response
+        .solutions
+        .iter()
+        .max_by_key(|solution| solution.score)
+}
+
+type OrderId = [u8; crate::domain::competition::order::UID_LEN];
+
+/// The differences between the originally reported and the replayed best
+/// solution of an auction.
+#[serde_as]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayReport {
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    auction_id: i64,
+    /// The block at which the auction was replayed.
+    block: u64,
+    reported: Option<Summary>,
+    replayed: Option<Summary>,
+    /// The replayed score minus the reported score.
+    score_difference: Option<String>,
+    /// The orders whose executed amounts differ between both solutions.
+    #[serde_as(as = "BTreeMap<serialize::Hex, _>")]
+    orders: BTreeMap<OrderId, OrderDiff>,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Summary {
+    solution_id: u64,
+    #[serde_as(as = "serialize::U256")]
+    score: eth::U256,
+}
+
+impl Summary {
+    fn new(solution: &solve_response::Solution) -> Self {
+        Self {
+            solution_id: solution.solution_id,
+            score: solution.score,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OrderDiff {
+    reported: Option<Executed>,
+    replayed: Option<Executed>,
+}
+
+#[serde_as]
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Executed {
+    #[serde_as(as = "serialize::U256")]
+    sell: eth::U256,
+    #[serde_as(as = "serialize::U256")]
+    buy: eth::U256,
+}
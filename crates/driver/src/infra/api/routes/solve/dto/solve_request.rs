@@ -8,7 +8,7 @@ use {
         infra::{solver::Timeouts, tokens, Ethereum},
         util::serialize,
     },
-    serde::Deserialize,
+    serde::{Deserialize, Serialize},
     serde_with::serde_as,
     std::collections::HashSet,
 };
@@ -135,6 +135,17 @@ impl SolveRequest {
                             FeePolicy::Volume { factor } => {
                                 competition::order::FeePolicy::Volume { factor }
                             }
+                            FeePolicy::TieredVolume { tiers } => {
+                                competition::order::FeePolicy::TieredVolume {
+                                    tiers: tiers
+                                        .into_iter()
+                                        .map(|tier| competition::order::fees::VolumeTier {
+                                            min_notional: tier.min_notional.into(),
+                                            factor: tier.factor,
+                                        })
+                                        .collect(),
+                                }
+                            }
                         })
                         .collect(),
                     quote: order
@@ -196,7 +207,7 @@ impl From<auction::Error> for Error {
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SolveRequest {
     #[serde_as(as = "serde_with::DisplayFromStr")]
@@ -212,10 +223,172 @@ impl SolveRequest {
     pub fn id(&self) -> i64 {
         self.id
     }
+
+    /// Replaces the deadline of the request. Used for replaying archived
+    /// requests whose deadline has long passed.
+    pub fn with_deadline(self, deadline: chrono::DateTime<chrono::Utc>) -> Self {
+        Self { deadline, ..self }
+    }
 }
 
-#[serde_as]
+/// An auction as archived by the driver: the auction with liquidity that was
+/// sent to the solver engine, along with the block the driver was at when
+/// sending it.
 #[derive(Debug, Deserialize)]
+pub struct ArchivedAuction {
+    /// Unset for auctions archived before the block was stored.
+    block: Option<u64>,
+    #[serde(flatten)]
+    auction: solvers_dto::auction::Auction,
+}
+
+impl ArchivedAuction {
+    pub fn block(&self) -> Option<u64> {
+        self.block
+    }
+}
+
+impl TryFrom<ArchivedAuction> for SolveRequest {
+    type Error = Error;
+
+    /// Reconstructs the `/solve` request from the archived auction. Details
+    /// that are not sent to solver engines, like the order creation time and
+    /// the quotes of the orders, are lost.
+    fn try_from(archived: ArchivedAuction) -> Result<Self, Self::Error> {
+        use solvers_dto::auction as archive;
+
+        let auction = archived.auction;
+        let quote = |quote: archive::Quote| Quote {
+            sell_amount: quote.sell_amount,
+            buy_amount: quote.buy_amount,
+            fee: quote.fee,
+            solver: Default::default(),
+        };
+        Ok(Self {
+            id: auction.id.ok_or(Error::InvalidAuctionId)?,
+            tokens: auction
+                .tokens
+                .into_iter()
+                .map(|(address, token)| Token {
+                    address,
+                    price: token.reference_price,
+                    trusted: token.trusted,
+                })
+                .collect(),
+            orders: auction
+                .orders
+                .into_iter()
+                .map(|order| Order {
+                    uid: order.uid,
+                    sell_token: order.sell_token,
+                    buy_token: order.buy_token,
+                    sell_amount: order.full_sell_amount,
+                    buy_amount: order.full_buy_amount,
+                    protocol_fees: order
+                        .fee_policies
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|policy| match policy {
+                            archive::FeePolicy::Surplus {
+                                factor,
+                                max_volume_factor,
+                            } => FeePolicy::Surplus {
+                                factor,
+                                max_volume_factor,
+                            },
+                            archive::FeePolicy::PriceImprovement {
+                                factor,
+                                max_volume_factor,
+                                quote: q,
+                            } => FeePolicy::PriceImprovement {
+                                factor,
+                                max_volume_factor,
+                                quote: quote(q),
+                            },
+                            archive::FeePolicy::Volume { factor } => FeePolicy::Volume { factor },
+                            archive::FeePolicy::TieredVolume { tiers } => FeePolicy::TieredVolume {
+                                tiers: tiers
+                                    .into_iter()
+                                    .map(|tier| VolumeTier {
+                                        min_notional: tier.min_notional,
+                                        factor: tier.factor,
+                                    })
+                                    .collect(),
+                            },
+                        })
+                        .collect(),
+                    created: 0,
+                    valid_to: order.valid_to,
+                    // The archived amounts are the amounts that were still
+                    // available to be executed.
+                    executed: match (order.partially_fillable, &order.kind) {
+                        (false, _) => Default::default(),
+                        (true, archive::Kind::Sell) => {
+                            order.full_sell_amount.saturating_sub(order.sell_amount)
+                        }
+                        (true, archive::Kind::Buy) => {
+                            order.full_buy_amount.saturating_sub(order.buy_amount)
+                        }
+                    },
+                    kind: match order.kind {
+                        archive::Kind::Sell => Kind::Sell,
+                        archive::Kind::Buy => Kind::Buy,
+                    },
+                    receiver: order.receiver,
+                    owner: order.owner,
+                    partially_fillable: order.partially_fillable,
+                    pre_interactions: order
+                        .pre_interactions
+                        .into_iter()
+                        .map(Interaction::from)
+                        .collect(),
+                    post_interactions: order
+                        .post_interactions
+                        .into_iter()
+                        .map(Interaction::from)
+                        .collect(),
+                    sell_token_balance: match order.sell_token_source {
+                        archive::SellTokenSource::Erc20 => SellTokenBalance::Erc20,
+                        archive::SellTokenSource::External => SellTokenBalance::External,
+                        archive::SellTokenSource::Internal => SellTokenBalance::Internal,
+                    },
+                    buy_token_balance: match order.buy_token_destination {
+                        archive::BuyTokenDestination::Erc20 => BuyTokenBalance::Erc20,
+                        archive::BuyTokenDestination::Internal => BuyTokenBalance::Internal,
+                    },
+                    class: match order.class {
+                        archive::Class::Market => Class::Market,
+                        archive::Class::Limit => Class::Limit,
+                    },
+                    app_data: order.app_data.0,
+                    signing_scheme: match order.signing_scheme {
+                        archive::SigningScheme::Eip712 => SigningScheme::Eip712,
+                        archive::SigningScheme::EthSign => SigningScheme::EthSign,
+                        archive::SigningScheme::Eip1271 => SigningScheme::Eip1271,
+                        archive::SigningScheme::PreSign => SigningScheme::PreSign,
+                    },
+                    signature: order.signature,
+                    quote: None,
+                })
+                .collect(),
+            deadline: auction.deadline,
+            surplus_capturing_jit_order_owners: auction.surplus_capturing_jit_order_owners,
+        })
+    }
+}
+
+impl From<solvers_dto::auction::InteractionData> for Interaction {
+    fn from(interaction: solvers_dto::auction::InteractionData) -> Self {
+        Self {
+            target: interaction.target,
+            value: interaction.value,
+            call_data: interaction.call_data,
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct Token {
     pub address: eth::H160,
@@ -225,7 +398,7 @@ struct Token {
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct Order {
     #[serde_as(as = "serialize::Hex")]
@@ -261,7 +434,7 @@ struct Order {
     quote: Option<Quote>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 enum Kind {
     Sell,
@@ -269,7 +442,7 @@ enum Kind {
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct Interaction {
     target: eth::H160,
@@ -279,7 +452,7 @@ struct Interaction {
     call_data: Vec<u8>,
 }
 
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 enum SellTokenBalance {
     #[default]
@@ -288,7 +461,7 @@ enum SellTokenBalance {
     External,
 }
 
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 enum BuyTokenBalance {
     #[default]
@@ -296,7 +469,7 @@ enum BuyTokenBalance {
     Internal,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 enum SigningScheme {
     Eip712,
@@ -305,14 +478,14 @@ enum SigningScheme {
     Eip1271,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 enum Class {
     Market,
     Limit,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 enum FeePolicy {
     #[serde(rename_all = "camelCase")]
@@ -325,10 +498,21 @@ enum FeePolicy {
     },
     #[serde(rename_all = "camelCase")]
     Volume { factor: f64 },
+    #[serde(rename_all = "camelCase")]
+    TieredVolume { tiers: Vec<VolumeTier> },
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VolumeTier {
+    #[serde_as(as = "serialize::U256")]
+    min_notional: eth::U256,
+    factor: f64,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Quote {
     #[serde_as(as = "serialize::U256")]
@@ -359,3 +543,88 @@ impl Quote {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json};
+
+    fn archived_auction() -> serde_json::Value {
+        json!({
+            "block": 20_000_000,
+            "id": "42",
+            "tokens": {
+                "0x0101010101010101010101010101010101010101": {
+                    "decimals": 18,
+                    "symbol": "A",
+                    "referencePrice": "1000",
+                    "availableBalance": "0",
+                    "trusted": true,
+                },
+            },
+            "orders": [{
+                "uid": format!("0x{}", "11".repeat(order::UID_LEN)),
+                "sellToken": "0x0101010101010101010101010101010101010101",
+                "buyToken": "0x0202020202020202020202020202020202020202",
+                "sellAmount": "600",
+                "fullSellAmount": "1000",
+                "buyAmount": "540",
+                "fullBuyAmount": "900",
+                "feePolicies": [{ "volume": { "factor": 0.1 } }],
+                "validTo": 100,
+                "kind": "sell",
+                "owner": "0x0303030303030303030303030303030303030303",
+                "partiallyFillable": true,
+                "preInteractions": [],
+                "postInteractions": [],
+                "sellTokenSource": "erc20",
+                "buyTokenDestination": "internal",
+                "class": "limit",
+                "appData": format!("0x{}", "22".repeat(order::app_data::APP_DATA_LEN)),
+                "signingScheme": "presign",
+                "signature": "0x",
+            }],
+            "liquidity": [],
+            "effectiveGasPrice": "1",
+            "deadline": "2024-01-01T00:00:00Z",
+            "surplusCapturingJitOrderOwners": [],
+        })
+    }
+
+    #[test]
+    fn reconstructs_request_from_archived_auction() {
+        let archived: ArchivedAuction = serde_json::from_value(archived_auction()).unwrap();
+        assert_eq!(archived.block(), Some(20_000_000));
+
+        let request = SolveRequest::try_from(archived).unwrap();
+        assert_eq!(request.id(), 42);
+        assert_eq!(request.tokens.len(), 1);
+        assert_eq!(request.tokens[0].price, Some(1000.into()));
+        assert!(request.tokens[0].trusted);
+
+        let [order] = request.orders.as_slice() else {
+            panic!("expected a single order");
+        };
+        assert_eq!(order.uid, [0x11; order::UID_LEN]);
+        // The full amounts of the order get restored and the difference to the
+        // archived amounts was already executed.
+        assert_eq!(order.sell_amount, 1000.into());
+        assert_eq!(order.buy_amount, 900.into());
+        assert_eq!(order.executed, 400.into());
+        assert!(matches!(
+            order.protocol_fees.as_slice(),
+            [FeePolicy::Volume { factor }] if *factor == 0.1
+        ));
+        assert!(matches!(order.buy_token_balance, BuyTokenBalance::Internal));
+        assert!(matches!(order.class, Class::Limit));
+        assert!(matches!(order.signing_scheme, SigningScheme::PreSign));
+        assert_eq!(order.app_data, [0x22; order::app_data::APP_DATA_LEN]);
+    }
+
+    #[test]
+    fn archived_auctions_without_block() {
+        let mut auction = archived_auction();
+        auction.as_object_mut().unwrap().remove("block");
+        let archived: ArchivedAuction = serde_json::from_value(auction).unwrap();
+        assert_eq!(archived.block(), None);
+    }
+}
@@ -1,10 +1,13 @@
 mod dto;
 
-pub use dto::AuctionError;
+pub use dto::{ArchivedAuction, AuctionError, ReplayReport};
 use {
-    crate::infra::{
-        api::{Error, State},
-        observe,
+    crate::{
+        domain::competition::auction,
+        infra::{
+            api::{Error, State},
+            observe,
+        },
     },
     std::time::Instant,
     tap::TapFallible,
@@ -21,33 +24,67 @@ async fn route(
 ) -> Result<axum::Json<dto::SolveResponse>, (hyper::StatusCode, axum::Json<Error>)> {
     let auction_id = req.id();
     let handle_request = async {
-        observe::auction(auction_id);
-        let start = Instant::now();
-        let auction = req
-            .0
-            .into_domain(state.eth(), state.tokens(), state.timeouts())
-            .await
-            .tap_err(|err| {
-                observe::invalid_dto(err, "auction");
-            })?;
-        tracing::debug!(elapsed = ?start.elapsed(), "auction task execution time");
-        let competition = state.competition();
-        let auction = state
-            .pre_processor()
-            .prioritize(auction, &competition.solver.account().address())
-            .await;
-        let result = competition.solve(auction).await;
-        // Solving takes some time, so there is a chance for the settlement queue to
-        // have capacity again.
-        competition.ensure_settle_queue_capacity()?;
-        observe::solved(state.solver().name(), &result);
-        Ok(axum::Json(dto::SolveResponse::new(
-            result?,
-            &competition.solver,
-        )))
+        let response = handle(&state.0, req.0).await?;
+        if let Ok(id) = auction::Id::try_from(auction_id) {
+            state.solver().persistence().archive_response(id, &response);
+        }
+        Ok(axum::Json(response))
     };
 
     handle_request
         .instrument(tracing::info_span!("/solve", solver = %state.solver().name(), auction_id))
         .await
 }
+
+async fn handle(
+    state: &State,
+    req: dto::SolveRequest,
+) -> Result<dto::SolveResponse, (hyper::StatusCode, axum::Json<Error>)> {
+    observe::auction(req.id());
+    let start = Instant::now();
+    let auction = req
+        .into_domain(state.eth(), state.tokens(), state.timeouts())
+        .await
+        .tap_err(|err| {
+            observe::invalid_dto(err, "auction");
+        })?;
+    tracing::debug!(elapsed = ?start.elapsed(), "auction task execution time");
+    let competition = state.competition();
+    let auction = state
+        .pre_processor()
+        .prioritize(auction, &competition.solver.account().address())
+        .await;
+    let result = competition.solve(auction).await;
+    // Solving takes some time, so there is a chance for the settlement queue to
+    // have capacity again.
+    competition.ensure_settle_queue_capacity()?;
+    observe::solved(state.solver().name(), &result);
+    Ok(dto::SolveResponse::new(result?, &competition.solver))
+}
+
+/// Runs an archived auction through the same pipeline as the route, with a
+/// new deadline, and compares the result with the originally reported
+/// response.
+pub(in crate::infra::api) async fn replay(
+    state: &State,
+    auction: ArchivedAuction,
+    reported: Option<serde_json::Value>,
+    deadline: chrono::DateTime<chrono::Utc>,
+) -> anyhow::Result<ReplayReport> {
+    let request = dto::SolveRequest::try_from(auction)?;
+    let reported: Option<dto::SolveResponse> = reported.map(serde_json::from_value).transpose()?;
+    let auction_id = request.id();
+    let block = state.eth().current_block().borrow().number;
+
+    let replayed = handle(state, request.with_deadline(deadline))
+        .instrument(tracing::info_span!("replay", solver = %state.solver().name(), auction_id))
+        .await
+        .map_err(|(_, err)| anyhow::anyhow!("failed to solve auction: {:?}", err.0))?;
+
+    Ok(ReplayReport::new(
+        auction_id,
+        block,
+        &replayed,
+        reported.as_ref(),
+    ))
+}
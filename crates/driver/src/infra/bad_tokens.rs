@@ -0,0 +1,291 @@
+//! Persists the state of the bad token detectors to a local file, so that
+//! token qualities survive driver restarts.
+
+use {
+    crate::{
+        domain::{
+            competition::bad_tokens::{self, cache, metrics, Quality},
+            eth,
+        },
+        infra::solver,
+    },
+    anyhow::Context,
+    serde::{Deserialize, Serialize},
+    std::{
+        collections::HashMap,
+        path::PathBuf,
+        sync::Arc,
+        time::{Duration, Instant},
+    },
+};
+
+/// How often the state of the detectors gets written to the file.
+const PERSIST_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+pub struct Store {
+    path: PathBuf,
+}
+
+impl Store {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Restores the persisted state into the detectors and keeps persisting
+    /// their state in the background.
+    pub fn spawn(
+        self,
+        simulation: bad_tokens::simulation::Detector,
+        solvers: Vec<(solver::Name, Arc<bad_tokens::Detector>)>,
+    ) {
+        match self.read() {
+            Ok(snapshot) => snapshot.restore(&simulation, &solvers),
+            Err(err) => tracing::warn!(?err, path = ?self.path, "failed to restore bad tokens"),
+        }
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(PERSIST_INTERVAL);
+            loop {
+                interval.tick().await;
+                let snapshot = Snapshot::new(&simulation, &solvers);
+                let store = self.clone();
+                let result = tokio::task::spawn_blocking(move || store.write(&snapshot)).await;
+                match result {
+                    Ok(Ok(())) => tracing::trace!("persisted bad tokens"),
+                    Ok(Err(err)) => tracing::warn!(?err, "failed to persist bad tokens"),
+                    Err(err) => tracing::warn!(?err, "bad token persistence task failed"),
+                }
+            }
+        });
+    }
+
+    fn read(&self) -> anyhow::Result<Snapshot> {
+        if !self.path.exists() {
+            return Ok(Default::default());
+        }
+        let data = std::fs::read(&self.path).context("read file")?;
+        serde_json::from_slice(&data).context("parse file")
+    }
+
+    /// Writes the snapshot to a temporary file first and then moves it in
+    /// place, so that a crash never leaves a partially written file behind.
+    fn write(&self, snapshot: &Snapshot) -> anyhow::Result<()> {
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec(snapshot)?).context("write file")?;
+        std::fs::rename(&tmp, &self.path).context("rename file")
+    }
+}
+
+/// The persisted state. Points in time are stored as UNIX timestamps, since
+/// [`Instant`]s don't survive restarts.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Snapshot {
+    simulation: Vec<SimulationEntry>,
+    solvers: HashMap<String, SolverSnapshot>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SolverSnapshot {
+    metrics: Vec<MetricsEntry>,
+    overrides: Vec<OverrideEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SimulationEntry {
+    token: eth::H160,
+    supported: bool,
+    updated_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MetricsEntry {
+    token: eth::H160,
+    attempts: u32,
+    fails: u32,
+    flagged_unsupported_at: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OverrideEntry {
+    token: eth::H160,
+    supported: bool,
+    expires_at: Option<i64>,
+}
+
+impl Snapshot {
+    fn new(
+        simulation: &cache::Cache,
+        solvers: &[(solver::Name, Arc<bad_tokens::Detector>)],
+    ) -> Self {
+        let (now, timestamp) = (Instant::now(), chrono::Utc::now().timestamp());
+        let secs = |duration: Duration| i64::try_from(duration.as_secs()).unwrap_or(i64::MAX);
+        let ago = |age: Duration| timestamp.saturating_sub(secs(age));
+        let from_now = |ttl: Duration| timestamp.saturating_add(secs(ttl));
+
+        Self {
+            simulation: simulation
+                .entries(now)
+                .into_iter()
+                .map(|entry| SimulationEntry {
+                    token: entry.token.0 .0,
+                    supported: entry.is_supported,
+                    updated_at: ago(entry.age),
+                })
+                .collect(),
+            solvers: solvers
+                .iter()
+                .map(|(name, detector)| {
+                    let snapshot = SolverSnapshot {
+                        metrics: detector
+                            .metrics_detector()
+                            .map(|metrics| metrics.entries(now))
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|entry| MetricsEntry {
+                                token: entry.token.0 .0,
+                                attempts: entry.attempts,
+                                fails: entry.fails,
+                                flagged_unsupported_at: entry.flagged_unsupported.map(ago),
+                            })
+                            .collect(),
+                        overrides: detector
+                            .overrides(now)
+                            .into_iter()
+                            .map(|(token, quality, ttl)| OverrideEntry {
+                                token: token.0 .0,
+                                supported: quality == Quality::Supported,
+                                expires_at: ttl.map(from_now),
+                            })
+                            .collect(),
+                    };
+                    (name.as_str().to_owned(), snapshot)
+                })
+                .collect(),
+        }
+    }
+
+    fn restore(
+        self,
+        simulation: &cache::Cache,
+        solvers: &[(solver::Name, Arc<bad_tokens::Detector>)],
+    ) {
+        let (now, timestamp) = (Instant::now(), chrono::Utc::now().timestamp());
+        let since = |at: i64| {
+            Duration::from_secs(u64::try_from(timestamp.saturating_sub(at)).unwrap_or_default())
+        };
+        let token = |token: eth::H160| eth::TokenAddress(eth::ContractAddress(token));
+
+        simulation.restore(
+            self.simulation.into_iter().map(|entry| cache::Entry {
+                token: token(entry.token),
+                is_supported: entry.supported,
+                age: since(entry.updated_at),
+            }),
+            now,
+        );
+
+        let mut snapshots = self.solvers;
+        for (name, detector) in solvers {
+            let Some(snapshot) = snapshots.remove(name.as_str()) else {
+                continue;
+            };
+            if let Some(metrics) = detector.metrics_detector() {
+                metrics.restore(
+                    snapshot.metrics.into_iter().map(|entry| metrics::Entry {
+                        token: token(entry.token),
+                        attempts: entry.attempts,
+                        fails: entry.fails,
+                        flagged_unsupported: entry.flagged_unsupported_at.map(since),
+                    }),
+                    now,
+                );
+            }
+            for entry in snapshot.overrides {
+                let ttl = match entry.expires_at {
+                    Some(at) if at <= timestamp => continue,
+                    Some(at) => Some(Duration::from_secs(at.abs_diff(timestamp))),
+                    None => None,
+                };
+                let quality = match entry.supported {
+                    true => Quality::Supported,
+                    false => Quality::Unsupported,
+                };
+                detector.set_override(token(entry.token), quality, ttl, now);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, ethcontract::H160, itertools::Itertools};
+
+    #[test]
+    fn store_roundtrip() {
+        let cache = || cache::Cache::new(Duration::from_secs(600));
+        let solvers = || {
+            let mut detector = bad_tokens::Detector::new(Default::default());
+            detector.with_metrics_detector(metrics::Detector::new(
+                0.5,
+                2,
+                false,
+                Duration::from_secs(600),
+                solver::Name("mysolver".to_string()),
+            ));
+            vec![(solver::Name("mysolver".to_string()), Arc::new(detector))]
+        };
+        let token = |byte| eth::TokenAddress(eth::ContractAddress(H160([byte; 20])));
+
+        let now = Instant::now();
+        let original = (cache(), solvers());
+        original.0.update_quality(token(1), false, now);
+        let detector = &original.1[0].1;
+        let metrics = detector.metrics_detector().unwrap();
+        metrics.update_tokens(&[(token(2), token(3))], true);
+        metrics.update_tokens(&[(token(2), token(3))], true);
+        detector.set_override(token(4), Quality::Supported, None, now);
+        detector.set_override(
+            token(5),
+            Quality::Unsupported,
+            Some(Duration::from_secs(3600)),
+            now,
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::new(dir.path().join("bad_tokens.json"));
+        // Reading a store that was never written restores nothing.
+        assert!(store.read().unwrap().simulation.is_empty());
+        store
+            .write(&Snapshot::new(&original.0, &original.1))
+            .unwrap();
+
+        let restored = (cache(), solvers());
+        store.read().unwrap().restore(&restored.0, &restored.1);
+
+        let now = Instant::now();
+        assert_eq!(restored.0.get_quality(&token(1), now), Quality::Unsupported);
+        let detector = &restored.1[0].1;
+        let metrics = detector.metrics_detector().unwrap();
+        assert_eq!(metrics.get_quality(&token(2), now), Quality::Unsupported);
+        assert_eq!(metrics.get_quality(&token(3), now), Quality::Unsupported);
+        let overrides = detector
+            .overrides(now)
+            .into_iter()
+            .map(|(token, quality, ttl)| (token, quality, ttl.is_some()))
+            .sorted_by_key(|(token, ..)| *token)
+            .collect_vec();
+        assert_eq!(
+            overrides,
+            vec![
+                (token(4), Quality::Supported, false),
+                (token(5), Quality::Unsupported, true),
+            ]
+        );
+    }
+}
@@ -3,6 +3,7 @@ use {
     chain::Chain,
     ethcontract::dyns::DynWeb3,
     ethrpc::block_stream::CurrentBlockWatcher,
+    std::collections::HashMap,
     thiserror::Error,
     url::Url,
 };
@@ -17,6 +18,7 @@ pub struct Contracts {
     /// The domain separator for settlement contract used for signing orders.
     settlement_domain_separator: eth::DomainSeparator,
     cow_amm_registry: cow_amm::Registry,
+    flashloan_lenders: HashMap<eth::ContractAddress, FlashloanLender>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -24,6 +26,7 @@ pub struct Addresses {
     pub settlement: Option<eth::ContractAddress>,
     pub weth: Option<eth::ContractAddress>,
     pub cow_amms: Vec<CowAmmConfig>,
+    pub flashloan_lenders: Vec<FlashloanLenderConfig>,
 }
 
 impl Contracts {
@@ -78,6 +81,23 @@ impl Contracts {
         }
         cow_amm_registry.spawn_maintenance_task(block_stream);
 
+        let flashloan_lenders = addresses
+            .flashloan_lenders
+            .into_iter()
+            .map(|config| {
+                (
+                    config.lender,
+                    FlashloanLender {
+                        wrapper: contracts::ERC3156FlashLoanSolverWrapper::at(
+                            web3,
+                            config.wrapper.0,
+                        ),
+                        fee_bps: config.fee_bps,
+                    },
+                )
+            })
+            .collect();
+
         Ok(Self {
             settlement,
             vault_relayer,
@@ -85,6 +105,7 @@ impl Contracts {
             weth,
             settlement_domain_separator,
             cow_amm_registry,
+            flashloan_lenders,
         })
     }
 
@@ -115,6 +136,22 @@ impl Contracts {
     pub fn cow_amm_registry(&self) -> &cow_amm::Registry {
         &self.cow_amm_registry
     }
+
+    /// Returns the configuration for taking out flashloans from the specified
+    /// lender, or `None` if the driver is not configured to borrow from it.
+    pub fn flashloan_lender(&self, lender: eth::ContractAddress) -> Option<&FlashloanLender> {
+        self.flashloan_lenders.get(&lender)
+    }
+}
+
+/// A lender that settlements can take out flashloans from.
+#[derive(Debug, Clone)]
+pub struct FlashloanLender {
+    /// The solver wrapper contract that takes out the flashloan and calls the
+    /// settlement contract with the borrowed funds.
+    pub wrapper: contracts::ERC3156FlashLoanSolverWrapper,
+    /// The fee charged by the lender in basis points of the borrowed amount.
+    pub fee_bps: u32,
 }
 
 #[derive(Debug, Clone)]
@@ -127,6 +164,17 @@ pub struct CowAmmConfig {
     pub index_start: u64,
 }
 
+#[derive(Debug, Clone)]
+pub struct FlashloanLenderConfig {
+    /// The ERC-3156 flashloan lender lending the tokens.
+    pub lender: eth::ContractAddress,
+    /// The solver wrapper contract to take out flashloans from the lender
+    /// through.
+    pub wrapper: eth::ContractAddress,
+    /// The fee charged by the lender in basis points of the borrowed amount.
+    pub fee_bps: u32,
+}
+
 /// Returns the address of a contract for the specified network, or `None` if
 /// there is no known deployment for the contract on that network.
 pub fn deployment_address(
@@ -7,7 +7,8 @@ use {
     crate::infra::config::file::GasEstimatorType,
     crate::{domain::eth, infra::mempool},
     ethcontract::dyns::DynWeb3,
-    gas_estimation::{nativegasestimator::NativeGasEstimator, GasPriceEstimating},
+    gas_estimation::{nativegasestimator::NativeGasEstimator, GasPrice1559, GasPriceEstimating},
+    shared::gas_price_estimation::FakeGasPriceEstimator,
     std::sync::Arc,
 };
 
@@ -45,6 +46,11 @@ impl GasPriceEstimator {
                     additional_tip_percentage,
                     ..
                 } => (max_additional_tip, additional_tip_percentage),
+                mempool::Kind::BundleRelay {
+                    max_additional_tip,
+                    additional_tip_percentage,
+                    ..
+                } => (max_additional_tip, additional_tip_percentage),
                 mempool::Kind::Public {
                     max_additional_tip,
                     additional_tip_percentage,
@@ -73,6 +79,21 @@ impl GasPriceEstimator {
         })
     }
 
+    /// Returns an estimator that always estimates the specified base fee
+    /// instead of following the chain. Used for replaying archived auctions
+    /// with the gas price of the block they were solved at.
+    pub fn with_base_fee(self, base_fee: eth::U256) -> Self {
+        let base_fee = base_fee.to_f64_lossy();
+        Self {
+            gas: Arc::new(FakeGasPriceEstimator::new(GasPrice1559 {
+                base_fee_per_gas: base_fee,
+                max_fee_per_gas: base_fee,
+                max_priority_fee_per_gas: 0.,
+            })),
+            ..self
+        }
+    }
+
     /// Estimates the gas price for a transaction.
     /// If additional tip is configured, it will be added to the gas price. This
     /// is to increase the chance of a transaction being included in a block, in
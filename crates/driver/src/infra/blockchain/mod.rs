@@ -3,7 +3,7 @@ use {
     crate::{boundary, domain::eth},
     chain::Chain,
     ethcontract::{dyns::DynWeb3, errors::ExecutionError},
-    ethrpc::block_stream::CurrentBlockWatcher,
+    ethrpc::block_stream::{BlockInfo, CurrentBlockWatcher},
     std::{fmt, sync::Arc},
     thiserror::Error,
     url::Url,
@@ -68,6 +68,8 @@ struct Inner {
     contracts: Contracts,
     gas: Arc<GasPriceEstimator>,
     current_block: CurrentBlockWatcher,
+    /// The block all state gets read at, if not following the chain.
+    pinned: Option<u64>,
 }
 
 impl Ethereum {
@@ -83,12 +85,66 @@ impl Ethereum {
         gas: Arc<GasPriceEstimator>,
         archive_node_url: Option<&Url>,
     ) -> Self {
-        let Rpc { web3, chain, url } = rpc;
+        let current_block_stream = ethrpc::block_stream::current_block_stream(
+            rpc.url.clone(),
+            std::time::Duration::from_millis(500),
+        )
+        .await
+        .expect("couldn't initialize current block stream");
+        Self::with_current_block(
+            rpc,
+            addresses,
+            gas,
+            archive_node_url,
+            current_block_stream,
+            None,
+        )
+        .await
+    }
 
-        let current_block_stream =
-            ethrpc::block_stream::current_block_stream(url, std::time::Duration::from_millis(500))
-                .await
-                .expect("couldn't initialize current block stream");
+    /// Access the Ethereum blockchain through an RPC API as of the specified
+    /// block instead of following the chain. Used for replaying archived
+    /// auctions, which requires the node to have the state of that block.
+    /// Gas prices are estimated with the base fee of that block.
+    ///
+    /// # Panics
+    ///
+    /// Panics on any initialization error, like [`Ethereum::new`].
+    pub async fn pinned(
+        rpc: Rpc,
+        addresses: contracts::Addresses,
+        gas: GasPriceEstimator,
+        archive_node_url: Option<&Url>,
+        block: u64,
+    ) -> Self {
+        let number = web3::types::BlockNumber::Number(block.into());
+        let block = ethrpc::block_stream::block_by_number(&rpc.web3, number)
+            .await
+            .and_then(|block| BlockInfo::try_from(block).ok())
+            .unwrap_or_else(|| panic!("couldn't fetch block {block}"));
+        let gas = Arc::new(gas.with_base_fee(block.gas_price));
+        let pinned = Some(block.number);
+        let current_block_stream = ethrpc::block_stream::mock_single_block(block);
+        Self::with_current_block(
+            rpc,
+            addresses,
+            gas,
+            archive_node_url,
+            current_block_stream,
+            pinned,
+        )
+        .await
+    }
+
+    async fn with_current_block(
+        rpc: Rpc,
+        addresses: contracts::Addresses,
+        gas: Arc<GasPriceEstimator>,
+        archive_node_url: Option<&Url>,
+        current_block_stream: CurrentBlockWatcher,
+        pinned: Option<u64>,
+    ) -> Self {
+        let Rpc { web3, chain, .. } = rpc;
 
         let contracts = Contracts::new(
             &web3,
@@ -106,6 +162,7 @@ impl Ethereum {
                 chain,
                 contracts,
                 gas,
+                pinned,
             }),
             web3,
         }
@@ -182,7 +239,10 @@ impl Ethereum {
             .transport()
             .execute(
                 "eth_createAccessList",
-                vec![serde_json::to_value(&tx).unwrap()],
+                vec![
+                    serde_json::to_value(&tx).unwrap(),
+                    serde_json::to_value(self.block()).unwrap(),
+                ],
             )
             .await?;
         if let Some(err) = json.get("error") {
@@ -207,7 +267,7 @@ impl Ethereum {
                     gas_price: self.simulation_gas_price().await,
                     ..Default::default()
                 },
-                None,
+                Some(self.block()),
             )
             .await
             .map(Into::into)
@@ -259,6 +319,15 @@ impl Ethereum {
             .map_err(Into::into)
     }
 
+    /// The block to run calls and simulations at: the pinned block if set and
+    /// the latest block otherwise.
+    fn block(&self) -> web3::types::BlockNumber {
+        match self.inner.pinned {
+            Some(block) => web3::types::BlockNumber::Number(block.into()),
+            None => web3::types::BlockNumber::Latest,
+        }
+    }
+
     pub(super) async fn simulation_gas_price(&self) -> Option<eth::U256> {
         // Some nodes don't pick a reasonable default value when you don't specify a gas
         // price and default to 0. Additionally some sneaky tokens have special code
@@ -1,6 +1,6 @@
 use {
     reqwest::Url,
-    std::{net::SocketAddr, path::PathBuf},
+    std::{net::SocketAddr, path::PathBuf, time::Duration},
 };
 
 #[derive(Debug, clap::Parser)]
@@ -26,4 +26,53 @@ pub struct Args {
     /// https://github.com/cowprotocol/services/blob/main/crates/driver/example.toml.
     #[clap(long, env)]
     pub config: PathBuf,
+
+    /// Runs a one-off command instead of serving the driver API.
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Command {
+    /// Replays an archived auction: liquidity fetching, solving, encoding,
+    /// simulation and scoring all run again and the result is compared with
+    /// the originally reported solution. Everything runs at the block stored
+    /// with the archived auction, so the node at --ethrpc needs the state of
+    /// that block, e.g. an archive node or a fork.
+    Replay(Replay),
+}
+
+#[derive(Debug, clap::Args)]
+pub struct Replay {
+    /// The solver for which to replay the auction.
+    #[clap(long)]
+    pub solver: String,
+
+    /// Path to a file containing the archived auction with liquidity to
+    /// replay.
+    #[clap(long, required_unless_present = "auction_id")]
+    pub auction: Option<PathBuf>,
+
+    /// Path to a file containing the originally reported `/solve` response.
+    #[clap(long, requires = "auction")]
+    pub response: Option<PathBuf>,
+
+    /// ID of the auction to replay. The auction with liquidity and the
+    /// `/solve` response get fetched from the solver's configured archive.
+    #[clap(long, conflicts_with = "auction")]
+    pub auction_id: Option<i64>,
+
+    /// The block at which to replay the auction. Defaults to the block stored
+    /// with the archived auction.
+    #[clap(long)]
+    pub block: Option<u64>,
+
+    /// How much time the driver gets for solving the replayed auction.
+    #[clap(long, default_value = "15s", value_parser = humantime::parse_duration)]
+    pub time_limit: Duration,
+
+    /// Path to write the replay report to. The report gets printed to stdout
+    /// if not set.
+    #[clap(long)]
+    pub report: Option<PathBuf>,
 }
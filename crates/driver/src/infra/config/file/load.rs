@@ -1,6 +1,9 @@
 use {
     crate::{
-        domain::{competition::bad_tokens, eth},
+        domain::{
+            competition::{auction, bad_tokens},
+            eth,
+        },
         infra::{
             self,
             blockchain,
@@ -95,6 +98,10 @@ pub async fn load(chain: Chain, path: &Path) -> infra::Config {
                     false => SolutionMerging::Forbidden,
                 },
                 s3: solver_config.s3.map(Into::into),
+                archive_dir: solver_config.archive_dir,
+                archive_retention: solver_config.archive_retention,
+                archive_gzip: solver_config.archive_gzip,
+                archive_naming: solver_config.archive_naming.into(),
                 solver_native_token: solver_config.manage_native_token.to_domain(),
                 quote_tx_origin: solver_config.quote_tx_origin.map(eth::Address),
                 response_size_limit_max_bytes: solver_config.response_size_limit_max_bytes,
@@ -134,6 +141,8 @@ pub async fn load(chain: Chain, path: &Path) -> infra::Config {
                 },
                 settle_queue_size: solver_config.settle_queue_size,
                 flashloans_enabled: config.flashloans_enabled,
+                extended_liquidity_kinds: solver_config.extended_liquidity_kinds,
+                policy: policy(solver_config.policy),
             }
         }))
         .await,
@@ -312,12 +321,13 @@ pub async fn load(chain: Chain, path: &Path) -> infra::Config {
                         // If there is no private mempool, revert protection is
                         // disabled, otherwise driver would not even try to settle revertable
                         // settlements
-                        let revert_protection = if config
-                            .submission
-                            .mempools
-                            .iter()
-                            .any(|pool| matches!(pool, file::Mempool::MevBlocker { .. }))
-                        {
+                        let revert_protection = if config.submission.mempools.iter().any(|pool| {
+                            matches!(
+                                pool,
+                                file::Mempool::MevBlocker { .. }
+                                    | file::Mempool::BundleRelay { .. }
+                            )
+                        }) {
                             mempool::RevertProtection::Enabled
                         } else {
                             mempool::RevertProtection::Disabled
@@ -340,27 +350,22 @@ pub async fn load(chain: Chain, path: &Path) -> infra::Config {
                         additional_tip_percentage: *additional_tip_percentage,
                         use_soft_cancellations: *use_soft_cancellations,
                     },
+                    file::Mempool::BundleRelay {
+                        url,
+                        signing_key,
+                        max_additional_tip,
+                        additional_tip_percentage,
+                    } => mempool::Kind::BundleRelay {
+                        url: url.to_owned(),
+                        signing_key: signing_key
+                            .map(|key| ethcontract::PrivateKey::from_raw(key.0).unwrap()),
+                        max_additional_tip: *max_additional_tip,
+                        additional_tip_percentage: *additional_tip_percentage,
+                    },
                 },
             })
             .collect(),
-        simulator: match (config.tenderly, config.enso) {
-            (Some(config), None) => {
-                Some(simulator::Config::Tenderly(simulator::tenderly::Config {
-                    url: config.url,
-                    api_key: config.api_key,
-                    user: config.user,
-                    project: config.project,
-                    save: config.save,
-                    save_if_fails: config.save_if_fails,
-                }))
-            }
-            (None, Some(config)) => Some(simulator::Config::Enso(simulator::enso::Config {
-                url: config.url,
-                network_block_interval: config.network_block_interval,
-            })),
-            (None, None) => None,
-            (Some(_), Some(_)) => panic!("Cannot configure both Tenderly and Enso"),
-        },
+        simulator: simulator_config(config.tenderly, config.enso, config.simulation),
         contracts: blockchain::contracts::Addresses {
             settlement: config.contracts.gp_v2_settlement.map(Into::into),
             weth: config.contracts.weth.map(Into::into),
@@ -374,6 +379,16 @@ pub async fn load(chain: Chain, path: &Path) -> infra::Config {
                     helper: cfg.helper,
                 })
                 .collect(),
+            flashloan_lenders: config
+                .contracts
+                .flashloan_lenders
+                .into_iter()
+                .map(|cfg| blockchain::contracts::FlashloanLenderConfig {
+                    lender: cfg.lender.into(),
+                    wrapper: cfg.wrapper.into(),
+                    fee_bps: cfg.fee_bps,
+                })
+                .collect(),
         },
         disable_access_list_simulation: config.disable_access_list_simulation,
         disable_gas_simulation: config.disable_gas_simulation.map(Into::into),
@@ -381,6 +396,85 @@ pub async fn load(chain: Chain, path: &Path) -> infra::Config {
         order_priority_strategies: config.order_priority_strategies,
         archive_node_url: config.archive_node_url,
         simulation_bad_token_max_age: config.simulation_bad_token_max_age,
+        bad_token_cache_file: config.bad_token_cache_file,
         app_data_fetching: config.app_data_fetching,
     }
 }
+
+fn simulator_config(
+    tenderly: Option<file::TenderlyConfig>,
+    enso: Option<file::EnsoConfig>,
+    simulation: Option<file::SimulationConfig>,
+) -> Option<simulator::Config> {
+    let tenderly = tenderly.map(|config| simulator::tenderly::Config {
+        url: config.url,
+        api_key: config.api_key,
+        user: config.user,
+        project: config.project,
+        save: config.save,
+        save_if_fails: config.save_if_fails,
+    });
+    let enso = enso.map(|config| simulator::enso::Config {
+        url: config.url,
+        network_block_interval: config.network_block_interval,
+    });
+
+    let Some(simulation) = simulation else {
+        return match (tenderly, enso) {
+            (Some(config), None) => Some(simulator::Config::Tenderly(config)),
+            (None, Some(config)) => Some(simulator::Config::Enso(config)),
+            (None, None) => None,
+            (Some(_), Some(_)) => panic!("Cannot configure both Tenderly and Enso"),
+        };
+    };
+
+    Some(simulator::Config::Composite(simulator::composite::Config {
+        backends: simulation
+            .backends
+            .into_iter()
+            .map(|backend| simulator::composite::Backend {
+                kind: match backend.kind {
+                    file::SimulationBackend::Tenderly => simulator::composite::Kind::Tenderly(
+                        tenderly
+                            .clone()
+                            .expect("Tenderly simulation requires a Tenderly configuration"),
+                    ),
+                    file::SimulationBackend::Enso => simulator::composite::Kind::Enso(
+                        enso.clone()
+                            .expect("Enso simulation requires an Enso configuration"),
+                    ),
+                    file::SimulationBackend::Ethereum => simulator::composite::Kind::Ethereum,
+                },
+                timeout: backend.timeout,
+                access_list: backend
+                    .operations
+                    .contains(&file::SimulationOperation::AccessList),
+                gas: backend.operations.contains(&file::SimulationOperation::Gas),
+            })
+            .collect(),
+        cache_size: simulation.cache_size,
+        confirm_reverts: simulation.confirm_reverts,
+    }))
+}
+
+fn policy(config: file::SolverPolicy) -> auction::Policy {
+    let token = |token: eth::H160| eth::TokenAddress(eth::ContractAddress(token));
+    let pair = |[a, b]: [eth::H160; 2]| {
+        crate::domain::liquidity::TokenPair::try_new(token(a), token(b))
+            .unwrap_or_else(|_| panic!("invalid solver policy token pair {a:?}/{b:?}"))
+    };
+    auction::Policy {
+        allowed_tokens: config
+            .allowed_tokens
+            .map(|tokens| tokens.into_iter().map(token).collect()),
+        denied_tokens: config.denied_tokens.into_iter().map(token).collect(),
+        allowed_pairs: config
+            .allowed_pairs
+            .map(|pairs| pairs.into_iter().map(pair).collect()),
+        denied_pairs: config.denied_pairs.into_iter().map(pair).collect(),
+        max_order_notional: config.max_order_notional.map(eth::Ether),
+        market_orders: config.market_orders,
+        limit_orders: config.limit_orders,
+        liquidity_orders: config.liquidity_orders,
+    }
+}
@@ -1,11 +1,15 @@
 pub use load::load;
 use {
-    crate::{domain::eth, infra, util::serialize},
+    crate::{
+        domain::{self, eth},
+        infra,
+        util::serialize,
+    },
     reqwest::Url,
     serde::{Deserialize, Deserializer, Serialize},
     serde_with::serde_as,
     solver::solver::Arn,
-    std::{collections::HashMap, time::Duration},
+    std::{collections::HashMap, path::PathBuf, time::Duration},
 };
 
 mod load;
@@ -49,6 +53,12 @@ struct Config {
     /// Use Enso for transaction simulation.
     enso: Option<EnsoConfig>,
 
+    /// Simulate transactions with multiple backends which are tried in order,
+    /// falling back to the next backend if one fails or times out. The
+    /// Tenderly and Enso backends use the `[tenderly]` and `[enso]`
+    /// configurations, which can both be set in this case.
+    simulation: Option<SimulationConfig>,
+
     #[serde(rename = "solver")]
     solvers: Vec<SolverConfig>,
 
@@ -74,6 +84,12 @@ struct Config {
     )]
     simulation_bad_token_max_age: Duration,
 
+    /// Local file in which the state of the bad token detectors, including
+    /// tokens marked at runtime, is persisted across restarts. The state is
+    /// kept in memory only if unset.
+    #[serde(default)]
+    bad_token_cache_file: Option<PathBuf>,
+
     /// Configuration for the app-data fetching.
     #[serde(default, flatten)]
     app_data_fetching: AppDataFetching,
@@ -110,7 +126,7 @@ struct SubmissionConfig {
     retry_interval: Duration,
 
     /// The mempools to submit settlement transactions to. Can be the public
-    /// mempool of a node or the private MEVBlocker mempool.
+    /// mempool of a node, the private MEVBlocker mempool or bundle relays.
     #[serde(rename = "mempool", default)]
     mempools: Vec<Mempool>,
 }
@@ -155,6 +171,25 @@ enum Mempool {
         #[serde(default = "default_soft_cancellations_flag")]
         use_soft_cancellations: bool,
     },
+    #[serde(rename_all = "kebab-case")]
+    BundleRelay {
+        /// The URL of the `eth_sendBundle` compatible relay to use.
+        url: Url,
+        /// Private key to authenticate bundles with using the
+        /// `X-Flashbots-Signature` header. Expects a 32-byte hex encoded
+        /// string. Only required for relays that authenticate bundles.
+        signing_key: Option<eth::H256>,
+        /// Maximum additional tip in Gwei that we are willing to give to
+        /// the relay above regular gas price estimation.
+        #[serde(default = "default_max_additional_tip")]
+        #[serde_as(as = "serialize::U256")]
+        max_additional_tip: eth::U256,
+        /// Additional tip in percentage of max_fee_per_gas we are giving to
+        /// the relay above regular gas price estimation. Expects a
+        /// floating point value between 0 and 1.
+        #[serde(default = "default_additional_tip_percentage")]
+        additional_tip_percentage: f64,
+    },
 }
 
 #[derive(Debug, Deserialize)]
@@ -261,10 +296,30 @@ struct SolverConfig {
     merge_solutions: bool,
 
     /// S3 configuration for storing the auctions in the form they are sent to
-    /// the solver engine
+    /// the solver engine, along with an audit trail of the proposed solutions,
+    /// their simulation and their submission.
     #[serde(default)]
     s3: Option<S3>,
 
+    /// Local directory for storing the same data as with the S3
+    /// configuration. Can't be configured together with S3.
+    #[serde(default)]
+    archive_dir: Option<PathBuf>,
+
+    /// Archived data older than this gets deleted, both from S3 and from the
+    /// local archive directory. Archived data is kept forever if unset.
+    #[serde(default, with = "humantime_serde")]
+    archive_retention: Option<Duration>,
+
+    /// Whether archived data gets Gzip compressed. By default data archived in
+    /// S3 gets compressed and data in the local archive directory doesn't.
+    #[serde(default)]
+    archive_gzip: Option<bool>,
+
+    /// How archived data gets named.
+    #[serde(default)]
+    archive_naming: ArchiveNaming,
+
     /// Whether the native token is wrapped or not when sent to the solvers
     #[serde(default)]
     manage_native_token: ManageNativeToken,
@@ -285,6 +340,17 @@ struct SolverConfig {
     /// before the driver starts dropping new `/solve` requests.
     #[serde(default = "default_settle_queue_size")]
     settle_queue_size: usize,
+
+    /// Restricts which orders of an auction get sent to the solver.
+    #[serde(default)]
+    policy: SolverPolicy,
+
+    /// Whether liquidity bootstrapping and composable stable pools are sent to
+    /// the solver as `liquidityBootstrapping` and `composableStable` liquidity.
+    /// Otherwise they are sent as `weightedProduct` and `stable` liquidity
+    /// with the weights at the block the liquidity was fetched at.
+    #[serde(default)]
+    extended_liquidity_kinds: bool,
 }
 
 #[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
@@ -306,6 +372,27 @@ pub struct S3 {
     pub prefix: String,
 }
 
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ArchiveNaming {
+    /// `<prefix><key>.json`
+    #[default]
+    Flat,
+    /// `<prefix><yyyy>/<mm>/<dd>/<key>.json`, using the UTC date at which the
+    /// data gets archived. Archived auctions can't be replayed by ID with this
+    /// naming scheme.
+    Dated,
+}
+
+impl From<ArchiveNaming> for s3::Naming {
+    fn from(value: ArchiveNaming) -> Self {
+        match value {
+            ArchiveNaming::Flat => Self::Flat,
+            ArchiveNaming::Dated => Self::Dated,
+        }
+    }
+}
+
 #[serde_as]
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
@@ -366,6 +453,10 @@ struct ContractsConfig {
     /// rebalancing orders for.
     #[serde(default)]
     cow_amms: Vec<CowAmmConfig>,
+
+    /// Lenders that settlements may take out flashloans from.
+    #[serde(default)]
+    flashloan_lenders: Vec<FlashloanLenderConfig>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -379,6 +470,20 @@ pub struct CowAmmConfig {
     pub index_start: u64,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct FlashloanLenderConfig {
+    /// The ERC-3156 flashloan lender lending the tokens.
+    pub lender: eth::H160,
+    /// The solver wrapper contract to take out flashloans from the lender
+    /// through. It takes out the loan, calls the settlement contract with the
+    /// borrowed funds and repays the lender afterwards.
+    pub wrapper: eth::H160,
+    /// The fee charged by the lender in basis points of the borrowed amount.
+    #[serde(default)]
+    pub fee_bps: u32,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 struct TenderlyConfig {
@@ -413,6 +518,64 @@ struct EnsoConfig {
     network_block_interval: Option<Duration>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct SimulationConfig {
+    /// The simulator backends in the order in which they are tried.
+    backends: Vec<SimulationBackendConfig>,
+
+    /// How many simulation results are cached per operation. Results are
+    /// cached by transaction and block.
+    #[serde(default = "default_simulation_cache_size")]
+    cache_size: u64,
+
+    /// Confirm reverts reported by a backend with the next backend, to detect
+    /// disagreements between them. The revert is reported either way.
+    #[serde(default)]
+    confirm_reverts: bool,
+}
+
+fn default_simulation_cache_size() -> u64 {
+    1000
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct SimulationBackendConfig {
+    kind: SimulationBackend,
+
+    /// How long to wait for the backend before falling back to the next one.
+    #[serde(with = "humantime_serde", default = "default_simulation_timeout")]
+    timeout: Duration,
+
+    /// The simulations the backend is used for.
+    #[serde(default = "default_simulation_operations")]
+    operations: Vec<SimulationOperation>,
+}
+
+fn default_simulation_timeout() -> Duration {
+    Duration::from_secs(2)
+}
+
+fn default_simulation_operations() -> Vec<SimulationOperation> {
+    vec![SimulationOperation::AccessList, SimulationOperation::Gas]
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum SimulationBackend {
+    Tenderly,
+    Enso,
+    Ethereum,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum SimulationOperation {
+    AccessList,
+    Gas,
+}
+
 #[derive(Clone, Debug, Default, Deserialize)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 struct LiquidityConfig {
@@ -803,6 +966,67 @@ fn default_settle_queue_size() -> usize {
     2
 }
 
+#[serde_as]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields, default)]
+struct SolverPolicy {
+    /// If set, only orders trading exclusively these tokens are sent to the
+    /// solver.
+    allowed_tokens: Option<Vec<eth::H160>>,
+
+    /// Orders trading any of these tokens are never sent to the solver.
+    denied_tokens: Vec<eth::H160>,
+
+    /// If set, only orders trading one of these token pairs, in either
+    /// direction, are sent to the solver.
+    allowed_pairs: Option<Vec<[eth::H160; 2]>>,
+
+    /// Orders trading any of these token pairs, in either direction, are never
+    /// sent to the solver.
+    denied_pairs: Vec<[eth::H160; 2]>,
+
+    /// Orders whose sell amount is worth more than this many wei, or whose sell
+    /// token has no native price, are not sent to the solver.
+    #[serde_as(as = "Option<serialize::U256>")]
+    max_order_notional: Option<eth::U256>,
+
+    /// Whether market orders are sent to the solver.
+    market_orders: bool,
+
+    /// Whether limit orders are sent to the solver.
+    limit_orders: bool,
+
+    /// Whether liquidity orders, like CoW AMM template orders, are sent to the
+    /// solver.
+    liquidity_orders: bool,
+}
+
+/// Unset fields default to the values of the default domain policy.
+impl Default for SolverPolicy {
+    fn default() -> Self {
+        let policy = domain::competition::auction::Policy::default();
+        let token = |token: eth::TokenAddress| token.0 .0;
+        let pair = |pair: domain::liquidity::TokenPair| {
+            let (a, b) = pair.get();
+            [token(a), token(b)]
+        };
+        Self {
+            allowed_tokens: policy
+                .allowed_tokens
+                .map(|tokens| tokens.into_iter().map(token).collect()),
+            denied_tokens: policy.denied_tokens.into_iter().map(token).collect(),
+            allowed_pairs: policy
+                .allowed_pairs
+                .map(|pairs| pairs.into_iter().map(pair).collect()),
+            denied_pairs: policy.denied_pairs.into_iter().map(pair).collect(),
+            max_order_notional: policy.max_order_notional.map(|notional| notional.0),
+            market_orders: policy.market_orders,
+            limit_orders: policy.limit_orders,
+            liquidity_orders: policy.liquidity_orders,
+        }
+    }
+}
+
 fn default_metrics_bad_token_detector_log_only() -> bool {
     true
 }
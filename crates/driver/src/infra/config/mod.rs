@@ -10,7 +10,7 @@ use {
             solver,
         },
     },
-    std::time::Duration,
+    std::{path::PathBuf, time::Duration},
     url::Url,
 };
 
@@ -30,5 +30,7 @@ pub struct Config {
     pub order_priority_strategies: Vec<OrderPriorityStrategy>,
     pub archive_node_url: Option<Url>,
     pub simulation_bad_token_max_age: Duration,
+    /// Local file in which the state of the bad token detectors is persisted.
+    pub bad_token_cache_file: Option<PathBuf>,
     pub app_data_fetching: AppDataFetching,
 }
@@ -5,6 +5,8 @@ use {
         infra,
     },
     ethcontract::dyns::DynWeb3,
+    serde_json::json,
+    web3::signing::{self, Key, SecretKeyRef},
 };
 
 #[derive(Debug, Clone)]
@@ -31,6 +33,17 @@ pub enum Kind {
         additional_tip_percentage: f64,
         use_soft_cancellations: bool,
     },
+    /// A relay accepting signed transactions as bundles via `eth_sendBundle`,
+    /// like Flashbots. Bundles only target a single block and never get
+    /// included if they revert.
+    BundleRelay {
+        url: reqwest::Url,
+        /// The key to authenticate bundles with using the
+        /// `X-Flashbots-Signature` header, for relays that require it.
+        signing_key: Option<ethcontract::PrivateKey>,
+        max_additional_tip: eth::U256,
+        additional_tip_percentage: f64,
+    },
 }
 
 impl Kind {
@@ -39,6 +52,7 @@ impl Kind {
         match self {
             Kind::Public { .. } => "PublicMempool",
             Kind::MEVBlocker { .. } => "MEVBlocker",
+            Kind::BundleRelay { .. } => "BundleRelay",
         }
     }
 }
@@ -57,6 +71,7 @@ pub enum RevertProtection {
 #[derive(Debug, Clone)]
 pub struct Mempool {
     transport: DynWeb3,
+    client: reqwest::Client,
     config: Config,
 }
 
@@ -69,11 +84,17 @@ impl std::fmt::Display for Mempool {
 impl Mempool {
     pub fn new(config: Config, transport: DynWeb3) -> Self {
         let transport = match &config.kind {
-            Kind::Public { .. } => transport,
+            // Bundle relays only accept bundles, so transactions are prepared
+            // using the node.
+            Kind::Public { .. } | Kind::BundleRelay { .. } => transport,
             // Flashbots Protect RPC fallback doesn't support buffered transport
             Kind::MEVBlocker { url, .. } => unbuffered_web3_client(url),
         };
-        Self { config, transport }
+        Self {
+            config,
+            transport,
+            client: reqwest::Client::new(),
+        }
     }
 
     /// Submits a transaction to the mempool. Returns optimistically as soon as
@@ -84,6 +105,16 @@ impl Mempool {
         gas: competition::solution::settlement::Gas,
         solver: &infra::Solver,
     ) -> Result<eth::TxId, mempools::Error> {
+        if let Kind::BundleRelay {
+            url, signing_key, ..
+        } = &self.config.kind
+        {
+            return self
+                .submit_bundle(tx, gas, solver, url, signing_key.as_ref())
+                .await
+                .map_err(mempools::Error::Other);
+        }
+
         ethcontract::transaction::TransactionBuilder::new(self.transport.clone())
             .from(solver.account().clone())
             .to(tx.to.into())
@@ -102,6 +133,65 @@ impl Mempool {
             .map_err(|err| mempools::Error::Other(anyhow::Error::from(err)))
     }
 
+    /// Signs the transaction and submits it as a bundle targeting the next
+    /// block.
+    async fn submit_bundle(
+        &self,
+        tx: eth::Tx,
+        gas: competition::solution::settlement::Gas,
+        solver: &infra::Solver,
+        url: &reqwest::Url,
+        signing_key: Option<&ethcontract::PrivateKey>,
+    ) -> anyhow::Result<eth::TxId> {
+        let signed = ethcontract::transaction::TransactionBuilder::new(self.transport.clone())
+            .from(solver.account().clone())
+            .to(tx.to.into())
+            .gas_price(ethcontract::GasPrice::Eip1559 {
+                max_fee_per_gas: gas.price.max().into(),
+                max_priority_fee_per_gas: gas.price.tip().into(),
+            })
+            .data(tx.input.into())
+            .value(tx.value.0)
+            .gas(gas.limit.0)
+            .access_list(web3::types::AccessList::from(tx.access_list))
+            .build()
+            .await?;
+        let ethcontract::transaction::Transaction::Raw { bytes, hash } = signed else {
+            anyhow::bail!("bundles can only be submitted for accounts with local signing");
+        };
+        let block = self.transport.eth().block_number().await?;
+
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_sendBundle",
+            "params": [{
+                "txs": [format!("0x{}", hex::encode(bytes.0))],
+                "blockNumber": format!("{:#x}", block + 1),
+            }],
+        })
+        .to_string();
+        let mut request = self
+            .client
+            .post(url.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+        if let Some(key) = signing_key {
+            request = request.header("X-Flashbots-Signature", bundle_signature(&body, key));
+        }
+        let response: serde_json::Value = request
+            .body(body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        if let Some(error) = response.get("error") {
+            anyhow::bail!("relay rejected bundle: {error}");
+        }
+
+        Ok(eth::TxId(hash))
+    }
+
     pub fn config(&self) -> &Config {
         &self.config
     }
@@ -109,7 +199,31 @@ impl Mempool {
     pub fn may_revert(&self) -> bool {
         match &self.config.kind {
             Kind::Public { .. } => true,
-            Kind::MEVBlocker { .. } => false,
+            Kind::MEVBlocker { .. } | Kind::BundleRelay { .. } => false,
         }
     }
+
+    /// Whether the mempool submits bundles, which are only valid for a single
+    /// block and need to be resubmitted for every block until they get
+    /// included.
+    pub fn submits_bundles(&self) -> bool {
+        matches!(self.config.kind, Kind::BundleRelay { .. })
+    }
+}
+
+/// Computes the `X-Flashbots-Signature` header value for a request body, i.e.
+/// the signing address and its EIP-191 signature of the hex encoded hash of the
+/// body.
+fn bundle_signature(body: &str, key: &ethcontract::PrivateKey) -> String {
+    let message = format!("0x{}", hex::encode(signing::keccak256(body.as_bytes())));
+    let hash = signing::hash_message(message.as_bytes());
+    // Unwrap because the only error is for invalid messages which we don't create.
+    let signature = SecretKeyRef::new(key).sign(hash.as_bytes(), None).unwrap();
+    format!(
+        "{:?}:0x{}{}{:02x}",
+        key.public_address(),
+        hex::encode(signature.r),
+        hex::encode(signature.s),
+        signature.v,
+    )
 }
@@ -1,4 +1,5 @@
 pub mod api;
+pub mod bad_tokens;
 pub mod blockchain;
 pub mod cli;
 pub mod config;
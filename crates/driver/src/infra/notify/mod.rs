@@ -108,7 +108,7 @@ pub fn executed(
         Err(Error::Revert { tx_id: hash, .. }) => notification::Settlement::Revert(hash.clone()),
         Err(Error::SimulationRevert { .. }) => notification::Settlement::SimulationRevert,
         Err(Error::Expired) => notification::Settlement::Expired,
-        Err(Error::Other(_) | Error::Disabled) => notification::Settlement::Fail,
+        Err(Error::Other(_) | Error::Disabled | Error::Cancelled) => notification::Settlement::Fail,
     };
 
     solver.notify(
@@ -13,12 +13,22 @@ pub struct Metrics {
     /// The results of the settlement process.
     #[metric(labels("solver", "result"))]
     pub settlements: prometheus::IntCounterVec,
+    /// The final statuses of settlements whose cancellation was requested.
+    #[metric(labels("solver", "result"))]
+    pub cancellations: prometheus::IntCounterVec,
     /// The results of the quoting process.
     #[metric(labels("solver", "result"))]
     pub quotes: prometheus::IntCounterVec,
     /// The results of the mempool submission.
     #[metric(labels("mempool", "result"))]
     pub mempool_submission: prometheus::IntCounterVec,
+    /// The results of simulations per backend of the composite simulator.
+    #[metric(labels("backend", "operation", "result"))]
+    pub simulations: prometheus::IntCounterVec,
+    /// How often simulator backends disagreed on whether a transaction
+    /// reverts.
+    #[metric(labels("reverted", "succeeded", "operation"))]
+    pub simulation_disagreements: prometheus::IntCounterVec,
     /// How many tokens detected by specific solver and strategy.
     #[metric(labels("solver", "strategy"))]
     pub bad_tokens_detected: prometheus::IntCounterVec,
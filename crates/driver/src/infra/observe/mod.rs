@@ -125,6 +125,16 @@ pub fn encoding_failed(solver: &solver::Name, id: &solution::Id, err: &solution:
         .inc();
 }
 
+/// Observe that encoding a merged solution failed, for example because the
+/// merged settlement reverted in simulation.
+pub fn merged_encoding_failed(solver: &solver::Name, id: &solution::Id, err: &solution::Error) {
+    tracing::debug!(?id, ?err, "discarded merged solution: settlement encoding");
+    metrics::get()
+        .dropped_solutions
+        .with_label_values(&[solver.as_str(), "MergedSettlementEncoding"])
+        .inc();
+}
+
 /// Observe that two solutions were merged.
 pub fn merged(first: &Solution, other: &Solution, result: &Solution) {
     tracing::debug!(?first, ?other, ?result, "merged solutions");
@@ -168,6 +178,52 @@ pub fn winner_voided(block: BlockInfo, err: &simulator::RevertError) {
     tracing::warn!(block = block.number, ?err, "solution reverts on new block");
 }
 
+/// Observe the result of a simulation with one of the backends of a composite
+/// simulator.
+pub fn simulated<T>(
+    backend: &str,
+    operation: simulator::composite::Operation,
+    result: &Result<T, simulator::Error>,
+) {
+    let result = match result {
+        Ok(_) => "Success",
+        Err(simulator::Error::Revert(_)) => "Revert",
+        Err(simulator::Error::Other(simulator::SimulatorError::Timeout(_))) => "Timeout",
+        Err(err @ simulator::Error::Other(_)) => {
+            tracing::warn!(
+                backend,
+                operation = operation.as_str(),
+                ?err,
+                "simulation failed"
+            );
+            "Error"
+        }
+    };
+    metrics::get()
+        .simulations
+        .with_label_values(&[backend, operation.as_str(), result])
+        .inc();
+}
+
+/// Observe that two simulator backends disagreed on whether a transaction
+/// reverts.
+pub fn simulation_disagreement(
+    reverted: &str,
+    succeeded: &str,
+    operation: simulator::composite::Operation,
+) {
+    tracing::warn!(
+        reverted,
+        succeeded,
+        operation = operation.as_str(),
+        "simulator backends disagree on whether the transaction reverts"
+    );
+    metrics::get()
+        .simulation_disagreements
+        .with_label_values(&[reverted, succeeded, operation.as_str()])
+        .inc();
+}
+
 pub fn revealing() {
     tracing::trace!("revealing");
 }
@@ -216,6 +272,28 @@ pub fn settled(solver: &solver::Name, result: &Result<competition::Settled, comp
     }
 }
 
+/// Observe that a cancellation of a settlement was requested.
+pub fn cancelling() {
+    tracing::trace!("cancelling settlement");
+}
+
+/// Observe the final status of a settlement whose cancellation was requested.
+pub fn cancelled(solver: &solver::Name, cancellation: &competition::Cancellation) {
+    tracing::info!(?cancellation, "cancelled settlement");
+    let result = match cancellation {
+        competition::Cancellation::NotFound => "NotFound",
+        competition::Cancellation::NotSubmitted => "NotSubmitted",
+        competition::Cancellation::NotCancellable => "NotCancellable",
+        competition::Cancellation::Cancelled => "Cancelled",
+        competition::Cancellation::Settled(_) => "Settled",
+        competition::Cancellation::Failed => "Failed",
+    };
+    metrics::get()
+        .cancellations
+        .with_label_values(&[solver.as_str(), result])
+        .inc();
+}
+
 /// Observe the result of solving an auction.
 pub fn solved(solver: &solver::Name, result: &Result<Option<Solved>, competition::Error>) {
     match result {
@@ -343,6 +421,7 @@ pub fn mempool_executed(
         Err(mempools::Error::Expired) => "Expired",
         Err(mempools::Error::Other(_)) => "Other",
         Err(mempools::Error::Disabled) => "Disabled",
+        Err(mempools::Error::Cancelled) => "Cancelled",
     };
     metrics::get()
         .mempool_submission
@@ -368,7 +447,9 @@ fn competition_error(err: &competition::Error) -> &'static str {
         competition::Error::Solver(solver::Error::Deserialize(_)) => "SolverDeserializeError",
         competition::Error::Solver(solver::Error::Dto(_)) => "SolverDtoError",
         competition::Error::SubmissionError => "SubmissionError",
+        competition::Error::Reverted => "Reverted",
         competition::Error::TooManyPendingSettlements => "TooManyPendingSettlements",
+        competition::Error::Cancelled => "Cancelled",
     }
 }
 
@@ -0,0 +1,254 @@
+use {
+    crate::{
+        domain::{
+            competition::{self, order, solution},
+            eth,
+            mempools,
+        },
+        infra::simulator,
+        util::serialize,
+    },
+    serde::Serialize,
+    serde_with::serde_as,
+    std::collections::HashMap,
+};
+
+impl Solution {
+    pub fn new(solution: &competition::Solution) -> Self {
+        Self {
+            id: solution.id().get(),
+            solver_solutions: solution.id().solutions().to_vec(),
+            solver: solution.solver().name().as_str().to_owned(),
+            clearing_prices: solution
+                .clearing_prices()
+                .into_iter()
+                .map(|(token, price)| (token.into(), price))
+                .collect(),
+            trades: solution
+                .trades()
+                .iter()
+                .map(|trade| Trade {
+                    kind: match trade {
+                        solution::Trade::Fulfillment(_) => TradeKind::Fulfillment,
+                        solution::Trade::Jit(_) => TradeKind::Jit,
+                    },
+                    order: trade.uid().into(),
+                    executed: trade.executed().0,
+                })
+                .collect(),
+            gas: solution.gas().map(|gas| gas.0),
+            flashloans: solution
+                .flashloans()
+                .iter()
+                .map(|flashloan| Flashloan {
+                    lender: flashloan.lender.into(),
+                    borrower: flashloan.borrower.into(),
+                    token: flashloan.token.into(),
+                    amount: flashloan.amount.into(),
+                })
+                .collect(),
+        }
+    }
+}
+
+type OrderId = [u8; order::UID_LEN];
+
+/// An auction as it was sent to the solver engine, along with the block the
+/// driver was at when sending it.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Auction<T> {
+    pub block: u64,
+    #[serde(flatten)]
+    pub auction: T,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Solution {
+    id: u64,
+    /// The IDs of the solutions returned by the solver that make up this
+    /// solution. There is more than one for merged solutions.
+    solver_solutions: Vec<u64>,
+    solver: String,
+    #[serde_as(as = "HashMap<_, serialize::U256>")]
+    clearing_prices: HashMap<eth::H160, eth::U256>,
+    trades: Vec<Trade>,
+    #[serde_as(as = "Option<serialize::U256>")]
+    gas: Option<eth::U256>,
+    flashloans: Vec<Flashloan>,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Trade {
+    kind: TradeKind,
+    #[serde_as(as = "serialize::Hex")]
+    order: OrderId,
+    #[serde_as(as = "serialize::U256")]
+    executed: eth::U256,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+enum TradeKind {
+    Fulfillment,
+    Jit,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Flashloan {
+    lender: eth::H160,
+    borrower: eth::H160,
+    token: eth::H160,
+    #[serde_as(as = "serialize::U256")]
+    amount: eth::U256,
+}
+
+impl Settlement {
+    pub fn new(result: &Result<solution::Settlement, solution::Error>) -> Self {
+        match result {
+            Ok(settlement) => Self::Success {
+                internalized: Tx::new(
+                    settlement.transaction(solution::settlement::Internalization::Enable),
+                ),
+                uninternalized: Tx::new(
+                    settlement.transaction(solution::settlement::Internalization::Disable),
+                ),
+                gas: Gas {
+                    estimate: settlement.gas.estimate.0,
+                    limit: settlement.gas.limit.0,
+                    max_fee_per_gas: settlement.gas.price.max().0 .0,
+                    max_priority_fee_per_gas: settlement.gas.price.tip().0 .0,
+                },
+            },
+            Err(err) => {
+                let revert = match err {
+                    solution::Error::Simulation(simulator::Error::Revert(revert)) => Some(Revert {
+                        block: revert.block.0,
+                        tx: Tx::new(&revert.tx),
+                    }),
+                    _ => None,
+                };
+                Self::Failure {
+                    error: err.to_string(),
+                    revert,
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase", tag = "result")]
+pub enum Settlement {
+    #[serde(rename_all = "camelCase")]
+    Success {
+        internalized: Tx,
+        uninternalized: Tx,
+        gas: Gas,
+    },
+    #[serde(rename_all = "camelCase")]
+    Failure {
+        error: String,
+        /// The reverting transaction if the settlement failed simulation.
+        revert: Option<Revert>,
+    },
+}
+
+impl Tx {
+    fn new(tx: &eth::Tx) -> Self {
+        Self {
+            from: tx.from.into(),
+            to: tx.to.into(),
+            input: tx.input.clone().into(),
+            value: tx.value.into(),
+        }
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tx {
+    from: eth::H160,
+    to: eth::H160,
+    #[serde_as(as = "serialize::Hex")]
+    input: Vec<u8>,
+    #[serde_as(as = "serialize::U256")]
+    value: eth::U256,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Gas {
+    #[serde_as(as = "serialize::U256")]
+    estimate: eth::U256,
+    #[serde_as(as = "serialize::U256")]
+    limit: eth::U256,
+    #[serde_as(as = "serialize::U256")]
+    max_fee_per_gas: eth::U256,
+    #[serde_as(as = "serialize::U256")]
+    max_priority_fee_per_gas: eth::U256,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Revert {
+    block: u64,
+    tx: Tx,
+}
+
+impl Execution {
+    pub fn new(result: &Result<eth::TxId, mempools::Error>) -> Self {
+        match result {
+            Ok(tx_id) => Self::Success { tx_hash: tx_id.0 },
+            Err(mempools::Error::Revert {
+                tx_id,
+                block_number,
+            }) => Self::Revert {
+                tx_hash: tx_id.0,
+                block: *block_number,
+            },
+            Err(mempools::Error::SimulationRevert(block)) => {
+                Self::SimulationRevert { block: *block }
+            }
+            Err(mempools::Error::Expired) => Self::Expired,
+            Err(mempools::Error::Disabled) => Self::Disabled,
+            Err(mempools::Error::Cancelled) => Self::Cancelled,
+            Err(err @ mempools::Error::Other(_)) => Self::Failed {
+                error: err.to_string(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase", tag = "result")]
+pub enum Execution {
+    #[serde(rename_all = "camelCase")]
+    Success {
+        tx_hash: eth::H256,
+    },
+    #[serde(rename_all = "camelCase")]
+    Revert {
+        tx_hash: eth::H256,
+        block: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    SimulationRevert {
+        block: Option<u64>,
+    },
+    Expired,
+    Disabled,
+    Cancelled,
+    #[serde(rename_all = "camelCase")]
+    Failed {
+        error: String,
+    },
+}
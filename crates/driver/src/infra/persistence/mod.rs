@@ -1,14 +1,21 @@
 use {
     crate::{
-        domain::competition::auction::Id,
+        domain::{
+            competition::{auction::Id, solution, Solution},
+            eth,
+            mempools,
+        },
         infra::{config::file, solver::Config},
     },
+    anyhow::Context,
     serde::Serialize,
     serde_json::to_value,
-    std::sync::Arc,
+    std::{path::PathBuf, time::Duration},
     tracing::Instrument,
 };
 
+mod dto;
+
 #[derive(Clone, Debug, Default)]
 pub struct S3 {
     /// Name of the AWS S3 bucket in which the auctions will be stored
@@ -30,50 +37,156 @@ impl From<file::S3> for S3 {
 
 impl From<S3> for s3::Config {
     fn from(value: S3) -> Self {
-        Self {
-            bucket: value.bucket,
-            filename_prefix: value.prefix,
-        }
+        Self::s3(value.bucket, value.prefix)
     }
 }
 
+/// Returns how data gets archived, or `None` if archiving is disabled.
+fn archive_config(
+    s3: Option<S3>,
+    archive_dir: Option<PathBuf>,
+    gzip: Option<bool>,
+    naming: s3::Naming,
+    retention: Option<Duration>,
+) -> Option<s3::Config> {
+    let (backend, filename_prefix) = match (s3, archive_dir) {
+        (Some(s3), None) => (s3::Backend::S3 { bucket: s3.bucket }, s3.prefix),
+        (None, Some(dir)) => (s3::Backend::Local { dir }, String::new()),
+        (None, None) => return None,
+        (Some(_), Some(_)) => panic!("Cannot configure both S3 and a local archive directory"),
+    };
+    Some(s3::Config::archive(
+        backend,
+        filename_prefix,
+        gzip,
+        naming,
+        retention,
+    ))
+}
+
+/// Archives the auctions sent to the solver engine, as well as an audit trail
+/// of what happened to each proposed solution: the solution itself, its
+/// encoded and simulated settlement and the outcome of its submission. The
+/// audit trail is keyed by auction and solution id.
 #[derive(Clone, Debug)]
 pub struct Persistence {
-    s3: Option<Arc<s3::Uploader>>,
+    s3: Option<s3::Uploader>,
 }
 
 impl Persistence {
     pub async fn build(config: &Config) -> Self {
-        if let Some(s3) = &config.s3 {
-            Self {
-                s3: Some(Arc::new(s3::Uploader::new(s3.clone().into()).await)),
-            }
-        } else {
-            Self { s3: None }
+        let s3_config = archive_config(
+            config.s3.clone(),
+            config.archive_dir.clone(),
+            config.archive_gzip,
+            config.archive_naming,
+            config.archive_retention,
+        );
+        Self {
+            s3: match s3_config {
+                Some(s3_config) => Some(s3::Uploader::new(s3_config).await),
+                None => None,
+            },
         }
     }
 
     /// Saves the given auction with liquidity with fire and forget mentality
-    /// (non-blocking operation)
-    pub fn archive_auction(&self, auction_id: Id, body: impl Serialize) {
+    /// (non-blocking operation). The current block gets stored along with the
+    /// auction.
+    pub fn archive_auction(&self, auction_id: Id, block: u64, body: impl Serialize) {
+        self.archive(
+            auction_id.to_string(),
+            "auction with liquidity",
+            dto::Auction {
+                block,
+                auction: body,
+            },
+        );
+    }
+
+    /// Saves the `/solve` response the driver returned for an auction.
+    pub fn archive_response(&self, auction_id: Id, response: impl Serialize) {
+        self.archive(format!("{auction_id}/response"), "response", response);
+    }
+
+    /// Fetches the auction with liquidity archived for an auction.
+    pub async fn fetch_auction(&self, auction_id: Id) -> anyhow::Result<serde_json::Value> {
+        self.fetch(auction_id.to_string()).await
+    }
+
+    /// Fetches the `/solve` response archived for an auction.
+    pub async fn fetch_response(&self, auction_id: Id) -> anyhow::Result<serde_json::Value> {
+        self.fetch(format!("{auction_id}/response")).await
+    }
+
+    /// Saves a solution proposed by the solver, before it gets encoded.
+    pub fn archive_solution(&self, auction_id: Option<Id>, solution: &Solution) {
+        let Some(auction_id) = auction_id else {
+            return;
+        };
+        self.archive(
+            key(auction_id, solution.id(), "solution"),
+            "solution",
+            dto::Solution::new(solution),
+        );
+    }
+
+    /// Saves the settlement encoded for a solution, including the results of
+    /// simulating it.
+    pub fn archive_settlement(
+        &self,
+        auction_id: Option<Id>,
+        solution_id: &solution::Id,
+        result: &Result<solution::Settlement, solution::Error>,
+    ) {
+        let Some(auction_id) = auction_id else {
+            return;
+        };
+        self.archive(
+            key(auction_id, solution_id, "settlement"),
+            "settlement",
+            dto::Settlement::new(result),
+        );
+    }
+
+    /// Saves the outcome of submitting a settlement to the mempools.
+    pub fn archive_execution(
+        &self,
+        auction_id: Id,
+        solution_id: &solution::Id,
+        result: &Result<eth::TxId, mempools::Error>,
+    ) {
+        self.archive(
+            key(auction_id, solution_id, "execution"),
+            "execution",
+            dto::Execution::new(result),
+        );
+    }
+
+    async fn fetch(&self, key: String) -> anyhow::Result<serde_json::Value> {
+        let uploader = self.s3.as_ref().context("archiving is not configured")?;
+        uploader.download(&key).await
+    }
+
+    fn archive(&self, key: String, kind: &'static str, body: impl Serialize) {
         let Some(uploader) = self.s3.clone() else {
             return;
         };
         let body = match to_value(body) {
             Ok(body) => body,
             Err(err) => {
-                tracing::error!(?err, "failed to parse the auction with liquidity to JSON");
+                tracing::error!(?err, kind, "failed to serialize archive to JSON");
                 return;
             }
         };
         tokio::spawn(
             async move {
-                match uploader.upload(auction_id.to_string(), body).await {
+                match uploader.upload(key, body).await {
                     Ok(key) => {
-                        tracing::debug!(?key, "uploaded auction with liquidity to s3");
+                        tracing::debug!(?key, kind, "archived");
                     }
                     Err(err) => {
-                        tracing::warn!(?err, "failed to upload auction to s3");
+                        tracing::warn!(?err, kind, "failed to archive");
                     }
                 }
             }
@@ -81,3 +194,7 @@ impl Persistence {
         );
     }
 }
+
+fn key(auction_id: Id, solution_id: &solution::Id, kind: &str) -> String {
+    format!("{auction_id}/{}/{kind}", solution_id.get())
+}
@@ -0,0 +1,432 @@
+//! A simulator that tries multiple simulator backends in order, falling back
+//! to the next backend whenever one is unavailable.
+
+use {
+    super::{enso, tenderly, Error, Simulator, SimulatorError},
+    crate::{
+        domain::eth,
+        infra::{observe, Ethereum},
+    },
+    futures::{Future, FutureExt},
+    moka::future::Cache,
+    std::time::Duration,
+};
+
+/// Configuration of the composite simulator.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// The backends in the order in which they are tried.
+    pub backends: Vec<Backend>,
+    /// How many simulation results are cached per operation.
+    pub cache_size: u64,
+    /// When a backend reports a revert, confirm it with the next backend. Any
+    /// disagreement between the backends is recorded in the metrics and the
+    /// revert is reported.
+    pub confirm_reverts: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Backend {
+    pub kind: Kind,
+    /// How long to wait for the backend before falling back to the next one.
+    pub timeout: Duration,
+    /// Whether the backend is used for access list simulation.
+    pub access_list: bool,
+    /// Whether the backend is used for gas simulation.
+    pub gas: bool,
+}
+
+#[derive(Debug, Clone)]
+pub enum Kind {
+    Tenderly(tenderly::Config),
+    Enso(enso::Config),
+    Ethereum,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Operation {
+    AccessList,
+    Gas,
+}
+
+impl Operation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AccessList => "access_list",
+            Self::Gas => "gas",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Composite {
+    backends: Vec<Inner>,
+    access_lists: Cache<Key, eth::AccessList>,
+    gas: Cache<Key, eth::Gas>,
+    confirm_reverts: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Inner<S = Simulator> {
+    name: &'static str,
+    simulator: S,
+    timeout: Duration,
+    access_list: bool,
+    gas: bool,
+}
+
+impl<S> Inner<S> {
+    fn supports(&self, operation: Operation) -> bool {
+        match operation {
+            Operation::AccessList => self.access_list,
+            Operation::Gas => self.gas,
+        }
+    }
+}
+
+/// Simulation results are cached by the simulated transaction and the block
+/// at which it was simulated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Key {
+    tx: eth::H256,
+    block: u64,
+}
+
+impl Composite {
+    pub fn new(config: Config, eth: Ethereum) -> Self {
+        for operation in [Operation::AccessList, Operation::Gas] {
+            assert!(
+                config.backends.iter().any(|backend| match operation {
+                    Operation::AccessList => backend.access_list,
+                    Operation::Gas => backend.gas,
+                }),
+                "no simulator backend configured for {} simulation",
+                operation.as_str(),
+            );
+        }
+
+        Self {
+            backends: config
+                .backends
+                .into_iter()
+                .map(|backend| {
+                    let (name, simulator) = match backend.kind {
+                        Kind::Tenderly(config) => {
+                            ("tenderly", Simulator::tenderly(config, eth.clone()))
+                        }
+                        Kind::Enso(config) => ("enso", Simulator::enso(config, eth.clone())),
+                        Kind::Ethereum => ("ethereum", Simulator::ethereum(eth.clone())),
+                    };
+                    Inner {
+                        name,
+                        simulator,
+                        timeout: backend.timeout,
+                        access_list: backend.access_list,
+                        gas: backend.gas,
+                    }
+                })
+                .collect(),
+            access_lists: Cache::new(config.cache_size),
+            gas: Cache::new(config.cache_size),
+            confirm_reverts: config.confirm_reverts,
+        }
+    }
+
+    pub async fn access_list(&self, tx: &eth::Tx, block: u64) -> Result<eth::AccessList, Error> {
+        // The backends' futures need to be boxed, since backends are simulators
+        // themselves.
+        self.simulate(
+            Operation::AccessList,
+            tx,
+            block,
+            &self.access_lists,
+            |simulator| simulator.access_list(tx).boxed(),
+        )
+        .await
+    }
+
+    pub async fn gas(&self, tx: &eth::Tx, block: u64) -> Result<eth::Gas, Error> {
+        self.simulate(Operation::Gas, tx, block, &self.gas, |simulator| {
+            simulator.gas(tx).boxed()
+        })
+        .await
+    }
+
+    async fn simulate<'a, T, F, Fut>(
+        &'a self,
+        operation: Operation,
+        tx: &eth::Tx,
+        block: u64,
+        cache: &Cache<Key, T>,
+        simulate: F,
+    ) -> Result<T, Error>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: Fn(&'a Simulator) -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        let key = Key {
+            tx: hash(tx),
+            block,
+        };
+        fallback(
+            &self.backends,
+            self.confirm_reverts,
+            operation,
+            key,
+            cache,
+            simulate,
+        )
+        .await
+    }
+}
+
+/// Runs the simulation on the backends supporting the operation in order until
+/// one of them succeeds or reports a revert. Successful results are cached.
+async fn fallback<'a, S, T, F, Fut>(
+    backends: &'a [Inner<S>],
+    confirm_reverts: bool,
+    operation: Operation,
+    key: Key,
+    cache: &Cache<Key, T>,
+    simulate: F,
+) -> Result<T, Error>
+where
+    T: Clone + Send + Sync + 'static,
+    F: Fn(&'a S) -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    if let Some(result) = cache.get(&key).await {
+        return Ok(result);
+    }
+
+    let mut revert = None;
+    let mut error = None;
+    for backend in backends.iter().filter(|b| b.supports(operation)) {
+        let result = match tokio::time::timeout(backend.timeout, simulate(&backend.simulator)).await
+        {
+            Ok(result) => result,
+            Err(_) => Err(Error::Other(SimulatorError::Timeout(backend.timeout))),
+        };
+        observe::simulated(backend.name, operation, &result);
+
+        match (result, revert) {
+            (Ok(_), Some((reverted, err))) => {
+                observe::simulation_disagreement(reverted, backend.name, operation);
+                return Err(err);
+            }
+            (Ok(value), None) => {
+                cache.insert(key, value.clone()).await;
+                return Ok(value);
+            }
+            (Err(err @ Error::Revert(_)), None) if confirm_reverts => {
+                revert = Some((backend.name, err));
+            }
+            (Err(err @ Error::Revert(_)), None) => return Err(err),
+            (Err(Error::Revert(_)), Some((_, err))) => return Err(err),
+            (Err(err), previous) => {
+                revert = previous;
+                error = Some(err);
+            }
+        }
+    }
+
+    match (revert, error) {
+        (Some((_, err)), _) | (None, Some(err)) => Err(err),
+        (None, None) => unreachable!("at least one backend is configured per operation"),
+    }
+}
+
+/// A hash identifying the simulated transaction.
+fn hash(tx: &eth::Tx) -> eth::H256 {
+    let access_list: web3::types::AccessList = tx.access_list.clone().into();
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(tx.from.0.as_bytes());
+    bytes.extend_from_slice(tx.to.0.as_bytes());
+    bytes.extend_from_slice(&<[u8; 32]>::from(tx.value.0));
+    bytes.extend_from_slice(&tx.input.0);
+    for item in access_list {
+        bytes.extend_from_slice(item.address.as_bytes());
+        for key in item.storage_keys {
+            bytes.extend_from_slice(key.as_bytes());
+        }
+    }
+    eth::H256(web3::signing::keccak256(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::infra::{blockchain, simulator::RevertError},
+        std::sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    #[derive(Debug, Clone, Copy)]
+    enum Outcome {
+        Success(u64),
+        Revert,
+        Error,
+        Hang,
+    }
+
+    #[derive(Debug)]
+    struct Fake {
+        outcome: Outcome,
+        calls: AtomicUsize,
+    }
+
+    impl Fake {
+        fn simulate(&self) -> impl Future<Output = Result<u64, Error>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let outcome = self.outcome;
+            async move {
+                match outcome {
+                    Outcome::Success(gas) => Ok(gas),
+                    Outcome::Revert => Err(Error::Revert(RevertError {
+                        err: SimulatorError::Enso(enso::Error::Revert("reverted".to_string())),
+                        tx: eth::Tx {
+                            from: Default::default(),
+                            to: Default::default(),
+                            value: eth::U256::zero().into(),
+                            input: Default::default(),
+                            access_list: Default::default(),
+                        },
+                        block: 0.into(),
+                    })),
+                    Outcome::Error => Err(Error::Other(SimulatorError::Blockchain(
+                        blockchain::Error::Web3(web3::error::Error::Unreachable),
+                    ))),
+                    Outcome::Hang => futures::future::pending().await,
+                }
+            }
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+    }
+
+    fn fakes(outcomes: &[Outcome]) -> Vec<Inner<Fake>> {
+        outcomes
+            .iter()
+            .map(|outcome| Inner {
+                name: "fake",
+                simulator: Fake {
+                    outcome: *outcome,
+                    calls: Default::default(),
+                },
+                timeout: Duration::from_millis(10),
+                access_list: true,
+                gas: true,
+            })
+            .collect()
+    }
+
+    async fn simulate(
+        backends: &[Inner<Fake>],
+        confirm_reverts: bool,
+        cache: &Cache<Key, u64>,
+        block: u64,
+    ) -> Result<u64, Error> {
+        let key = Key {
+            tx: eth::H256::zero(),
+            block,
+        };
+        fallback(
+            backends,
+            confirm_reverts,
+            Operation::Gas,
+            key,
+            cache,
+            Fake::simulate,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn falls_back_on_timeout() {
+        let backends = fakes(&[Outcome::Hang, Outcome::Success(1)]);
+        let result = simulate(&backends, false, &Cache::new(10), 0).await;
+        assert_eq!(result.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn falls_back_on_error() {
+        let backends = fakes(&[Outcome::Error, Outcome::Success(2)]);
+        let result = simulate(&backends, false, &Cache::new(10), 0).await;
+        assert_eq!(result.unwrap(), 2);
+
+        let backends = fakes(&[Outcome::Error, Outcome::Hang]);
+        let result = simulate(&backends, false, &Cache::new(10), 0).await;
+        assert!(matches!(
+            result,
+            Err(Error::Other(SimulatorError::Timeout(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn skips_backends_not_supporting_operation() {
+        let mut backends = fakes(&[Outcome::Success(1), Outcome::Success(2)]);
+        backends[0].gas = false;
+        let result = simulate(&backends, false, &Cache::new(10), 0).await;
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(backends[0].simulator.calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn reports_reverts_immediately() {
+        let backends = fakes(&[Outcome::Revert, Outcome::Success(1)]);
+        let result = simulate(&backends, false, &Cache::new(10), 0).await;
+        assert!(matches!(result, Err(Error::Revert(_))));
+        assert_eq!(backends[1].simulator.calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn confirm_reverts() {
+        // Reverts are confirmed with the next backend, but still reported if
+        // the backends disagree.
+        for outcomes in [
+            [Outcome::Revert, Outcome::Revert],
+            [Outcome::Revert, Outcome::Success(1)],
+            [Outcome::Revert, Outcome::Error],
+        ] {
+            let backends = fakes(&outcomes);
+            let cache = Cache::new(10);
+            let result = simulate(&backends, true, &cache, 0).await;
+            assert!(matches!(result, Err(Error::Revert(_))));
+            assert_eq!(backends[1].simulator.calls(), 1);
+            assert_eq!(cache.entry_count(), 0);
+        }
+
+        // Backends failing to simulate don't count as a confirmation.
+        let backends = fakes(&[Outcome::Error, Outcome::Revert, Outcome::Success(1)]);
+        let result = simulate(&backends, true, &Cache::new(10), 0).await;
+        assert!(matches!(result, Err(Error::Revert(_))));
+        assert_eq!(backends[2].simulator.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn caches_results_per_block() {
+        let backends = fakes(&[Outcome::Success(1)]);
+        let cache = Cache::new(10);
+
+        assert_eq!(simulate(&backends, false, &cache, 0).await.unwrap(), 1);
+        assert_eq!(simulate(&backends, false, &cache, 0).await.unwrap(), 1);
+        assert_eq!(backends[0].simulator.calls(), 1);
+
+        // Cached results expire with a new block.
+        assert_eq!(simulate(&backends, false, &cache, 1).await.unwrap(), 1);
+        assert_eq!(backends[0].simulator.calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn does_not_cache_failures() {
+        let backends = fakes(&[Outcome::Error]);
+        let cache = Cache::new(10);
+
+        assert!(simulate(&backends, false, &cache, 0).await.is_err());
+        assert!(simulate(&backends, false, &cache, 0).await.is_err());
+        assert_eq!(backends[0].simulator.calls(), 2);
+    }
+}
@@ -6,6 +6,7 @@ use {
     observe::future::Measure,
 };
 
+pub mod composite;
 pub mod enso;
 pub mod tenderly;
 
@@ -25,6 +26,7 @@ pub struct Simulator {
 pub enum Config {
     Tenderly(tenderly::Config),
     Enso(enso::Config),
+    Composite(composite::Config),
 }
 
 impl Simulator {
@@ -66,6 +68,17 @@ impl Simulator {
         }
     }
 
+    /// Simulate transactions with multiple backends, falling back to the next
+    /// backend whenever one fails or times out.
+    pub fn composite(config: composite::Config, eth: Ethereum) -> Self {
+        Self {
+            inner: Inner::Composite(composite::Composite::new(config, eth.clone())),
+            eth,
+            disable_access_lists: false,
+            disable_gas: None,
+        }
+    }
+
     /// Disable access list simulation. Some environments, such as less popular
     /// blockchains, don't support access list simulation.
     pub fn disable_access_lists(&mut self) {
@@ -104,6 +117,7 @@ impl Simulator {
                 .create_access_list(tx.clone())
                 .await
                 .map_err(with(tx.clone(), block))?,
+            Inner::Composite(composite) => composite.access_list(tx, block.0).await?,
         };
         Ok(tx.access_list.clone().merge(access_list))
     }
@@ -133,6 +147,7 @@ impl Simulator {
                 .measure("enso_simulate_gas")
                 .await
                 .map_err(with(tx.clone(), block))?,
+            Inner::Composite(composite) => composite.gas(tx, block.0).await?,
         })
     }
 }
@@ -142,6 +157,7 @@ enum Inner {
     Tenderly(tenderly::Tenderly),
     Ethereum,
     Enso(enso::Enso),
+    Composite(composite::Composite),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -154,6 +170,8 @@ pub enum SimulatorError {
     Enso(#[from] enso::Error),
     #[error("the simulated gas {0} exceeded the gas limit {1} provided in the solution")]
     GasExceeded(eth::Gas, eth::Gas),
+    #[error("simulation timed out after {0:?}")]
+    Timeout(std::time::Duration),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -195,6 +213,7 @@ where
             SimulatorError::Enso(enso::Error::Http(_)) => None,
             SimulatorError::Enso(enso::Error::Revert(_)) => Some(tx),
             SimulatorError::GasExceeded(..) => Some(tx),
+            SimulatorError::Timeout(_) => None,
         };
         match tx {
             Some(tx) => Error::Revert(RevertError { err, tx, block }),
@@ -34,6 +34,7 @@ impl Auction {
         fee_handler: FeeHandler,
         solver_native_token: ManageNativeToken,
         flashloans_enabled: bool,
+        extended_liquidity_kinds: bool,
     ) -> Self {
         let mut tokens: HashMap<eth::H160, _> = auction
             .tokens()
@@ -75,6 +76,7 @@ impl Auction {
             tokens.entry(token.into()).or_insert_with(Default::default);
         }
 
+        let prices = auction.prices();
         Self {
             id: auction.id().as_ref().map(ToString::to_string),
             orders: auction
@@ -95,8 +97,31 @@ impl Auction {
                     //
                     // https://github.com/cowprotocol/services/issues/2440
                     if fee_handler == FeeHandler::Driver {
+                        // The notional of tiered volume fees if the available amounts get
+                        // executed.
+                        let notional = {
+                            let (token, amount) = match order.side {
+                                Side::Sell => (order.sell.token, available.sell.amount),
+                                Side::Buy => (order.buy.token, available.buy.amount),
+                            };
+                            prices.get(&token).map(|price| price.in_eth(amount))
+                        };
                         order.protocol_fees.iter().for_each(|protocol_fee| {
-                            if let fees::FeePolicy::Volume { factor } = protocol_fee {
+                            let factor = match protocol_fee {
+                                fees::FeePolicy::Volume { factor } => Some(*factor),
+                                // Fill-or-kill orders are executed with exactly that
+                                // notional. Partially fillable orders can be executed
+                                // with any smaller notional, so the highest factor of
+                                // those tiers is assumed.
+                                fees::FeePolicy::TieredVolume { tiers } => match notional {
+                                    Some(notional) if !order.is_partial() => {
+                                        Some(fees::tiered_volume_factor(tiers, notional))
+                                    }
+                                    _ => fees::max_tiered_volume_factor(tiers, notional),
+                                },
+                                _ => None,
+                            };
+                            if let Some(factor) = factor {
                                 match order.side {
                                     Side::Buy => {
                                         // reduce sell amount by factor
@@ -216,60 +241,11 @@ impl Auction {
                             fee: rational_to_big_decimal(&pool.fee.0),
                         })
                     }
-                    liquidity::Kind::BalancerV2Stable(pool) => Liquidity::Stable(StablePool {
-                        id: liquidity.id.into(),
-                        address: pool.id.address().into(),
-                        balancer_pool_id: pool.id.into(),
-                        gas_estimate: liquidity.gas.into(),
-                        tokens: pool
-                            .reserves
-                            .iter()
-                            .map(|r| {
-                                (
-                                    r.asset.token.into(),
-                                    StableReserve {
-                                        balance: r.asset.amount.into(),
-                                        scaling_factor: scaling_factor_to_decimal(r.scale),
-                                    },
-                                )
-                            })
-                            .collect(),
-                        amplification_parameter: rational_to_big_decimal(&num::BigRational::new(
-                            pool.amplification_parameter.factor().to_big_int(),
-                            pool.amplification_parameter.precision().to_big_int(),
-                        )),
-                        fee: fee_to_decimal(pool.fee),
-                    }),
+                    liquidity::Kind::BalancerV2Stable(pool) => {
+                        Liquidity::balancer_v2_stable(liquidity, pool, extended_liquidity_kinds)
+                    }
                     liquidity::Kind::BalancerV2Weighted(pool) => {
-                        Liquidity::WeightedProduct(WeightedProductPool {
-                            id: liquidity.id.into(),
-                            address: pool.id.address().into(),
-                            balancer_pool_id: pool.id.into(),
-                            gas_estimate: liquidity.gas.into(),
-                            tokens: pool
-                                .reserves
-                                .iter()
-                                .map(|r| {
-                                    (
-                                        r.asset.token.into(),
-                                        WeightedProductReserve {
-                                            balance: r.asset.amount.into(),
-                                            scaling_factor: scaling_factor_to_decimal(r.scale),
-                                            weight: weight_to_decimal(r.weight),
-                                        },
-                                    )
-                                })
-                                .collect(),
-                            fee: fee_to_decimal(pool.fee),
-                            version: match pool.version {
-                                liquidity::balancer::v2::weighted::Version::V0 => {
-                                    WeightedProductVersion::V0
-                                }
-                                liquidity::balancer::v2::weighted::Version::V3Plus => {
-                                    WeightedProductVersion::V3Plus
-                                }
-                            },
-                        })
+                        Liquidity::balancer_v2_weighted(liquidity, pool, extended_liquidity_kinds)
                     }
                     liquidity::Kind::Swapr(pool) => {
                         Liquidity::ConstantProduct(ConstantProductPool {
@@ -411,6 +387,17 @@ pub enum FeePolicy {
     },
     #[serde(rename_all = "camelCase")]
     Volume { factor: f64 },
+    #[serde(rename_all = "camelCase")]
+    TieredVolume { tiers: Vec<VolumeTier> },
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeTier {
+    #[serde_as(as = "serialize::U256")]
+    pub min_notional: eth::U256,
+    pub factor: f64,
 }
 
 impl FeePolicy {
@@ -437,6 +424,15 @@ impl FeePolicy {
                 },
             },
             order::FeePolicy::Volume { factor } => FeePolicy::Volume { factor },
+            order::FeePolicy::TieredVolume { tiers } => FeePolicy::TieredVolume {
+                tiers: tiers
+                    .into_iter()
+                    .map(|tier| VolumeTier {
+                        min_notional: tier.min_notional.0,
+                        factor: tier.factor,
+                    })
+                    .collect(),
+            },
         }
     }
 }
@@ -473,11 +469,121 @@ struct Token {
 enum Liquidity {
     ConstantProduct(ConstantProductPool),
     WeightedProduct(WeightedProductPool),
+    LiquidityBootstrapping(LiquidityBootstrappingPool),
     Stable(StablePool),
+    ComposableStable(StablePool),
     ConcentratedLiquidity(ConcentratedLiquidityPool),
     LimitOrder(ForeignLimitOrder),
 }
 
+impl Liquidity {
+    /// Converts a Balancer V2 stable pool. Composable stable pools are only
+    /// sent as such to solvers that support the `composableStable` kind.
+    fn balancer_v2_stable(
+        liquidity: &liquidity::Liquidity,
+        pool: &liquidity::balancer::v2::stable::Pool,
+        extended_kinds: bool,
+    ) -> Self {
+        let stable = StablePool {
+            id: liquidity.id.into(),
+            address: pool.id.address().into(),
+            balancer_pool_id: pool.id.into(),
+            gas_estimate: liquidity.gas.into(),
+            tokens: pool
+                .reserves
+                .iter()
+                .map(|r| {
+                    (
+                        r.asset.token.into(),
+                        StableReserve {
+                            balance: r.asset.amount.into(),
+                            scaling_factor: scaling_factor_to_decimal(r.scale),
+                        },
+                    )
+                })
+                .collect(),
+            amplification_parameter: rational_to_big_decimal(&num::BigRational::new(
+                pool.amplification_parameter.factor().to_big_int(),
+                pool.amplification_parameter.precision().to_big_int(),
+            )),
+            fee: fee_to_decimal(pool.fee),
+        };
+        if extended_kinds && pool.is_composable() {
+            Self::ComposableStable(stable)
+        } else {
+            Self::Stable(stable)
+        }
+    }
+
+    /// Converts a Balancer V2 weighted pool. Liquidity bootstrapping pools are
+    /// only sent with their weight update to solvers that support the
+    /// `liquidityBootstrapping` kind, other solvers get the weights at the
+    /// block the liquidity was fetched at.
+    fn balancer_v2_weighted(
+        liquidity: &liquidity::Liquidity,
+        pool: &liquidity::balancer::v2::weighted::Pool,
+        extended_kinds: bool,
+    ) -> Self {
+        if let Some(update) = pool.weight_update.as_ref().filter(|_| extended_kinds) {
+            return Self::LiquidityBootstrapping(LiquidityBootstrappingPool {
+                id: liquidity.id.into(),
+                address: pool.id.address().into(),
+                balancer_pool_id: pool.id.into(),
+                gas_estimate: liquidity.gas.into(),
+                tokens: pool
+                    .reserves
+                    .iter()
+                    .zip(&update.end_weights)
+                    .map(|(r, end_weight)| {
+                        (
+                            r.asset.token.into(),
+                            LiquidityBootstrappingReserve {
+                                balance: r.asset.amount.into(),
+                                scaling_factor: scaling_factor_to_decimal(r.scale),
+                                start_weight: weight_to_decimal(r.weight),
+                                end_weight: weight_to_decimal(*end_weight),
+                            },
+                        )
+                    })
+                    .collect(),
+                fee: fee_to_decimal(pool.fee),
+                start_time: update.start,
+                end_time: update.end,
+                // Pools with disabled swaps are not fetched in the first place.
+                swap_enabled: true,
+            });
+        }
+
+        Self::WeightedProduct(WeightedProductPool {
+            id: liquidity.id.into(),
+            address: pool.id.address().into(),
+            balancer_pool_id: pool.id.into(),
+            gas_estimate: liquidity.gas.into(),
+            tokens: pool
+                .reserves
+                .iter()
+                .map(|r| {
+                    (
+                        r.asset.token.into(),
+                        WeightedProductReserve {
+                            balance: r.asset.amount.into(),
+                            scaling_factor: scaling_factor_to_decimal(r.scale),
+                            weight: weight_to_decimal(r.weight),
+                        },
+                    )
+                })
+                .collect(),
+            fee: fee_to_decimal(pool.fee),
+            version: match pool.version {
+                liquidity::balancer::v2::weighted::Version::V0 => WeightedProductVersion::V0,
+                liquidity::balancer::v2::weighted::Version::V3Plus => {
+                    WeightedProductVersion::V3Plus
+                }
+            },
+        })
+    }
+}
+
 #[serde_as]
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -535,6 +641,38 @@ enum WeightedProductVersion {
     V3Plus,
 }
 
+#[serde_as]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LiquidityBootstrappingPool {
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    id: usize,
+    address: eth::H160,
+    balancer_pool_id: eth::H256,
+    #[serde_as(as = "serialize::U256")]
+    gas_estimate: eth::U256,
+    tokens: IndexMap<eth::H160, LiquidityBootstrappingReserve>,
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    fee: bigdecimal::BigDecimal,
+    start_time: u64,
+    end_time: u64,
+    swap_enabled: bool,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LiquidityBootstrappingReserve {
+    #[serde_as(as = "serialize::U256")]
+    balance: eth::U256,
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    scaling_factor: bigdecimal::BigDecimal,
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    start_weight: bigdecimal::BigDecimal,
+    #[serde_as(as = "serde_with::DisplayFromStr")]
+    end_weight: bigdecimal::BigDecimal,
+}
+
 #[serde_as]
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -640,3 +778,144 @@ fn scaling_factor_to_decimal(
 ) -> bigdecimal::BigDecimal {
     bigdecimal::BigDecimal::new(scale.as_raw().to_big_int(), 18)
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::domain::liquidity::balancer::v2};
+
+    const POOL: eth::H160 = eth::H160([0xb0; 20]);
+
+    fn pool_id() -> v2::Id {
+        let mut id = [0; 32];
+        id[..20].copy_from_slice(POOL.as_bytes());
+        v2::Id(eth::H256(id))
+    }
+
+    fn asset(token: eth::H160) -> eth::Asset {
+        eth::Asset {
+            token: token.into(),
+            amount: eth::U256::exp10(18).into(),
+        }
+    }
+
+    fn scale() -> v2::ScalingFactor {
+        v2::ScalingFactor::from_raw(eth::U256::exp10(18)).unwrap()
+    }
+
+    fn stable(tokens: &[eth::H160]) -> (liquidity::Liquidity, v2::stable::Pool) {
+        let pool = v2::stable::Pool {
+            vault: eth::H160([0xba; 20]).into(),
+            id: pool_id(),
+            reserves: v2::stable::Reserves::try_new(
+                tokens
+                    .iter()
+                    .map(|token| v2::stable::Reserve {
+                        asset: asset(*token),
+                        scale: scale(),
+                    })
+                    .collect(),
+            )
+            .unwrap(),
+            amplification_parameter: v2::stable::AmplificationParameter::new(
+                200_000.into(),
+                1_000.into(),
+            )
+            .unwrap(),
+            fee: v2::Fee::from_raw(eth::U256::exp10(14)),
+        };
+        let liquidity = liquidity::Liquidity {
+            id: liquidity::Id(0),
+            gas: eth::Gas(88_892.into()),
+            kind: liquidity::Kind::BalancerV2Stable(pool.clone()),
+        };
+        (liquidity, pool)
+    }
+
+    fn weight(weight: u64) -> v2::weighted::Weight {
+        v2::weighted::Weight::from_raw(eth::U256::from(weight) * eth::U256::exp10(16))
+    }
+
+    fn weighted(
+        weight_update: Option<v2::weighted::WeightUpdate>,
+    ) -> (liquidity::Liquidity, v2::weighted::Pool) {
+        let pool = v2::weighted::Pool {
+            vault: eth::H160([0xba; 20]).into(),
+            id: pool_id(),
+            reserves: v2::weighted::Reserves::try_new(
+                [(eth::H160([1; 20]), 80), (eth::H160([2; 20]), 20)]
+                    .into_iter()
+                    .map(|(token, percent)| v2::weighted::Reserve {
+                        asset: asset(token),
+                        scale: scale(),
+                        weight: weight(percent),
+                    })
+                    .collect(),
+            )
+            .unwrap(),
+            fee: v2::Fee::from_raw(eth::U256::exp10(15)),
+            version: v2::weighted::Version::V0,
+            weight_update,
+        };
+        let liquidity = liquidity::Liquidity {
+            id: liquidity::Id(0),
+            gas: eth::Gas(88_892.into()),
+            kind: liquidity::Kind::BalancerV2Weighted(pool.clone()),
+        };
+        (liquidity, pool)
+    }
+
+    #[test]
+    fn emits_composable_stable_pools() {
+        let (liquidity, pool) = stable(&[eth::H160([1; 20]), eth::H160([2; 20])]);
+        let dto =
+            serde_json::to_value(Liquidity::balancer_v2_stable(&liquidity, &pool, true)).unwrap();
+        assert_eq!(dto["kind"], "stable");
+
+        // Composable stable pools hold their own pool token.
+        let (liquidity, pool) = stable(&[eth::H160([1; 20]), eth::H160([2; 20]), POOL]);
+        let dto =
+            serde_json::to_value(Liquidity::balancer_v2_stable(&liquidity, &pool, true)).unwrap();
+        assert_eq!(dto["kind"], "composableStable");
+        assert_eq!(dto["tokens"].as_object().unwrap().len(), 3);
+
+        // Solvers that don't support the extended kinds get a stable pool.
+        let dto =
+            serde_json::to_value(Liquidity::balancer_v2_stable(&liquidity, &pool, false)).unwrap();
+        assert_eq!(dto["kind"], "stable");
+    }
+
+    #[test]
+    fn emits_liquidity_bootstrapping_pools() {
+        let (liquidity, pool) = weighted(None);
+        let dto =
+            serde_json::to_value(Liquidity::balancer_v2_weighted(&liquidity, &pool, true)).unwrap();
+        assert_eq!(dto["kind"], "weightedProduct");
+
+        let (liquidity, pool) = weighted(Some(v2::weighted::WeightUpdate {
+            start: 1_700_000_000,
+            end: 1_700_086_400,
+            end_weights: vec![weight(20), weight(80)],
+        }));
+        let dto =
+            serde_json::to_value(Liquidity::balancer_v2_weighted(&liquidity, &pool, true)).unwrap();
+        assert_eq!(dto["kind"], "liquidityBootstrapping");
+        assert_eq!(dto["swapEnabled"], true);
+        assert_eq!(dto["startTime"], 1_700_000_000);
+        assert_eq!(dto["endTime"], 1_700_086_400);
+        let tokens = dto["tokens"].as_object().unwrap();
+        let first = &tokens[&format!("{:?}", eth::H160([1; 20]))];
+        assert_eq!(first["startWeight"], "0.800000000000000000");
+        assert_eq!(first["endWeight"], "0.200000000000000000");
+        let second = &tokens[&format!("{:?}", eth::H160([2; 20]))];
+        assert_eq!(second["startWeight"], "0.200000000000000000");
+        assert_eq!(second["endWeight"], "0.800000000000000000");
+
+        // Solvers that don't support the extended kinds get a weighted pool
+        // with the current weights.
+        let dto = serde_json::to_value(Liquidity::balancer_v2_weighted(&liquidity, &pool, false))
+            .unwrap();
+        assert_eq!(dto["kind"], "weightedProduct");
+        let first = &dto["tokens"][&format!("{:?}", eth::H160([1; 20]))];
+        assert_eq!(first["weight"], "0.800000000000000000");
+    }
+}
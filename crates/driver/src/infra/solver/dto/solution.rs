@@ -25,9 +25,34 @@ impl Solutions {
         solver: Solver,
         solver_config: &Config,
     ) -> Result<Vec<competition::Solution>, super::Error> {
+        let native_prices = auction.prices();
         self.solutions
             .into_iter()
             .map(|solution| {
+                let flashloans = solution
+                    .flashloans
+                    .into_iter()
+                    .map(|flashloan| {
+                        let lender = eth::ContractAddress(flashloan.lender);
+                        let amount = eth::TokenAmount(flashloan.amount);
+                        // Loans from unsupported lenders fail to encode later on.
+                        let fee_bps = solver
+                            .eth
+                            .contracts()
+                            .flashloan_lender(lender)
+                            .map_or(0, |lender| lender.fee_bps);
+                        Ok(competition::solution::Flashloan {
+                            lender,
+                            borrower: flashloan.borrower.into(),
+                            token: flashloan.token.into(),
+                            amount,
+                            fee: competition::solution::Flashloan::fee(amount, fee_bps)
+                                .ok_or_else(|| {
+                                    super::Error("flashloan fee overflows".to_owned())
+                                })?,
+                        })
+                    })
+                    .collect::<Result<_, super::Error>>()?;
                 competition::Solution::new(
                     competition::solution::Id::new(solution.id),
                     solution
@@ -205,7 +230,8 @@ impl Solutions {
                     solution.gas.map(|gas| eth::Gas(gas.into())),
                     solver_config.fee_handler,
                     auction.surplus_capturing_jit_order_owners(),
-                    solution.flashloans,
+                    &native_prices,
+                    flashloans,
                 )
                 .map_err(|err| match err {
                     competition::solution::error::Solution::InvalidClearingPrices => {
@@ -22,7 +22,7 @@ use {
     derive_more::{From, Into},
     num::BigRational,
     reqwest::header::HeaderName,
-    std::{collections::HashMap, time::Duration},
+    std::{collections::HashMap, path::PathBuf, time::Duration},
     tap::TapFallible,
     thiserror::Error,
     tracing::Instrument,
@@ -117,8 +117,18 @@ pub struct Config {
     pub quote_using_limit_orders: bool,
     pub merge_solutions: SolutionMerging,
     /// S3 configuration for storing the auctions in the form they are sent to
-    /// the solver engine
+    /// the solver engine, along with an audit trail of the proposed solutions,
+    /// their simulation and their submission.
     pub s3: Option<S3>,
+    /// Local directory for storing the same data as with the S3
+    /// configuration.
+    pub archive_dir: Option<PathBuf>,
+    /// Archived data older than this gets deleted.
+    pub archive_retention: Option<Duration>,
+    /// Whether archived data gets Gzip compressed. Defaults to the backend's
+    /// default if unset.
+    pub archive_gzip: Option<bool>,
+    pub archive_naming: s3::Naming,
     /// Whether the native token is wrapped or not when sent to the solvers
     pub solver_native_token: ManageNativeToken,
     /// Which `tx.origin` is required to make quote verification pass.
@@ -129,6 +139,11 @@ pub struct Config {
     pub settle_queue_size: usize,
     /// Whether flashloan hints should be sent to the solver.
     pub flashloans_enabled: bool,
+    /// Whether liquidity bootstrapping and composable stable pools are sent
+    /// with their own liquidity kinds.
+    pub extended_liquidity_kinds: bool,
+    /// Restricts which orders of an auction get sent to the solver.
+    pub policy: auction::Policy,
 }
 
 impl Solver {
@@ -215,6 +230,10 @@ impl Solver {
         self.config.settle_queue_size
     }
 
+    pub fn policy(&self) -> &auction::Policy {
+        &self.config.policy
+    }
+
     /// Make a POST request instructing the solver to solve an auction.
     /// Allocates at most `timeout` time for the solving.
     pub async fn solve(
@@ -231,11 +250,13 @@ impl Solver {
             self.config.fee_handler,
             self.config.solver_native_token,
             self.config.flashloans_enabled,
+            self.config.extended_liquidity_kinds,
         );
         // Only auctions with IDs are real auctions (/quote requests don't have an ID,
         // and it makes no sense to store them)
         if let Some(id) = auction.id() {
-            self.persistence.archive_auction(id, &auction_dto);
+            let block = self.eth.current_block().borrow().number;
+            self.persistence.archive_auction(id, block, &auction_dto);
         };
         let body = serde_json::to_string(&auction_dto).unwrap();
         let url = shared::url::join(&self.config.endpoint, "solve");
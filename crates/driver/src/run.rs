@@ -1,7 +1,7 @@
 use {
     crate::{
         domain::{
-            competition::{bad_tokens, order::app_data::AppDataRetriever},
+            competition::{auction, bad_tokens, order::app_data::AppDataRetriever},
             Mempools,
         },
         infra::{
@@ -10,6 +10,7 @@ use {
             cli,
             config,
             liquidity,
+            persistence::Persistence,
             simulator::{self, Simulator},
             solver::Solver,
             Api,
@@ -17,7 +18,7 @@ use {
     },
     clap::Parser,
     futures::future::join_all,
-    std::{net::SocketAddr, sync::Arc, time::Duration},
+    std::{net::SocketAddr, path::Path, sync::Arc, time::Duration},
     tokio::sync::oneshot,
 };
 
@@ -47,24 +48,137 @@ pub async fn run(
 async fn run_with(args: cli::Args, addr_sender: Option<oneshot::Sender<SocketAddr>>) {
     crate::infra::observe::init(&args.log);
 
+    if let Some(cli::Command::Replay(replay)) = &args.command {
+        return replay_with(&args, replay).await;
+    }
+
     let ethrpc = ethrpc(&args).await;
-    let web3 = ethrpc.web3().clone();
     let config = config::file::load(ethrpc.chain(), &args.config).await;
     tracing::info!("running driver with {config:#?}");
 
     let (shutdown_sender, shutdown_receiver) = tokio::sync::oneshot::channel();
     let eth = ethereum(&config, ethrpc).await;
-    let app_data_retriever = match &config.app_data_fetching {
-        config::file::AppDataFetching::Enabled {
-            orderbook_url,
-            cache_size,
-        } => Some(AppDataRetriever::new(orderbook_url.clone(), *cache_size)),
-        config::file::AppDataFetching::Disabled => None,
+    let serve = api(&config, eth, args.addr, addr_sender).await.serve(
+        async {
+            let _ = shutdown_receiver.await;
+        },
+        config.order_priority_strategies.clone(),
+        app_data_retriever(&config),
+    );
+
+    futures::pin_mut!(serve);
+    tokio::select! {
+        result = &mut serve => panic!("serve task exited: {result:?}"),
+        _ = shutdown_signal() => {
+            tracing::info!("Gracefully shutting down API");
+            shutdown_sender.send(()).expect("failed to send shutdown signal");
+            // Shutdown timeout needs to be larger than the auction deadline
+            match tokio::time::timeout(Duration::from_secs(20), serve).await {
+                Ok(inner) => inner.expect("API failed during shutdown"),
+                Err(_) => panic!("API shutdown exceeded timeout"),
+            }
+        }
     };
-    let serve = Api {
-        solvers: solvers(&config, &eth).await,
-        liquidity: liquidity(&config, &eth).await,
-        simulator: simulator(&config, &eth),
+}
+
+/// Replays an archived auction and reports how the result differs from the
+/// originally reported one.
+async fn replay_with(args: &cli::Args, replay: &cli::Replay) {
+    let ethrpc = ethrpc(args).await;
+    let mut config = config::file::load(ethrpc.chain(), &args.config).await;
+
+    let (auction, response) = match (&replay.auction, replay.auction_id) {
+        (Some(auction), _) => (
+            read_json(auction).await,
+            match &replay.response {
+                Some(response) => Some(read_json(response).await),
+                None => None,
+            },
+        ),
+        (None, Some(auction_id)) => {
+            let solver = config
+                .solvers
+                .iter()
+                .find(|solver| solver.name.as_str() == replay.solver)
+                .expect("unknown solver");
+            let archive = Persistence::build(&infra::solver::Config {
+                archive_retention: None,
+                ..solver.clone()
+            })
+            .await;
+            let auction_id = auction::Id::try_from(auction_id).expect("invalid auction ID");
+            let auction = archive
+                .fetch_auction(auction_id)
+                .await
+                .expect("fetch archived auction");
+            let response = archive
+                .fetch_response(auction_id)
+                .await
+                .inspect_err(|err| tracing::warn!(?err, "no archived response"))
+                .ok();
+            (auction, response)
+        }
+        (None, None) => unreachable!("either an auction or an auction ID is required"),
+    };
+    let auction: infra::api::ArchivedAuction =
+        serde_json::from_value(auction).expect("invalid archived auction");
+    let block = replay
+        .block
+        .or(auction.block())
+        .expect("the archived auction has no block, specify it with --block");
+    tracing::info!(block, "replaying auction");
+    let eth = ethereum_at(&config, ethrpc, Some(block)).await;
+
+    // Make sure that the replay doesn't overwrite the archived data or the
+    // persisted bad tokens.
+    for solver in &mut config.solvers {
+        solver.s3 = None;
+        solver.archive_dir = None;
+    }
+    config.bad_token_cache_file = None;
+
+    let report = api(&config, eth, args.addr, None)
+        .await
+        .replay(
+            &replay.solver,
+            auction,
+            response,
+            infra::time::now() + replay.time_limit,
+            config.order_priority_strategies.clone(),
+            app_data_retriever(&config),
+        )
+        .await
+        .expect("replay auction");
+    let report = serde_json::to_string_pretty(&report).unwrap();
+    match &replay.report {
+        Some(path) => {
+            tokio::fs::write(path, report)
+                .await
+                .unwrap_or_else(|e| panic!("I/O error while writing {path:?}: {e:?}"));
+            tracing::info!(?path, "wrote replay report");
+        }
+        None => println!("{report}"),
+    }
+}
+
+async fn read_json(path: &Path) -> serde_json::Value {
+    let data = tokio::fs::read(path)
+        .await
+        .unwrap_or_else(|e| panic!("I/O error while reading {path:?}: {e:?}"));
+    serde_json::from_slice(&data).unwrap_or_else(|e| panic!("invalid JSON in {path:?}: {e:?}"))
+}
+
+async fn api(
+    config: &infra::Config,
+    eth: Ethereum,
+    addr: SocketAddr,
+    addr_sender: Option<oneshot::Sender<SocketAddr>>,
+) -> Api {
+    let web3 = eth.web3().clone();
+    Api {
+        solvers: solvers(config, &eth).await,
+        liquidity: liquidity(config, &eth).await,
+        simulator: simulator(config, &eth),
         mempools: Mempools::try_new(
             config
                 .mempools
@@ -80,31 +194,24 @@ async fn run_with(args: cli::Args, addr_sender: Option<oneshot::Sender<SocketAdd
             config.simulation_bad_token_max_age,
             &eth,
         ),
+        bad_token_store: config
+            .bad_token_cache_file
+            .clone()
+            .map(infra::bad_tokens::Store::new),
         eth,
-        addr: args.addr,
+        addr,
         addr_sender,
     }
-    .serve(
-        async {
-            let _ = shutdown_receiver.await;
-        },
-        config.order_priority_strategies,
-        app_data_retriever,
-    );
+}
 
-    futures::pin_mut!(serve);
-    tokio::select! {
-        result = &mut serve => panic!("serve task exited: {result:?}"),
-        _ = shutdown_signal() => {
-            tracing::info!("Gracefully shutting down API");
-            shutdown_sender.send(()).expect("failed to send shutdown signal");
-            // Shutdown timeout needs to be larger than the auction deadline
-            match tokio::time::timeout(Duration::from_secs(20), serve).await {
-                Ok(inner) => inner.expect("API failed during shutdown"),
-                Err(_) => panic!("API shutdown exceeded timeout"),
-            }
-        }
-    };
+fn app_data_retriever(config: &infra::Config) -> Option<AppDataRetriever> {
+    match &config.app_data_fetching {
+        config::file::AppDataFetching::Enabled {
+            orderbook_url,
+            cache_size,
+        } => Some(AppDataRetriever::new(orderbook_url.clone(), *cache_size)),
+        config::file::AppDataFetching::Disabled => None,
+    }
 }
 
 fn simulator(config: &infra::Config, eth: &Ethereum) -> Simulator {
@@ -127,6 +234,9 @@ fn simulator(config: &infra::Config, eth: &Ethereum) -> Simulator {
             },
             eth.to_owned(),
         ),
+        Some(infra::simulator::Config::Composite(composite)) => {
+            Simulator::composite(composite.to_owned(), eth.to_owned())
+        }
         None => Simulator::ethereum(eth.to_owned()),
     };
     if config.disable_access_list_simulation {
@@ -145,18 +255,25 @@ async fn ethrpc(args: &cli::Args) -> blockchain::Rpc {
 }
 
 async fn ethereum(config: &infra::Config, ethrpc: blockchain::Rpc) -> Ethereum {
-    let gas = Arc::new(
+    ethereum_at(config, ethrpc, None).await
+}
+
+/// Connects to the chain, pinned to the specified block if set.
+async fn ethereum_at(
+    config: &infra::Config,
+    ethrpc: blockchain::Rpc,
+    block: Option<u64>,
+) -> Ethereum {
+    let gas =
         blockchain::GasPriceEstimator::new(ethrpc.web3(), &config.gas_estimator, &config.mempools)
             .await
-            .expect("initialize gas price estimator"),
-    );
-    Ethereum::new(
-        ethrpc,
-        config.contracts.clone(),
-        gas,
-        config.archive_node_url.as_ref(),
-    )
-    .await
+            .expect("initialize gas price estimator");
+    let contracts = config.contracts.clone();
+    let archive_node_url = config.archive_node_url.as_ref();
+    match block {
+        Some(block) => Ethereum::pinned(ethrpc, contracts, gas, archive_node_url, block).await,
+        None => Ethereum::new(ethrpc, contracts, Arc::new(gas), archive_node_url).await,
+    }
 }
 
 async fn solvers(config: &config::Config, eth: &Ethereum) -> Vec<Solver> {
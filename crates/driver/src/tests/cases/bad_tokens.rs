@@ -0,0 +1,20 @@
+use crate::tests::setup::{ab_order, ab_pool, ab_solution, cd_order, cd_pool, setup};
+
+/// Test that orders trading tokens marked as unsupported at runtime are not
+/// sent to the solver.
+#[tokio::test]
+#[ignore]
+async fn override_token_quality() {
+    let test = setup()
+        .pool(ab_pool())
+        .pool(cd_pool())
+        .order(ab_order())
+        .order(cd_order().filtered())
+        .solution(ab_solution())
+        .done()
+        .await;
+
+    test.override_token_quality("C", "unsupported").await;
+
+    test.solve().await.ok().orders(&[ab_order()]);
+}
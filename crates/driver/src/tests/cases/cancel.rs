@@ -0,0 +1,37 @@
+use crate::tests::{
+    self,
+    setup::{ab_order, ab_pool, ab_solution},
+};
+
+/// Checks that cancelling a settlement before it got submitted prevents it
+/// from being settled.
+#[tokio::test]
+#[ignore]
+async fn not_submitted() {
+    let test = tests::setup()
+        .name("cancel before settling")
+        .pool(ab_pool())
+        .order(ab_order())
+        .solution(ab_solution())
+        .done()
+        .await;
+
+    let id = test.solve().await.ok().id();
+    test.cancel().await.ok().status("notSubmitted");
+    test.settle(id).await.err().kind("SolutionNotAvailable");
+}
+
+/// Checks that cancelling an auction without a settlement is reported as such.
+#[tokio::test]
+#[ignore]
+async fn not_found() {
+    let test = tests::setup()
+        .name("cancel without settlement")
+        .pool(ab_pool())
+        .order(ab_order())
+        .solution(ab_solution())
+        .done()
+        .await;
+
+    test.cancel().await.ok().status("notFound");
+}
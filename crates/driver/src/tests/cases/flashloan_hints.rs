@@ -33,13 +33,21 @@ async fn solutions_with_flashloan() {
         .pool(ab_pool())
         .order(order.clone())
         .solution(ab_solution().flashloan(flashloan_into_dto(flashloan)))
+        .flashloan_lender(H160::from_low_u64_be(1), H160::from_low_u64_be(4))
         .done()
         .await;
 
     // blocked by https://github.com/cowprotocol/services/issues/3218
     // todo: instead, check the solution using
     // `test.solve().await.ok().orders(&[order]);`
-    test.solve().await.ok();
+    let id = test.solve().await.ok().id();
+    // The wrapper isn't deployed, so check that the settlement is encoded as a
+    // call to it taking out the flashloan.
+    test.reveal(id).await.ok().calldata().flashloan(
+        H160::from_low_u64_be(1),
+        H160::from_low_u64_be(3),
+        3.into(),
+    );
 }
 
 #[tokio::test]
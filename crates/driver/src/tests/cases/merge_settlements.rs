@@ -69,7 +69,8 @@ async fn possible_common_token() {
     test.solve().await.ok().orders(&[ab_order, ad_order]);
 }
 
-/// Test that settlements are not merged if the clearing prices don't permit it.
+/// Test that settlements are not merged if the clearing prices or the
+/// liquidity they use don't permit it.
 #[tokio::test]
 #[ignore]
 async fn impossible() {
@@ -79,8 +80,8 @@ async fn impossible() {
         .pool(ab_pool())
         .order(order.clone())
         .order(order.clone().rename("reduced order").reduce_amount("1e-3".ether().into_wei()))
-        // These two solutions result in different clearing prices (due to different surplus),
-        // so they can't be merged.
+        // These two solutions result in different clearing prices (due to different surplus)
+        // and both swap through the A-B pool, so they can't be merged.
         .solution(ab_solution())
         .solution(Solution {
             orders: vec!["reduced order"],
@@ -8,7 +8,9 @@ use {
     std::str::FromStr,
 };
 
+pub mod bad_tokens;
 pub mod buy_eth;
+pub mod cancel;
 pub mod example_config;
 pub mod fees;
 mod flashloan_hints;
@@ -9,7 +9,7 @@ use crate::{
             ab_liquidity_quote,
             ab_order,
             ab_solution,
-            fee::{Policy, Quote},
+            fee::{Policy, Quote, VolumeTier},
             test_solver,
             ExpectedOrderAmounts,
             Test,
@@ -863,6 +863,88 @@ async fn volume_protocol_fee_partial_sell_order_at_limit_price() {
     protocol_fee_test_case(test_case).await;
 }
 
+#[tokio::test]
+#[ignore]
+async fn tiered_volume_protocol_fee_buy_order() {
+    let fee_policy = Policy::TieredVolume {
+        tiers: vec![
+            VolumeTier {
+                min_notional: 0.into(),
+                factor: 0.5,
+            },
+            VolumeTier {
+                min_notional: 100.ether().into_wei(),
+                factor: 0.25,
+            },
+        ],
+    };
+    let test_case = TestCase {
+        fee_policy: vec![fee_policy],
+        order: Order {
+            sell_amount: 50.ether().into_wei(),
+            buy_amount: 40.ether().into_wei(),
+            side: order::Side::Buy,
+        },
+        execution: Execution {
+            // The executed buy amount is worth 40 ETH, so half of the solver proposed
+            // sell volume is kept by the protocol
+            solver: Amounts {
+                sell: 30.ether().into_wei(),
+                buy: 40.ether().into_wei(),
+            },
+            driver: Amounts {
+                sell: 45.ether().into_wei(),
+                buy: 40.ether().into_wei(),
+            },
+        },
+        expected_score: 20.ether().into_wei(),
+        fee_handler: FeeHandler::Driver,
+    };
+    protocol_fee_test_case(test_case).await;
+}
+
+#[tokio::test]
+#[ignore]
+async fn tiered_volume_protocol_fee_sell_order() {
+    let fee_policy = Policy::TieredVolume {
+        tiers: vec![
+            VolumeTier {
+                min_notional: 0.into(),
+                factor: 0.2,
+            },
+            VolumeTier {
+                min_notional: 10.ether().into_wei(),
+                factor: 0.1,
+            },
+        ],
+    };
+    let test_case = TestCase {
+        fee_policy: vec![fee_policy],
+        order: Order {
+            sell_amount: 50.ether().into_wei(),
+            buy_amount: 40.ether().into_wei(),
+            side: order::Side::Sell,
+        },
+        execution: Execution {
+            // The order is partially fillable, so the limit price is adjusted by the
+            // highest factor of the tiers it can be executed in ( 40 / (1 - 0.2) = 50 ).
+            // The executed sell amount is worth 50 ETH, so 10% of the solver proposed
+            // buy volume is kept by the protocol
+            solver: Amounts {
+                sell: 50.ether().into_wei(),
+                buy: 50.ether().into_wei(),
+            },
+            driver: Amounts {
+                sell: 50.ether().into_wei(),
+                buy: 45.ether().into_wei(),
+            },
+        },
+        expected_score: 10.ether().into_wei(),
+        fee_handler: FeeHandler::Driver,
+    };
+    protocol_fee_test_case(test_case).await;
+}
+
 #[tokio::test]
 #[ignore]
 async fn price_improvement_fee_buy_in_market_order_not_capped() {
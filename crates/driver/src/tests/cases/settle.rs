@@ -82,6 +82,28 @@ async fn private_rpc_with_high_risk_solution() {
     test.settle(id).await.err().kind("FailedToSubmit");
 }
 
+/// Checks that settlements get submitted as bundles to bundle relays.
+#[tokio::test]
+#[ignore]
+async fn bundle_relay() {
+    let test = tests::setup()
+        .name("bundle relay")
+        .pool(ab_pool())
+        .order(ab_order())
+        .solution(ab_solution())
+        .mempools(vec![tests::setup::Mempool::BundleRelay])
+        .done()
+        .await;
+
+    let id = test.solve().await.ok().id();
+    test.settle(id)
+        .await
+        .ok()
+        .await
+        .ab_order_executed(&test)
+        .await;
+}
+
 #[tokio::test]
 #[ignore]
 async fn too_much_gas() {
@@ -1,11 +1,11 @@
 use {
     super::{blockchain::Blockchain, Mempool, Partial, Solver, Test},
     crate::{
-        domain::competition::order,
+        domain::{competition::order, eth},
         infra::config::file::OrderPriorityStrategy,
         tests::{
             hex_address,
-            setup::{blockchain::Trade, orderbook::Orderbook},
+            setup::{blockchain::Trade, orderbook::Orderbook, relay::Relay},
         },
     },
     rand::seq::SliceRandom,
@@ -22,6 +22,14 @@ pub struct Config {
     pub mempools: Vec<Mempool>,
     pub order_priority_strategies: Vec<OrderPriorityStrategy>,
     pub orderbook: Orderbook,
+    pub flashloan_lenders: Vec<FlashloanLender>,
+}
+
+/// A flashloan lender the driver is configured to borrow from.
+#[derive(Debug, Clone, Copy)]
+pub struct FlashloanLender {
+    pub lender: eth::H160,
+    pub wrapper: eth::H160,
 }
 
 pub struct Driver {
@@ -230,6 +238,19 @@ async fn create_config_file(
     )
     .unwrap();
 
+    for lender in &config.flashloan_lenders {
+        write!(
+            file,
+            r#"[[contracts.flashloan-lenders]]
+               lender = "{}"
+               wrapper = "{}"
+               "#,
+            hex_address(lender.lender),
+            hex_address(lender.wrapper),
+        )
+        .unwrap();
+    }
+
     for mempool in &config.mempools {
         match mempool {
             Mempool::Public => {
@@ -254,6 +275,19 @@ async fn create_config_file(
                 )
                 .unwrap();
             }
+            Mempool::BundleRelay => {
+                let relay = Relay::start(&blockchain.web3_url);
+                write!(
+                    file,
+                    r#"[[submission.mempool]]
+                    mempool = "bundle-relay"
+                    additional-tip-percentage = 0.0
+                    url = "http://{}"
+                    "#,
+                    relay.addr,
+                )
+                .unwrap();
+            }
         }
     }
 
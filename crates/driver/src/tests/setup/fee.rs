@@ -36,6 +36,36 @@ pub enum Policy {
     },
     #[serde(rename_all = "camelCase")]
     Volume { factor: f64 },
+    #[serde(rename_all = "camelCase")]
+    TieredVolume { tiers: Vec<VolumeTier> },
+}
+
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeTier {
+    /// The smallest order notional in native token the tier applies to.
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub min_notional: eth::U256,
+    pub factor: f64,
+}
+
+impl Policy {
+    /// The factor of the volume fee the driver withholds if the available
+    /// amounts are worth `notional` native token. Partially fillable orders
+    /// can be executed with any smaller notional.
+    pub fn volume_factor(&self, notional: eth::U256, partial: bool) -> Option<f64> {
+        match self {
+            Policy::Volume { factor } => Some(*factor),
+            Policy::TieredVolume { tiers } => tiers
+                .iter()
+                .enumerate()
+                .filter(|(i, tier)| *i == 0 || tier.min_notional <= notional)
+                .map(|(_, tier)| tier.factor)
+                .reduce(|max, factor| if partial { max.max(factor) } else { factor }),
+            _ => None,
+        }
+    }
 }
 
 impl Policy {
@@ -71,6 +101,17 @@ impl Policy {
                     "factor": factor
                 }
             }),
+            Policy::TieredVolume { tiers } => json!({
+                "tieredVolume": {
+                    "tiers": tiers
+                        .iter()
+                        .map(|tier| json!({
+                            "minNotional": tier.min_notional.to_string(),
+                            "factor": tier.factor,
+                        }))
+                        .collect::<Vec<_>>()
+                }
+            }),
         }
     }
 }
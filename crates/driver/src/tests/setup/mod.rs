@@ -59,6 +59,7 @@ pub mod blockchain;
 mod driver;
 pub mod fee;
 mod orderbook;
+mod relay;
 mod solver;
 
 #[derive(Debug, Clone, Copy)]
@@ -507,6 +508,8 @@ pub enum Mempool {
         /// Uses ethrpc node if None
         url: Option<String>,
     },
+    /// Submits bundles to a stand-in relay forwarding them to the node.
+    BundleRelay,
 }
 
 /// Create a builder for the setup process.
@@ -555,6 +558,8 @@ pub struct Setup {
     /// The maximum number of blocks to wait for a settlement to appear on
     /// chain.
     settle_submission_deadline: u64,
+    /// Flashloan lenders the driver is configured to borrow from
+    flashloan_lenders: Vec<driver::FlashloanLender>,
 }
 
 /// The validity of a solution.
@@ -883,6 +888,14 @@ impl Setup {
         self
     }
 
+    /// Configure the driver to take out flashloans from the lender through
+    /// the specified wrapper contract.
+    pub fn flashloan_lender(mut self, lender: H160, wrapper: H160) -> Self {
+        self.flashloan_lenders
+            .push(driver::FlashloanLender { lender, wrapper });
+        self
+    }
+
     /// Create the test: set up onchain contracts and pools, start a mock HTTP
     /// server for the solver and start the HTTP server for the driver.
     pub async fn done(self) -> Test {
@@ -989,6 +1002,7 @@ impl Setup {
                 mempools: self.mempools,
                 order_priority_strategies: self.order_priority_strategies,
                 orderbook,
+                flashloan_lenders: self.flashloan_lenders,
             },
             &solvers_with_address,
             &blockchain,
@@ -1127,6 +1141,25 @@ impl Test {
         }
     }
 
+    /// Call the /bad_tokens endpoint to override the quality of a token.
+    pub async fn override_token_quality(&self, token: &str, quality: &str) {
+        let res = self
+            .client
+            .post(format!(
+                "http://{}/{}/bad_tokens",
+                self.driver.addr,
+                solver::NAME
+            ))
+            .json(&serde_json::json!({
+                "token": hex_address(self.blockchain.get_token(token)),
+                "quality": quality,
+            }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
     /// Call the /settle endpoint.
     pub async fn settle(&self, solution_id: u64) -> Settle {
         self.settle_with_solver(solver::NAME, solution_id).await
@@ -1166,6 +1199,27 @@ impl Test {
         }
     }
 
+    /// Call the /cancel endpoint.
+    pub async fn cancel(&self) -> Cancel {
+        let res = self
+            .client
+            .post(format!(
+                "http://{}/{}/cancel",
+                self.driver.addr,
+                solver::NAME
+            ))
+            .json(&serde_json::json!({
+                "auctionId": self.auction_id.to_string(),
+            }))
+            .send()
+            .await
+            .unwrap();
+        let status = res.status();
+        let body = res.text().await.unwrap();
+        tracing::debug!(?status, ?body, "got a response from /cancel");
+        Cancel { status, body }
+    }
+
     async fn balances(&self) -> HashMap<&'static str, eth::U256> {
         let mut balances = HashMap::new();
         for (token, contract) in self.blockchain.tokens.iter() {
@@ -1400,6 +1454,35 @@ impl SolveErr {
     }
 }
 
+/// A /cancel response.
+pub struct Cancel {
+    status: StatusCode,
+    body: String,
+}
+
+impl Cancel {
+    /// Expect the /cancel endpoint to have returned a 200 OK response.
+    pub fn ok(self) -> CancelOk {
+        assert_eq!(self.status, hyper::StatusCode::OK);
+        CancelOk { body: self.body }
+    }
+}
+
+pub struct CancelOk {
+    body: String,
+}
+
+impl CancelOk {
+    /// Check the final status of the settlement.
+    pub fn status(self, expected_status: &str) {
+        let result: serde_json::Value = serde_json::from_str(&self.body).unwrap();
+        assert_eq!(
+            result.get("status").unwrap().as_str().unwrap(),
+            expected_status
+        );
+    }
+}
+
 /// A /reveal response.
 pub struct Reveal {
     status: StatusCode,
@@ -1451,6 +1534,43 @@ impl RevealOk {
             .is_empty());
         self
     }
+
+    /// Check that both calldata variants take out the specified flashloan
+    /// through the flashloan wrapper and settle with the borrowed funds.
+    pub fn flashloan(self, lender: H160, token: H160, amount: eth::U256) -> Self {
+        let flash_loan_and_settle = contracts::ERC3156FlashLoanSolverWrapper::raw_contract()
+            .interface
+            .abi
+            .function("flashLoanAndSettle")
+            .unwrap();
+        let settle = contracts::GPv2Settlement::raw_contract()
+            .interface
+            .abi
+            .function("settle")
+            .unwrap()
+            .short_signature();
+        let result: serde_json::Value = serde_json::from_str(&self.body).unwrap();
+        let calldata = result.get("calldata").unwrap().as_object().unwrap();
+        for kind in ["internalized", "uninternalized"] {
+            let calldata = calldata.get(kind).unwrap().as_str().unwrap();
+            let calldata = hex::decode(calldata.trim_start_matches("0x")).unwrap();
+            assert_eq!(calldata[..4], flash_loan_and_settle.short_signature());
+            let params = flash_loan_and_settle.decode_input(&calldata[4..]).unwrap();
+            assert_eq!(params[0], ethabi::Token::Address(lender));
+            assert_eq!(
+                params[1],
+                ethabi::Token::Tuple(vec![
+                    ethabi::Token::Address(token),
+                    ethabi::Token::Uint(amount)
+                ])
+            );
+            let ethabi::Token::Bytes(settlement) = &params[2] else {
+                panic!("settlement calldata is not bytes");
+            };
+            assert_eq!(settlement[..4], settle);
+        }
+        self
+    }
 }
 
 pub struct RevealErr {
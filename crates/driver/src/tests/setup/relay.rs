@@ -0,0 +1,59 @@
+use {
+    axum::{routing::post, Extension, Json, Router},
+    serde_json::json,
+    std::net::SocketAddr,
+    web3::Transport,
+};
+
+/// A stand-in for an `eth_sendBundle` compatible relay. Instead of building
+/// blocks, it forwards the transactions of every bundle it receives to the
+/// node.
+pub struct Relay {
+    pub addr: SocketAddr,
+}
+
+impl Relay {
+    /// Starts the relay server forwarding transactions to the node at the
+    /// specified URL. The server listens on a random port.
+    pub fn start(web3_url: &str) -> Self {
+        let web3 = web3::Web3::new(web3::transports::Http::new(web3_url).unwrap());
+        let app = Router::new()
+            .route("/", post(Self::handler))
+            .layer(Extension(web3));
+        let server =
+            axum::Server::bind(&"0.0.0.0:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+
+        tracing::info!("Bundle relay mock server listening on {}", addr);
+
+        tokio::spawn(server);
+
+        Relay { addr }
+    }
+
+    async fn handler(
+        Extension(web3): Extension<web3::Web3<web3::transports::Http>>,
+        Json(request): Json<serde_json::Value>,
+    ) -> Json<serde_json::Value> {
+        assert_eq!(request["method"], "eth_sendBundle");
+        let bundle = &request["params"][0];
+        tracing::debug!(?bundle, "Bundle relay received a bundle");
+
+        for tx in bundle["txs"].as_array().unwrap() {
+            // Bundles get resubmitted every block, so the node may already know
+            // about the transaction.
+            let _ = web3
+                .transport()
+                .execute("eth_sendRawTransaction", vec![tx.clone()])
+                .await;
+        }
+
+        Json(json!({
+            "jsonrpc": "2.0",
+            "id": request["id"],
+            "result": {
+                "bundleHash": format!("0x{}", "00".repeat(32)),
+            },
+        }))
+    }
+}
@@ -1,7 +1,6 @@
 use {
     super::{
         blockchain::{self, Blockchain},
-        fee,
         Partial,
     },
     crate::{
@@ -75,22 +74,23 @@ impl Solver {
                 }
                 order::Side::Buy => {
                     let mut current_sell_amount = quote.sell_amount();
-                    for fee_policy in &quote.order.fee_policy {
-                        match fee_policy {
-                            // If the fees are handled in the driver, for volume based fee, we
-                            // artificially reduce the limit sell amount
-                            // for buy orders before sending to solvers. This
-                            // allows driver to withhold volume based fee and not violate original
-                            // limit prices.
-                            fee::Policy::Volume { factor }
-                                if config.fee_handler == FeeHandler::Driver =>
+                    // If the fees are handled in the driver, for volume based fee, we
+                    // artificially reduce the limit sell amount
+                    // for buy orders before sending to solvers. This
+                    // allows driver to withhold volume based fee and not violate original
+                    // limit prices. All tokens are worth 1 ETH, so the notional of tiered
+                    // volume fees is the available amount of the order's target token.
+                    if config.fee_handler == FeeHandler::Driver {
+                        let partial = matches!(quote.order.partial, Partial::Yes { .. });
+                        for fee_policy in &quote.order.fee_policy {
+                            if let Some(factor) =
+                                fee_policy.volume_factor(quote.buy_amount(), partial)
                             {
                                 current_sell_amount = eth::TokenAmount(current_sell_amount)
                                     .apply_factor(1.0 / (1.0 + factor))
                                     .unwrap()
                                     .0;
                             }
-                            _ => {}
                         }
                     }
                     current_sell_amount.to_string()
@@ -101,22 +101,23 @@ impl Solver {
                 order::Side::Sell if config.quote => "1".to_owned(),
                 order::Side::Sell => {
                     let mut current_buy_amount = quote.buy_amount();
-                    for fee_policy in &quote.order.fee_policy {
-                        match fee_policy {
-                            // If the fees are handled in the driver, for volume based fee, we
-                            // artificially increase the limit buy
-                            // amount for sell orders before sending to solvers. This
-                            // allows driver to withhold volume based fee and not violate original
-                            // limit prices.
-                            fee::Policy::Volume { factor }
-                                if config.fee_handler == FeeHandler::Driver =>
+                    // If the fees are handled in the driver, for volume based fee, we
+                    // artificially increase the limit buy
+                    // amount for sell orders before sending to solvers. This
+                    // allows driver to withhold volume based fee and not violate original
+                    // limit prices. All tokens are worth 1 ETH, so the notional of tiered
+                    // volume fees is the available amount of the order's target token.
+                    if config.fee_handler == FeeHandler::Driver {
+                        let partial = matches!(quote.order.partial, Partial::Yes { .. });
+                        for fee_policy in &quote.order.fee_policy {
+                            if let Some(factor) =
+                                fee_policy.volume_factor(quote.sell_amount(), partial)
                             {
                                 current_buy_amount = eth::TokenAmount(current_buy_amount)
                                     .apply_factor(1.0 / (1.0 - factor))
                                     .unwrap()
                                     .0;
                             }
-                            _ => {}
                         }
                     }
                     current_buy_amount.to_string()
@@ -449,6 +450,7 @@ impl Solver {
                 settlement: Some(config.blockchain.settlement.address().into()),
                 weth: Some(config.blockchain.weth.address().into()),
                 cow_amms: vec![],
+                flashloan_lenders: vec![],
             },
             gas,
             None,
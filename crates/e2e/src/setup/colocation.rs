@@ -54,7 +54,7 @@ async fn start_solver(config_file: TempPath, solver_name: String) -> Url {
     let (bind, bind_receiver) = tokio::sync::oneshot::channel();
     tokio::task::spawn(async move {
         let _config_file = config_file;
-        solvers::run(args, Some(bind)).await;
+        solvers::run(args, solvers::Engines::builtin(), Some(bind)).await;
     });
 
     let solver_addr = bind_receiver.await.unwrap();
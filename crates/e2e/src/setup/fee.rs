@@ -33,6 +33,10 @@ pub enum FeePolicyKind {
     /// fee where price improvement is a difference between the executed price
     /// and the best quote.
     PriceImprovement { factor: f64, max_volume_factor: f64 },
+    /// How much of the order's volume should be taken as a protocol fee,
+    /// given as `(min_notional, factor)` tiers with the minimum notional in
+    /// ETH.
+    TieredVolume { tiers: Vec<(f64, f64)> },
 }
 
 impl std::fmt::Display for ProtocolFee {
@@ -58,6 +62,14 @@ impl std::fmt::Display for ProtocolFee {
                 "priceImprovement:{}:{}:{}",
                 factor, max_volume_factor, order_class_str
             ),
+            FeePolicyKind::TieredVolume { tiers } => {
+                let tiers_str = tiers
+                    .iter()
+                    .map(|(min_notional, factor)| format!("{}={}", min_notional, factor))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                write!(f, "tieredVolume:{}:{}", tiers_str, order_class_str)
+            }
         }
     }
 }
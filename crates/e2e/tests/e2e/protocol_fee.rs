@@ -32,6 +32,12 @@ async fn local_node_volume_fee_buy_order() {
     run_test(volume_fee_buy_order_test).await;
 }
 
+#[tokio::test]
+#[ignore]
+async fn local_node_tiered_volume_fee_buy_order() {
+    run_test(tiered_volume_fee_buy_order_test).await;
+}
+
 #[tokio::test]
 #[ignore]
 async fn local_node_combined_protocol_fees() {
@@ -526,3 +532,99 @@ async fn volume_fee_buy_order_test(web3: Web3) {
         .unwrap();
     assert_eq!(order.metadata.executed_fee, balance_after);
 }
+
+async fn tiered_volume_fee_buy_order_test(web3: Web3) {
+    // Orders with a notional of at least 1 ETH pay the lower fee.
+    let protocol_fee = ProtocolFee {
+        policy: FeePolicyKind::TieredVolume {
+            tiers: vec![(0., 0.2), (1., 0.1)],
+        },
+        policy_order_class: FeePolicyOrderClass::Any,
+    };
+
+    let mut onchain = OnchainComponents::deploy(web3.clone()).await;
+
+    let [solver] = onchain.make_solvers(to_wei(1)).await;
+    let [trader] = onchain.make_accounts(to_wei(20)).await;
+    let [token] = onchain
+        .deploy_tokens_with_weth_uni_v2_pools(to_wei(1_000), to_wei(1_000))
+        .await;
+
+    // Fund the trader with WETH and approve GPv2 for trading
+    tx_value!(
+        trader.account(),
+        to_wei(10),
+        onchain.contracts().weth.deposit()
+    );
+    tx!(
+        trader.account(),
+        onchain
+            .contracts()
+            .weth
+            .approve(onchain.contracts().allowance, to_wei(10))
+    );
+
+    let services = Services::new(&onchain).await;
+    services
+        .start_protocol_with_args(
+            ExtraServiceArgs {
+                autopilot: vec![ProtocolFeesConfig(vec![protocol_fee]).to_string()],
+                ..Default::default()
+            },
+            solver,
+        )
+        .await;
+
+    // Buys about 5 ETH worth of the token, which falls into the second tier.
+    let quote = get_quote(
+        &services,
+        onchain.contracts().weth.address(),
+        token.address(),
+        OrderKind::Buy,
+        to_wei(5),
+        model::time::now_in_epoch_seconds() + 300,
+    )
+    .await
+    .unwrap()
+    .quote;
+
+    let order = OrderCreation {
+        sell_token: onchain.contracts().weth.address(),
+        sell_amount: quote.sell_amount * 3 / 2,
+        buy_token: token.address(),
+        buy_amount: to_wei(5),
+        valid_to: model::time::now_in_epoch_seconds() + 300,
+        kind: OrderKind::Buy,
+        ..Default::default()
+    }
+    .sign(
+        EcdsaSigningScheme::Eip712,
+        &onchain.contracts().domain_separator,
+        SecretKeyRef::from(&SecretKey::from_slice(trader.private_key()).unwrap()),
+    );
+    let uid = services.create_order(&order).await.unwrap();
+
+    tracing::info!("Waiting for trade.");
+    let metadata_updated = || async {
+        onchain.mint_block().await;
+        let order = services.get_order(&uid).await.unwrap();
+        !order.metadata.executed_fee.is_zero()
+    };
+    wait_for_condition(TIMEOUT, metadata_updated).await.unwrap();
+
+    // The fee is taken with the factor of the second tier, 0.1, and not with
+    // the factor of the first tier, 0.2.
+    let order = services.get_order(&uid).await.unwrap();
+    assert!(order.metadata.executed_fee >= quote.sell_amount / 10);
+    assert!(order.metadata.executed_fee < quote.fee_amount + quote.sell_amount / 5);
+
+    // Check settlement contract balance
+    let balance_after = onchain
+        .contracts()
+        .weth
+        .balance_of(onchain.contracts().gp_settlement.address())
+        .call()
+        .await
+        .unwrap();
+    assert_eq!(order.metadata.executed_fee, balance_after);
+}
@@ -20,6 +20,19 @@ pub enum FeePolicy {
         max_volume_factor: f64,
         quote: Quote,
     },
+    #[serde(rename_all = "camelCase")]
+    TieredVolume { tiers: Vec<VolumeTier> },
+}
+
+#[serde_as]
+#[derive(PartialEq, Clone, Debug, Serialize)]
+#[cfg_attr(any(test, feature = "e2e"), derive(serde::Deserialize))]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeTier {
+    /// The smallest order notional in native token the tier applies to.
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub min_notional: U256,
+    pub factor: f64,
 }
 
 #[serde_as]
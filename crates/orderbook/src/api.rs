@@ -23,6 +23,7 @@ mod get_order_by_uid;
 mod get_order_status;
 mod get_orders_by_tx;
 mod get_solver_competition;
+mod get_solver_suspensions;
 mod get_token_metadata;
 mod get_total_surplus;
 mod get_trades;
@@ -91,6 +92,12 @@ pub fn handle_all_routes(
                 database.clone(),
             ))),
         ),
+        (
+            "v1/solver_suspensions",
+            box_filter(get_solver_suspensions::get_solver_suspensions(
+                database.clone(),
+            )),
+        ),
         ("v1/version", box_filter(version::version())),
         (
             "v1/get_native_price",
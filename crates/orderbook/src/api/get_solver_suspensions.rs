@@ -0,0 +1,30 @@
+use {
+    crate::database::Postgres,
+    hyper::StatusCode,
+    std::convert::Infallible,
+    warp::{reply, Filter, Rejection},
+};
+
+fn get_solver_suspensions_request() -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::path!("v1" / "solver_suspensions").and(warp::get())
+}
+
+pub fn get_solver_suspensions(
+    db: Postgres,
+) -> impl Filter<Extract = (super::ApiReply,), Error = Rejection> + Clone {
+    get_solver_suspensions_request().and_then(move || {
+        let db = db.clone();
+        async move {
+            let result = db.active_solver_suspensions().await;
+            let response = match result {
+                Ok(suspensions) => reply::with_status(reply::json(&suspensions), StatusCode::OK),
+                Err(err) => {
+                    tracing::error!(?err, "failed to fetch solver suspensions");
+                    crate::api::internal_error_reply()
+                }
+            };
+
+            Result::<_, Infallible>::Ok(response)
+        }
+    })
+}
@@ -5,7 +5,7 @@ use {
         FromPrimitive,
     },
     database::{auction::AuctionId, OrderUid},
-    model::fee_policy::{ExecutedProtocolFee, FeePolicy, Quote},
+    model::fee_policy::{ExecutedProtocolFee, FeePolicy, Quote, VolumeTier},
     num::BigRational,
     number::conversions::{big_decimal_to_u256, big_rational_to_u256},
     std::collections::HashMap,
@@ -141,5 +141,30 @@ fn fee_policy_from(
                 },
             }
         }
+        database::fee_policies::FeePolicyKind::TieredVolume => {
+            let min_notionals = db_fee_policy
+                .volume_tier_min_notionals
+                .context("missing volume tier min notionals")?;
+            let factors = db_fee_policy
+                .volume_tier_factors
+                .context("missing volume tier factors")?;
+            anyhow::ensure!(
+                min_notionals.len() == factors.len(),
+                "volume tier min notionals and factors have different lengths"
+            );
+            FeePolicy::TieredVolume {
+                tiers: min_notionals
+                    .iter()
+                    .zip(factors)
+                    .map(|(min_notional, factor)| {
+                        Ok(VolumeTier {
+                            min_notional: big_decimal_to_u256(min_notional)
+                                .context("invalid volume tier min notional")?,
+                            factor,
+                        })
+                    })
+                    .collect::<anyhow::Result<_>>()?,
+            }
+        }
     })
 }
@@ -5,6 +5,7 @@ mod fee_policies;
 pub mod orders;
 pub mod quotes;
 pub mod solver_competition;
+pub mod solver_suspensions;
 pub mod total_surplus;
 pub mod trades;
 
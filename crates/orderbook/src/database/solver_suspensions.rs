@@ -0,0 +1,34 @@
+use {
+    crate::dto::SolverSuspension,
+    anyhow::{Context, Result},
+    primitive_types::H160,
+};
+
+impl super::Postgres {
+    /// Returns the drivers that are currently excluded from auctions.
+    pub async fn active_solver_suspensions(&self) -> Result<Vec<SolverSuspension>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["active_solver_suspensions"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await?;
+        database::solver_suspensions::fetch_active(&mut ex, chrono::Utc::now())
+            .await?
+            .into_iter()
+            .map(|suspension| {
+                let count = |count: i64| u64::try_from(count).context("negative count");
+                Ok(SolverSuspension {
+                    driver: suspension.driver,
+                    solver: H160(suspension.solver.0),
+                    suspended_at: suspension.suspended_at,
+                    suspended_until: suspension.suspended_until,
+                    attempts: count(suspension.attempts)?,
+                    failed: count(suspension.failed)?,
+                    reverted: count(suspension.reverted)?,
+                    unsettled: count(suspension.unsettled)?,
+                })
+            })
+            .collect()
+    }
+}
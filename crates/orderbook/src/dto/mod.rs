@@ -6,8 +6,9 @@ pub use {
     order::Order,
 };
 use {
+    chrono::{DateTime, Utc},
     number::serialization::HexOrDecimalU256,
-    primitive_types::U256,
+    primitive_types::{H160, U256},
     serde::Serialize,
     serde_with::serde_as,
 };
@@ -20,3 +21,19 @@ pub struct TokenMetadata {
     #[serde_as(as = "Option<HexOrDecimalU256>")]
     pub native_price: Option<U256>,
 }
+
+/// A driver that is currently excluded from auctions because too many of its
+/// recent settlement attempts failed.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SolverSuspension {
+    pub driver: String,
+    pub solver: H160,
+    pub suspended_at: DateTime<Utc>,
+    pub suspended_until: DateTime<Utc>,
+    /// Number of settlement attempts the failure rate was computed from.
+    pub attempts: u64,
+    pub failed: u64,
+    pub reverted: u64,
+    pub unsettled: u64,
+}
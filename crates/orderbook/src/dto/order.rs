@@ -70,6 +70,17 @@ pub enum FeePolicy {
         max_volume_factor: f64,
         quote: Quote,
     },
+    #[serde(rename_all = "camelCase")]
+    TieredVolume { tiers: Vec<VolumeTier> },
+}
+
+#[serde_as]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeTier {
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub min_notional: U256,
+    pub factor: f64,
 }
 
 #[serde_as]
@@ -1,58 +1,190 @@
-//! Small abstraction over the AWS S3 SDK to upload arbitrary json object to S3.
+//! Small abstraction for archiving arbitrary json objects. Objects can be
+//! stored in AWS S3, in a local directory or in memory.
 
+pub use storage::{Local, Memory, Storage, S3};
 use {
     anyhow::{anyhow, Context, Result},
-    aws_sdk_s3::{primitives::ByteStream, Client},
-    flate2::{bufread::GzEncoder, Compression},
+    chrono::{DateTime, Utc},
+    flate2::{
+        bufread::{GzDecoder, GzEncoder},
+        Compression,
+    },
     serde::Serialize,
-    std::io::Read,
+    std::{io::Read, path::PathBuf, str::FromStr, sync::Arc, time::Duration},
 };
 
-#[derive(Default)]
+mod storage;
+
+/// How often stored objects get checked against the retention period.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone)]
 pub struct Config {
-    pub bucket: String,
+    /// Where objects get stored.
+    pub backend: Backend,
     /// Prepended to the the final filename for each uploaded object.
     pub filename_prefix: String,
+    /// Whether objects get compressed using Gzip.
+    pub gzip: bool,
+    /// How object IDs get mapped to filenames.
+    pub naming: Naming,
+    /// Objects older than this get deleted. Objects are kept forever if unset.
+    pub retention: Option<Duration>,
+}
+
+impl Config {
+    /// The configuration for uploading objects to an S3 bucket with the
+    /// defaults historically used for S3: Gzip compressed, flat naming and no
+    /// retention limit.
+    pub fn s3(bucket: String, filename_prefix: String) -> Self {
+        Self::archive(
+            Backend::S3 { bucket },
+            filename_prefix,
+            None,
+            Naming::Flat,
+            None,
+        )
+    }
+
+    /// The configuration for archiving objects with the specified backend.
+    /// Unless configured otherwise, objects uploaded to S3 get Gzip compressed
+    /// and objects stored in a local directory don't.
+    pub fn archive(
+        backend: Backend,
+        filename_prefix: String,
+        gzip: Option<bool>,
+        naming: Naming,
+        retention: Option<Duration>,
+    ) -> Self {
+        let gzip = gzip.unwrap_or(matches!(backend, Backend::S3 { .. }));
+        Self {
+            backend,
+            filename_prefix,
+            gzip,
+            naming,
+            retention,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Backend {
+    /// An AWS S3 bucket. Credentials are loaded from the environment.
+    S3 { bucket: String },
+    /// A directory on the local filesystem.
+    Local { dir: PathBuf },
+}
+
+/// The naming scheme for stored objects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Naming {
+    /// `<prefix><id>.json`
+    #[default]
+    Flat,
+    /// `<prefix><yyyy>/<mm>/<dd>/<id>.json`, using the UTC date at which the
+    /// object gets stored.
+    Dated,
+}
+
+impl Naming {
+    fn filename(&self, id: &str, now: DateTime<Utc>) -> String {
+        match self {
+            Self::Flat => format!("{id}.json"),
+            Self::Dated => format!("{}/{id}.json", now.format("%Y/%m/%d")),
+        }
+    }
+}
+
+impl FromStr for Naming {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "flat" => Ok(Self::Flat),
+            "dated" => Ok(Self::Dated),
+            _ => anyhow::bail!("unknown naming scheme {s:?}, expected \"flat\" or \"dated\""),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Uploader {
-    bucket: String,
+    storage: Arc<dyn Storage>,
     filename_prefix: String,
-    client: Client,
+    gzip: bool,
+    naming: Naming,
+    retention: Option<Duration>,
 }
 
 impl Uploader {
+    /// Creates an uploader for the configured backend. If a retention period
+    /// is configured, expired objects get pruned in the background.
     pub async fn new(config: Config) -> Self {
-        let uploader = Self {
-            bucket: config.bucket,
-            filename_prefix: config.filename_prefix,
-            client: Client::new(&aws_config::from_env().load().await),
+        let (storage, is_s3): (Arc<dyn Storage>, _) = match config.backend.clone() {
+            Backend::S3 { bucket } => (Arc::new(S3::new(bucket).await), true),
+            Backend::Local { dir } => (Arc::new(Local::new(dir)), false),
         };
-        uploader.assert_credentials_are_usable().await;
+        let uploader = Self::with_storage(storage, config);
+        if is_s3 {
+            uploader.assert_credentials_are_usable().await;
+        }
+        if uploader.retention.is_some() {
+            tokio::spawn(uploader.clone().prune_periodically());
+        }
         uploader
     }
 
-    /// Upload the bytes json encoded to the configured S3 bucket. Returns the
+    /// Creates an uploader for the specified storage, ignoring the backend
+    /// from the configuration. No background pruning gets scheduled.
+    pub fn with_storage(storage: Arc<dyn Storage>, config: Config) -> Self {
+        Self {
+            storage,
+            filename_prefix: config.filename_prefix,
+            gzip: config.gzip,
+            naming: config.naming,
+            retention: config.retention,
+        }
+    }
+
+    /// Upload the bytes json encoded to the configured storage. Returns the
     /// key under which the file can be queried
     pub async fn upload(&self, id: String, content: impl Serialize) -> Result<String> {
         let bytes = serde_json::to_vec(&content)?;
-        let encoded = self.gzip(&bytes)?;
-        let key = std::path::Path::new(&self.filename_prefix)
-            .join(format!("{id}.json"))
+        let bytes = if self.gzip { gzip(&bytes)? } else { bytes };
+        let key = self.key(&id, Utc::now())?;
+        self.storage.put(&key, bytes, self.gzip).await
+    }
+
+    /// Downloads and decodes the json object that was uploaded with the
+    /// specified ID. Only supported with the flat naming scheme, since the
+    /// date at which the object was uploaded is not known.
+    pub async fn download(&self, id: &str) -> Result<serde_json::Value> {
+        anyhow::ensure!(
+            self.naming == Naming::Flat,
+            "downloads require the flat naming scheme"
+        );
+        let key = self.key(id, Utc::now())?;
+        let bytes = self.storage.get(&key, self.gzip).await?;
+        let bytes = if self.gzip { gunzip(&bytes)? } else { bytes };
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    fn key(&self, id: &str, now: DateTime<Utc>) -> Result<String> {
+        Ok(std::path::Path::new(&self.filename_prefix)
+            .join(self.naming.filename(id, now))
             .to_str()
             .context(anyhow!("invalid path: {id}"))?
-            .to_string();
-        self.client
-            .put_object()
-            .bucket(self.bucket.clone())
-            .key(key.clone())
-            .body(ByteStream::new(encoded.into()))
-            .content_encoding("gzip")
-            .content_type("application/json")
-            .send()
-            .await?;
-        Ok(key)
+            .to_string())
+    }
+
+    /// Deletes all objects under the configured prefix that are older than the
+    /// retention period. Returns the number of deleted objects.
+    pub async fn prune(&self) -> Result<usize> {
+        let Some(retention) = self.retention else {
+            return Ok(0);
+        };
+        let cutoff = Utc::now() - chrono::Duration::from_std(retention)?;
+        self.storage.prune(&self.filename_prefix, cutoff).await
     }
 
     /// Uploads a small test file to verify that the credentials loaded from the
@@ -74,18 +206,148 @@ impl Uploader {
         });
     }
 
-    /// Compresses the input bytes using Gzip.
-    fn gzip(&self, bytes: &[u8]) -> Result<Vec<u8>> {
-        let mut encoder = GzEncoder::new(bytes, Compression::best());
-        let mut encoded: Vec<u8> = Vec::with_capacity(bytes.len());
-        encoder.read_to_end(&mut encoded).context("gzip encoding")?;
-        Ok(encoded)
+    async fn prune_periodically(self) {
+        let mut interval = tokio::time::interval(PRUNE_INTERVAL);
+        loop {
+            interval.tick().await;
+            match self.prune().await {
+                Ok(deleted) => tracing::debug!(deleted, "pruned archived objects"),
+                Err(err) => tracing::warn!(?err, "failed to prune archived objects"),
+            }
+        }
     }
 }
 
+/// Compresses the input bytes using Gzip.
+fn gzip(bytes: &[u8]) -> Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(bytes, Compression::best());
+    let mut encoded: Vec<u8> = Vec::with_capacity(bytes.len());
+    encoder.read_to_end(&mut encoded).context("gzip encoding")?;
+    Ok(encoded)
+}
+
+/// Decompresses Gzip encoded bytes.
+fn gunzip(bytes: &[u8]) -> Result<Vec<u8>> {
+    let mut decoded = Vec::new();
+    GzDecoder::new(bytes)
+        .read_to_end(&mut decoded)
+        .context("gzip decoding")?;
+    Ok(decoded)
+}
+
 #[cfg(test)]
 mod tests {
-    use {super::*, flate2::read::GzDecoder, serde_json::json};
+    use {super::*, serde_json::json};
+
+    fn config() -> Config {
+        Config {
+            backend: Backend::Local {
+                dir: Default::default(),
+            },
+            filename_prefix: "test/".to_string(),
+            gzip: false,
+            naming: Naming::Flat,
+            retention: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn uploads_json() {
+        let memory = Arc::new(Memory::default());
+        let uploader = Uploader::with_storage(memory.clone(), config());
+
+        let key = uploader
+            .upload("1".to_string(), json!({ "value": 1 }))
+            .await
+            .unwrap();
+
+        assert_eq!(key, "test/1.json");
+        assert_eq!(memory.get(&key).unwrap(), br#"{"value":1}"#);
+    }
+
+    #[tokio::test]
+    async fn uploads_gzip() {
+        let memory = Arc::new(Memory::default());
+        let uploader = Uploader::with_storage(
+            memory.clone(),
+            Config {
+                gzip: true,
+                ..config()
+            },
+        );
+
+        let key = uploader
+            .upload("1".to_string(), json!({ "value": 1 }))
+            .await
+            .unwrap();
+
+        let decoded = gunzip(&memory.get(&key).unwrap()).unwrap();
+        assert_eq!(decoded, br#"{"value":1}"#);
+        assert_eq!(uploader.download("1").await.unwrap(), json!({ "value": 1 }));
+    }
+
+    #[tokio::test]
+    async fn uploads_dated() {
+        let memory = Arc::new(Memory::default());
+        let uploader = Uploader::with_storage(
+            memory.clone(),
+            Config {
+                naming: Naming::Dated,
+                ..config()
+            },
+        );
+
+        let key = uploader
+            .upload("1".to_string(), json!({ "value": 1 }))
+            .await
+            .unwrap();
+
+        let date = Utc::now().format("%Y/%m/%d");
+        assert_eq!(key, format!("test/{date}/1.json"));
+        assert_eq!(memory.get(&key).unwrap(), br#"{"value":1}"#);
+        // The upload date isn't known when downloading.
+        assert!(uploader.download("1").await.is_err());
+    }
+
+    #[test]
+    fn archive_defaults() {
+        let s3 = || Backend::S3 {
+            bucket: "bucket".to_string(),
+        };
+        let local = || Backend::Local {
+            dir: "archive".into(),
+        };
+
+        let config = Config::archive(s3(), "prefix/".to_string(), None, Naming::Flat, None);
+        assert!(config.gzip);
+        assert_eq!(config.naming, Naming::Flat);
+        assert_eq!(config.filename_prefix, "prefix/");
+
+        let config = Config::archive(local(), String::new(), None, Naming::Flat, None);
+        assert!(!config.gzip);
+        assert_eq!(config.naming, Naming::Flat);
+
+        let config = Config::archive(s3(), String::new(), Some(false), Naming::Dated, None);
+        assert!(!config.gzip);
+        assert_eq!(config.naming, Naming::Dated);
+
+        let retention = Some(Duration::from_secs(60));
+        let config = Config::archive(local(), String::new(), Some(true), Naming::Dated, retention);
+        assert!(config.gzip);
+        assert_eq!(config.naming, Naming::Dated);
+        assert_eq!(config.retention, retention);
+    }
+
+    #[test]
+    fn naming() {
+        let now = DateTime::parse_from_rfc3339("2024-03-09T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(Naming::Flat.filename("42", now), "42.json");
+        assert_eq!(Naming::Dated.filename("42", now), "2024/03/09/42.json");
+        assert_eq!("dated".parse::<Naming>().unwrap(), Naming::Dated);
+        assert!("daily".parse::<Naming>().is_err());
+    }
 
     // This test requires AWS credentials to be set via env variables.
     // See https://docs.rs/aws-config/latest/aws_config/default_provider/credentials/struct.DefaultCredentialsChain.html
@@ -94,10 +356,8 @@ mod tests {
     #[tokio::test]
     #[ignore]
     async fn real_upload() {
-        let config = Config {
-            bucket: std::env::var("BUCKET").unwrap(),
-            filename_prefix: "test/".to_string(),
-        };
+        let bucket = std::env::var("BUCKET").unwrap();
+        let config = Config::s3(bucket.clone(), "test/".to_string());
 
         // Upload a reasonable amount of data. This helps see the benefits of
         // compression.
@@ -113,10 +373,10 @@ mod tests {
             .await
             .unwrap();
 
-        let get_object = uploader
-            .client
+        let client = aws_sdk_s3::Client::new(&aws_config::from_env().load().await);
+        let get_object = client
             .get_object()
-            .bucket(uploader.bucket)
+            .bucket(bucket)
             .key(key)
             .send()
             .await
@@ -0,0 +1,101 @@
+use {
+    super::Storage,
+    anyhow::{Context, Result},
+    chrono::{DateTime, Utc},
+    std::path::PathBuf,
+};
+
+/// Stores objects as files in a local directory. Keys are interpreted as paths
+/// relative to the directory. Gzip compressed objects get a `.gz` extension.
+#[derive(Debug)]
+pub struct Local {
+    dir: PathBuf,
+}
+
+impl Local {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn path(&self, key: &str, gzip: bool) -> PathBuf {
+        match gzip {
+            true => self.dir.join(format!("{key}.gz")),
+            false => self.dir.join(key),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Storage for Local {
+    async fn put(&self, key: &str, bytes: Vec<u8>, gzip: bool) -> Result<String> {
+        let path = self.path(key, gzip);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .with_context(|| format!("creating directory {parent:?}"))?;
+        }
+        tokio::fs::write(&path, bytes)
+            .await
+            .with_context(|| format!("writing file {path:?}"))?;
+        Ok(path.display().to_string())
+    }
+
+    async fn get(&self, key: &str, gzip: bool) -> Result<Vec<u8>> {
+        let path = self.path(key, gzip);
+        tokio::fs::read(&path)
+            .await
+            .with_context(|| format!("reading file {path:?}"))
+    }
+
+    async fn prune(&self, prefix: &str, cutoff: DateTime<Utc>) -> Result<usize> {
+        let root = self.dir.join(prefix);
+        if !tokio::fs::try_exists(&root).await? {
+            return Ok(0);
+        }
+
+        let mut deleted = 0;
+        let mut dirs = vec![root];
+        while let Some(dir) = dirs.pop() {
+            let mut entries = tokio::fs::read_dir(&dir)
+                .await
+                .with_context(|| format!("reading directory {dir:?}"))?;
+            while let Some(entry) = entries.next_entry().await? {
+                let metadata = entry.metadata().await?;
+                if metadata.is_dir() {
+                    dirs.push(entry.path());
+                } else if DateTime::<Utc>::from(metadata.modified()?) < cutoff {
+                    tokio::fs::remove_file(entry.path())
+                        .await
+                        .with_context(|| format!("removing file {:?}", entry.path()))?;
+                    deleted += 1;
+                }
+            }
+        }
+        Ok(deleted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, chrono::Duration};
+
+    #[tokio::test]
+    async fn put_and_prune() {
+        let dir = tempfile::tempdir().unwrap();
+        let local = Local::new(dir.path().to_owned());
+
+        let path = local.put("a/1.json", b"{}".to_vec(), false).await.unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"{}");
+        let path = local.put("a/2.json", vec![1, 2], true).await.unwrap();
+        assert!(path.ends_with("2.json.gz"));
+        assert_eq!(local.get("a/2.json", true).await.unwrap(), vec![1, 2]);
+        local.put("b/3.json", b"{}".to_vec(), false).await.unwrap();
+
+        let past = Utc::now() - Duration::hours(1);
+        assert_eq!(local.prune("a", past).await.unwrap(), 0);
+        let future = Utc::now() + Duration::hours(1);
+        assert_eq!(local.prune("a", future).await.unwrap(), 2);
+        assert_eq!(local.prune("c", future).await.unwrap(), 0);
+        assert!(dir.path().join("b/3.json").exists());
+    }
+}
@@ -0,0 +1,73 @@
+use {
+    super::Storage,
+    anyhow::Result,
+    chrono::{DateTime, Utc},
+    std::{collections::HashMap, sync::Mutex},
+};
+
+/// Keeps objects in memory. Useful for tests.
+#[derive(Debug, Default)]
+pub struct Memory {
+    objects: Mutex<HashMap<String, Object>>,
+}
+
+#[derive(Debug)]
+struct Object {
+    bytes: Vec<u8>,
+    stored_at: DateTime<Utc>,
+}
+
+impl Memory {
+    /// Returns the bytes stored under the specified key.
+    pub fn get(&self, key: &str) -> Option<Vec<u8>> {
+        self.objects
+            .lock()
+            .unwrap()
+            .get(key)
+            .map(|object| object.bytes.clone())
+    }
+}
+
+#[async_trait::async_trait]
+impl Storage for Memory {
+    async fn put(&self, key: &str, bytes: Vec<u8>, _: bool) -> Result<String> {
+        self.objects.lock().unwrap().insert(
+            key.to_string(),
+            Object {
+                bytes,
+                stored_at: Utc::now(),
+            },
+        );
+        Ok(key.to_string())
+    }
+
+    async fn get(&self, key: &str, _: bool) -> Result<Vec<u8>> {
+        Memory::get(self, key).ok_or_else(|| anyhow::anyhow!("no object stored under {key:?}"))
+    }
+
+    async fn prune(&self, prefix: &str, cutoff: DateTime<Utc>) -> Result<usize> {
+        let mut objects = self.objects.lock().unwrap();
+        let before = objects.len();
+        objects.retain(|key, object| !key.starts_with(prefix) || object.stored_at >= cutoff);
+        Ok(before - objects.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, chrono::Duration};
+
+    #[tokio::test]
+    async fn prunes_expired_objects_with_prefix() {
+        let memory = Memory::default();
+        memory.put("a/1", vec![1], false).await.unwrap();
+        memory.put("b/2", vec![2], false).await.unwrap();
+
+        let past = Utc::now() - Duration::hours(1);
+        assert_eq!(memory.prune("a/", past).await.unwrap(), 0);
+        let future = Utc::now() + Duration::hours(1);
+        assert_eq!(memory.prune("a/", future).await.unwrap(), 1);
+        assert_eq!(memory.get("a/1"), None);
+        assert_eq!(memory.get("b/2"), Some(vec![2]));
+    }
+}
@@ -0,0 +1,28 @@
+use {
+    anyhow::Result,
+    chrono::{DateTime, Utc},
+    std::fmt::Debug,
+};
+
+mod local;
+mod memory;
+mod s3;
+
+pub use {local::Local, memory::Memory, s3::S3};
+
+/// A place where archived objects get stored.
+#[async_trait::async_trait]
+pub trait Storage: Debug + Send + Sync {
+    /// Stores the bytes under the specified key. `gzip` indicates whether the
+    /// bytes are Gzip compressed. Returns the location under which the object
+    /// can be found.
+    async fn put(&self, key: &str, bytes: Vec<u8>, gzip: bool) -> Result<String>;
+
+    /// Returns the bytes stored under the specified key. `gzip` has to match
+    /// the value the object was stored with.
+    async fn get(&self, key: &str, gzip: bool) -> Result<Vec<u8>>;
+
+    /// Deletes all objects with keys starting with the specified prefix that
+    /// were stored before the cutoff. Returns the number of deleted objects.
+    async fn prune(&self, prefix: &str, cutoff: DateTime<Utc>) -> Result<usize>;
+}
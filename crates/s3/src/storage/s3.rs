@@ -0,0 +1,90 @@
+use {
+    super::Storage,
+    anyhow::Result,
+    aws_sdk_s3::{primitives::ByteStream, Client},
+    chrono::{DateTime, Utc},
+};
+
+/// Stores objects in an AWS S3 bucket.
+#[derive(Debug)]
+pub struct S3 {
+    bucket: String,
+    client: Client,
+}
+
+impl S3 {
+    /// Creates a client for the bucket with credentials loaded from the
+    /// environment.
+    pub async fn new(bucket: String) -> Self {
+        Self {
+            bucket,
+            client: Client::new(&aws_config::from_env().load().await),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Storage for S3 {
+    async fn put(&self, key: &str, bytes: Vec<u8>, gzip: bool) -> Result<String> {
+        let request = self
+            .client
+            .put_object()
+            .bucket(self.bucket.clone())
+            .key(key)
+            .body(ByteStream::new(bytes.into()))
+            .content_type("application/json");
+        let request = if gzip {
+            request.content_encoding("gzip")
+        } else {
+            request
+        };
+        request.send().await?;
+        Ok(key.to_string())
+    }
+
+    async fn get(&self, key: &str, _: bool) -> Result<Vec<u8>> {
+        let object = self
+            .client
+            .get_object()
+            .bucket(self.bucket.clone())
+            .key(key)
+            .send()
+            .await?;
+        Ok(object.body.collect().await?.to_vec())
+    }
+
+    async fn prune(&self, prefix: &str, cutoff: DateTime<Utc>) -> Result<usize> {
+        let mut deleted = 0;
+        let mut continuation_token = None;
+        loop {
+            let page = self
+                .client
+                .list_objects_v2()
+                .bucket(self.bucket.clone())
+                .prefix(prefix)
+                .set_continuation_token(continuation_token)
+                .send()
+                .await?;
+            for object in page.contents() {
+                let (Some(key), Some(last_modified)) = (object.key(), object.last_modified())
+                else {
+                    continue;
+                };
+                if last_modified.secs() >= cutoff.timestamp() {
+                    continue;
+                }
+                self.client
+                    .delete_object()
+                    .bucket(self.bucket.clone())
+                    .key(key)
+                    .send()
+                    .await?;
+                deleted += 1;
+            }
+            continuation_token = page.next_continuation_token().map(ToString::to_string);
+            if continuation_token.is_none() {
+                return Ok(deleted);
+            }
+        }
+    }
+}
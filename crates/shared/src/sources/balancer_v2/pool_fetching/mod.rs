@@ -16,6 +16,7 @@ use {
         pool_init::PoolInitializing,
         pools::{
             common::{self, PoolInfoFetcher},
+            liquidity_bootstrapping,
             stable,
             weighted,
             FactoryIndexing,
@@ -58,6 +59,7 @@ use {
 };
 pub use {
     common::TokenState,
+    liquidity_bootstrapping::WeightUpdate,
     stable::AmplificationParameter,
     weighted::{TokenState as WeightedTokenState, Version as WeightedPoolVersion},
 };
@@ -102,6 +104,22 @@ impl WeightedPool {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct LiquidityBootstrappingPool {
+    /// The pool with its weights at the fetched block.
+    pub pool: WeightedPool,
+    pub weight_update: WeightUpdate,
+}
+
+impl LiquidityBootstrappingPool {
+    pub fn new_unpaused(pool_id: H256, state: liquidity_bootstrapping::PoolState) -> Self {
+        LiquidityBootstrappingPool {
+            pool: WeightedPool::new_unpaused(pool_id, state.weighted),
+            weight_update: state.weight_update,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct StablePool {
     pub common: CommonPoolState,
@@ -128,6 +146,7 @@ impl StablePool {
 pub struct FetchedBalancerPools {
     pub stable_pools: Vec<StablePool>,
     pub weighted_pools: Vec<WeightedPool>,
+    pub liquidity_bootstrapping_pools: Vec<LiquidityBootstrappingPool>,
 }
 
 impl FetchedBalancerPools {
@@ -141,6 +160,11 @@ impl FetchedBalancerPools {
         tokens.extend(
             self.weighted_pools
                 .iter()
+                .chain(
+                    self.liquidity_bootstrapping_pools
+                        .iter()
+                        .map(|lbp| &lbp.pool),
+                )
                 .flat_map(|pool| pool.reserves.keys().copied()),
         );
         tokens
@@ -360,6 +384,9 @@ impl BalancerPoolFetching for BalancerPoolFetcher {
                     PoolKind::Weighted(state) => fetched_pools
                         .weighted_pools
                         .push(WeightedPool::new_unpaused(pool.id, state)),
+                    PoolKind::LiquidityBootstrapping(state) => fetched_pools
+                        .liquidity_bootstrapping_pools
+                        .push(LiquidityBootstrappingPool::new_unpaused(pool.id, state)),
                     PoolKind::Stable(state) => fetched_pools
                         .stable_pools
                         .push(StablePool::new_unpaused(pool.id, state)),
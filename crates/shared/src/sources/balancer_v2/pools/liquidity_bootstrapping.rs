@@ -1,7 +1,7 @@
 //! Module implementing liquidity bootstrapping pool specific indexing logic.
 
 use {
-    super::{common, FactoryIndexing, PoolIndexing},
+    super::{common, weighted, FactoryIndexing, PoolIndexing},
     crate::sources::balancer_v2::{
         graph_api::{PoolData, PoolType},
         swap::fixed_point::Bfp,
@@ -11,11 +11,69 @@ use {
         BalancerV2LiquidityBootstrappingPool,
         BalancerV2LiquidityBootstrappingPoolFactory,
     },
-    ethcontract::BlockId,
-    futures::{future::BoxFuture, FutureExt as _},
+    ethcontract::{BlockId, H160},
+    futures::{future::BoxFuture, FutureExt as _, TryFutureExt as _},
+    std::collections::BTreeMap,
 };
 
-pub use super::weighted::{PoolState, TokenState, Version};
+pub use super::weighted::{TokenState, Version};
+
+/// The state of a liquidity bootstrapping pool. These pools trade like
+/// weighted pools, but their weights change over time.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolState {
+    /// The pool state with the weights at the block it was fetched at.
+    pub weighted: weighted::PoolState,
+    /// The update of the weights from the fetched block onwards.
+    pub weight_update: WeightUpdate,
+}
+
+/// A gradual weight update of a liquidity bootstrapping pool. The token
+/// weights change linearly from their weights at `start_time` to their
+/// `end_weights` at `end_time`, and stay constant outside of that period.
+///
+/// The pool doesn't expose the weights at the start of its scheduled update,
+/// so the update starts at the fetched block if the scheduled update already
+/// started then. Interpolating from the weights at that block yields the same
+/// weights for any later time.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct WeightUpdate {
+    /// The Unix timestamp in seconds at which the weights start changing.
+    pub start_time: u64,
+    /// The Unix timestamp in seconds at which the weights reach their end
+    /// values.
+    pub end_time: u64,
+    pub end_weights: BTreeMap<H160, Bfp>,
+}
+
+impl WeightUpdate {
+    /// Creates the weight update of a pool from its tokens at the block with
+    /// the specified timestamp and its scheduled gradual weight update.
+    fn new(
+        tokens: &BTreeMap<H160, TokenState>,
+        timestamp: u64,
+        start_time: u64,
+        end_time: u64,
+        end_weights: Vec<Bfp>,
+    ) -> Self {
+        if timestamp >= end_time {
+            // The update is over, so the weights stay constant from now on.
+            return Self {
+                start_time: timestamp,
+                end_time: timestamp,
+                end_weights: tokens
+                    .iter()
+                    .map(|(address, token)| (*address, token.weight))
+                    .collect(),
+            };
+        }
+        Self {
+            start_time: start_time.max(timestamp),
+            end_time,
+            end_weights: tokens.keys().copied().zip(end_weights).collect(),
+        }
+    }
+}
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct PoolInfo {
@@ -63,15 +121,35 @@ impl FactoryIndexing for BalancerV2LiquidityBootstrappingPoolFactory {
         // need to fetch them every time.
         let fetch_weights = pool_contract.get_normalized_weights().block(block).call();
         let fetch_swap_enabled = pool_contract.get_swap_enabled().block(block).call();
+        let fetch_weight_update = pool_contract
+            .get_gradual_weight_update_params()
+            .block(block)
+            .call();
+        let web3 = self.raw_instance().web3();
+        // The weight update is anchored at the fetched block.
+        let fetch_timestamp = async move {
+            let header = web3
+                .eth()
+                .block(block)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("block {block:?} not found"))?;
+            Ok::<_, anyhow::Error>(header.timestamp.as_u64())
+        };
 
         async move {
-            let (common, weights, swap_enabled) =
-                futures::try_join!(fetch_common, fetch_weights, fetch_swap_enabled)?;
+            let (common, weights, swap_enabled, (start_time, end_time, end_weights), timestamp) =
+                futures::try_join!(
+                    fetch_common,
+                    fetch_weights.map_err(anyhow::Error::from),
+                    fetch_swap_enabled.map_err(anyhow::Error::from),
+                    fetch_weight_update.map_err(anyhow::Error::from),
+                    fetch_timestamp,
+                )?;
             if !swap_enabled {
                 return Ok(None);
             }
 
-            let tokens = common
+            let tokens: BTreeMap<_, _> = common
                 .tokens
                 .into_iter()
                 .zip(&weights)
@@ -85,12 +163,22 @@ impl FactoryIndexing for BalancerV2LiquidityBootstrappingPoolFactory {
                     )
                 })
                 .collect();
+            let weight_update = WeightUpdate::new(
+                &tokens,
+                timestamp,
+                start_time.as_u64(),
+                end_time.as_u64(),
+                end_weights.into_iter().map(Bfp::from_wei).collect(),
+            );
             let swap_fee = common.swap_fee;
 
             Ok(Some(PoolState {
-                tokens,
-                swap_fee,
-                version: Version::V0,
+                weighted: weighted::PoolState {
+                    tokens,
+                    swap_fee,
+                    version: Version::V0,
+                },
+                weight_update,
             }))
         }
         .boxed()
@@ -103,6 +191,7 @@ mod tests {
         super::*,
         crate::sources::balancer_v2::graph_api::Token,
         ethcontract::{H160, H256},
+        maplit::btreemap,
     };
 
     #[test]
@@ -129,4 +218,52 @@ mod tests {
 
         assert!(PoolInfo::from_graph_data(&pool, 42).is_err());
     }
+
+    #[test]
+    fn anchors_weight_update_at_fetched_block() {
+        let token = |weight| TokenState {
+            common: common::TokenState {
+                balance: 1_000.into(),
+                scaling_factor: Bfp::exp10(0),
+            },
+            weight,
+        };
+        let tokens = btreemap! {
+            H160([1; 20]) => token(bfp!("0.6")),
+            H160([2; 20]) => token(bfp!("0.4")),
+        };
+        let end_weights = vec![bfp!("0.2"), bfp!("0.8")];
+
+        // Ongoing updates start at the fetched block.
+        assert_eq!(
+            WeightUpdate::new(&tokens, 150, 100, 200, end_weights.clone()),
+            WeightUpdate {
+                start_time: 150,
+                end_time: 200,
+                end_weights: btreemap! {
+                    H160([1; 20]) => bfp!("0.2"),
+                    H160([2; 20]) => bfp!("0.8"),
+                },
+            }
+        );
+
+        // Scheduled updates keep their start time.
+        assert_eq!(
+            WeightUpdate::new(&tokens, 50, 100, 200, end_weights.clone()).start_time,
+            100
+        );
+
+        // Finished updates keep the current weights.
+        assert_eq!(
+            WeightUpdate::new(&tokens, 250, 100, 200, end_weights),
+            WeightUpdate {
+                start_time: 250,
+                end_time: 250,
+                end_weights: btreemap! {
+                    H160([1; 20]) => bfp!("0.6"),
+                    H160([2; 20]) => bfp!("0.4"),
+                },
+            }
+        );
+    }
 }
@@ -33,6 +33,7 @@ pub struct Pool {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum PoolKind {
     Weighted(weighted::PoolState),
+    LiquidityBootstrapping(liquidity_bootstrapping::PoolState),
     Stable(stable::PoolState),
 }
 
@@ -47,6 +48,7 @@ macro_rules! impl_from_state {
 }
 
 impl_from_state!(weighted::PoolState, Weighted);
+impl_from_state!(liquidity_bootstrapping::PoolState, LiquidityBootstrapping);
 impl_from_state!(stable::PoolState, Stable);
 
 #[derive(Clone, Debug, Eq, PartialEq)]
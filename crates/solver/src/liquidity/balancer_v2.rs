@@ -76,11 +76,19 @@ impl BalancerV2Liquidity {
         let weighted_product_orders: Vec<_> = pools
             .weighted_pools
             .into_iter()
-            .map(|pool| WeightedProductOrder {
+            .map(|pool| (pool, None))
+            .chain(
+                pools
+                    .liquidity_bootstrapping_pools
+                    .into_iter()
+                    .map(|lbp| (lbp.pool, Some(lbp.weight_update))),
+            )
+            .map(|(pool, weight_update)| WeightedProductOrder {
                 address: pool.common.address,
                 reserves: pool.reserves,
                 fee: pool.common.swap_fee,
                 version: pool.version,
+                weight_update,
                 settlement_handling: Arc::new(SettlementHandler {
                     pool_id: pool.common.id,
                     inner: inner.clone(),
@@ -374,6 +382,7 @@ mod tests {
                     Ok(FetchedBalancerPools {
                         stable_pools: stable_pools.clone(),
                         weighted_pools: weighted_pools.clone(),
+                        liquidity_bootstrapping_pools: Vec::new(),
                     })
                 }
             });
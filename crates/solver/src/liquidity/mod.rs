@@ -23,6 +23,7 @@ use {
                 pool_fetching::{
                     AmplificationParameter,
                     TokenState,
+                    WeightUpdate,
                     WeightedPoolVersion,
                     WeightedTokenState,
                 },
@@ -297,6 +298,9 @@ pub struct WeightedProductOrder {
     pub reserves: BTreeMap<H160, WeightedTokenState>,
     pub fee: Bfp,
     pub version: WeightedPoolVersion,
+    /// The weight update of liquidity bootstrapping pools, whose weights
+    /// change over time. The reserves hold the weights at the fetched block.
+    pub weight_update: Option<WeightUpdate>,
     #[cfg_attr(test, derivative(PartialEq = "ignore"))]
     pub settlement_handling: Arc<dyn SettlementHandling<Self>>,
 }
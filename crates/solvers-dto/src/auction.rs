@@ -127,6 +127,17 @@ pub enum FeePolicy {
     },
     #[serde(rename_all = "camelCase")]
     Volume { factor: f64 },
+    #[serde(rename_all = "camelCase")]
+    TieredVolume { tiers: Vec<VolumeTier> },
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeTier {
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub min_notional: U256,
+    pub factor: f64,
 }
 
 #[serde_as]
@@ -160,7 +171,9 @@ pub struct Token {
 pub enum Liquidity {
     ConstantProduct(ConstantProductPool),
     WeightedProduct(WeightedProductPool),
+    LiquidityBootstrapping(LiquidityBootstrappingPool),
     Stable(StablePool),
+    ComposableStable(StablePool),
     ConcentratedLiquidity(ConcentratedLiquidityPool),
     LimitOrder(ForeignLimitOrder),
 }
@@ -217,6 +230,33 @@ pub enum WeightedProductVersion {
     V3Plus,
 }
 
+#[serde_as]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidityBootstrappingPool {
+    pub id: String,
+    pub address: H160,
+    pub balancer_pool_id: H256,
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub gas_estimate: U256,
+    pub tokens: HashMap<H160, LiquidityBootstrappingReserve>,
+    pub fee: BigDecimal,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub swap_enabled: bool,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidityBootstrappingReserve {
+    #[serde_as(as = "HexOrDecimalU256")]
+    pub balance: U256,
+    pub scaling_factor: BigDecimal,
+    pub start_weight: BigDecimal,
+    pub end_weight: BigDecimal,
+}
+
 #[serde_as]
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -62,6 +62,7 @@ pub enum Kind {
         reason: String,
     },
     Cancelled,
+    Expired,
     Fail,
     PostprocessingTimedOut,
 }
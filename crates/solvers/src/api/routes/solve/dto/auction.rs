@@ -70,7 +70,13 @@ pub fn to_domain(auction: &Auction) -> Result<auction::Auction, Error> {
                 Liquidity::WeightedProduct(liquidity) => {
                     weighted_product_pool::to_domain(liquidity)
                 }
+                Liquidity::LiquidityBootstrapping(liquidity) => {
+                    liquidity_bootstrapping_pool::to_domain(liquidity)
+                }
                 Liquidity::Stable(liquidity) => stable_pool::to_domain(liquidity),
+                Liquidity::ComposableStable(liquidity) => {
+                    stable_pool::to_composable_domain(liquidity)
+                }
                 Liquidity::ConcentratedLiquidity(liquidity) => {
                     concentrated_liquidity_pool::to_domain(liquidity)
                 }
@@ -153,41 +159,115 @@ mod weighted_product_pool {
     }
 }
 
-mod stable_pool {
+mod liquidity_bootstrapping_pool {
     use super::*;
 
-    pub fn to_domain(pool: &StablePool) -> Result<liquidity::Liquidity, Error> {
+    pub fn to_domain(pool: &LiquidityBootstrappingPool) -> Result<liquidity::Liquidity, Error> {
         let reserves = {
             let entries = pool
                 .tokens
                 .iter()
                 .map(|(address, token)| {
-                    Ok(liquidity::stable::Reserve {
+                    Ok(liquidity::liquidity_bootstrapping::Reserve {
                         asset: eth::Asset {
                             token: eth::TokenAddress(*address),
                             amount: token.balance,
                         },
+                        start_weight: conv::decimal_to_rational(&token.start_weight)
+                            .ok_or("invalid token start weight")?,
+                        end_weight: conv::decimal_to_rational(&token.end_weight)
+                            .ok_or("invalid token end weight")?,
                         scale: conv::decimal_to_rational(&token.scaling_factor)
                             .and_then(liquidity::ScalingFactor::new)
                             .ok_or("invalid token scaling factor")?,
                     })
                 })
                 .collect::<Result<Vec<_>, Error>>()?;
-            liquidity::stable::Reserves::new(entries).ok_or("duplicate stable token addresses")?
+            liquidity::liquidity_bootstrapping::Reserves::new(entries)
+                .ok_or("duplicate liquidity bootstrapping token addresses")?
         };
 
+        Ok(liquidity::Liquidity {
+            id: liquidity::Id(pool.id.clone()),
+            address: pool.address,
+            gas: eth::Gas(pool.gas_estimate),
+            state: liquidity::State::LiquidityBootstrapping(
+                liquidity::liquidity_bootstrapping::Pool {
+                    reserves,
+                    fee: conv::decimal_to_rational(&pool.fee)
+                        .ok_or("invalid liquidity bootstrapping fee")?,
+                    schedule: liquidity::liquidity_bootstrapping::Schedule {
+                        start: pool.start_time,
+                        end: pool.end_time,
+                    },
+                    swap_enabled: pool.swap_enabled,
+                },
+            ),
+        })
+    }
+}
+
+mod stable_pool {
+    use super::*;
+
+    pub fn to_domain(pool: &StablePool) -> Result<liquidity::Liquidity, Error> {
+        let (reserves, amplification_parameter, fee) = to_domain_state(pool)?;
         Ok(liquidity::Liquidity {
             id: liquidity::Id(pool.id.clone()),
             address: pool.address,
             gas: eth::Gas(pool.gas_estimate),
             state: liquidity::State::Stable(liquidity::stable::Pool {
                 reserves,
-                amplification_parameter: conv::decimal_to_rational(&pool.amplification_parameter)
-                    .ok_or("invalid amplification parameter")?,
-                fee: conv::decimal_to_rational(&pool.fee).ok_or("invalid stable pool fee")?,
+                amplification_parameter,
+                fee,
             }),
         })
     }
+
+    pub fn to_composable_domain(pool: &StablePool) -> Result<liquidity::Liquidity, Error> {
+        let (reserves, amplification_parameter, fee) = to_domain_state(pool)?;
+        Ok(liquidity::Liquidity {
+            id: liquidity::Id(pool.id.clone()),
+            address: pool.address,
+            gas: eth::Gas(pool.gas_estimate),
+            state: liquidity::State::ComposableStable(liquidity::composable_stable::Pool {
+                bpt: eth::TokenAddress(pool.address),
+                reserves,
+                amplification_parameter,
+                fee,
+            }),
+        })
+    }
+
+    fn to_domain_state(
+        pool: &StablePool,
+    ) -> Result<(liquidity::stable::Reserves, eth::Rational, eth::Rational), Error> {
+        let reserves = {
+            let entries = pool
+                .tokens
+                .iter()
+                .map(|(address, token)| {
+                    Ok(liquidity::stable::Reserve {
+                        asset: eth::Asset {
+                            token: eth::TokenAddress(*address),
+                            amount: token.balance,
+                        },
+                        scale: conv::decimal_to_rational(&token.scaling_factor)
+                            .and_then(liquidity::ScalingFactor::new)
+                            .ok_or("invalid token scaling factor")?,
+                    })
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+            liquidity::stable::Reserves::new(entries).ok_or("duplicate stable token addresses")?
+        };
+
+        Ok((
+            reserves,
+            conv::decimal_to_rational(&pool.amplification_parameter)
+                .ok_or("invalid amplification parameter")?,
+            conv::decimal_to_rational(&pool.fee).ok_or("invalid stable pool fee")?,
+        ))
+    }
 }
 
 mod concentrated_liquidity_pool {
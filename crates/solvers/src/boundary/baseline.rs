@@ -3,7 +3,7 @@
 use {
     crate::{
         boundary,
-        domain::{eth, liquidity, order, solver},
+        domain::{auction, eth, liquidity, order, solver},
     },
     ethereum_types::{H160, U256},
     model::TokenPair,
@@ -12,28 +12,142 @@ use {
 };
 
 pub struct Solver<'a> {
+    weth: eth::TokenAddress,
     base_tokens: BaseTokens,
     onchain_liquidity: HashMap<TokenPair, Vec<OnchainLiquidity>>,
     liquidity: HashMap<liquidity::Id, &'a liquidity::Liquidity>,
+    gas_pricing: Option<GasPricing<'a>>,
+}
+
+/// Prices used for converting the gas used by a route into an amount of the
+/// traded tokens.
+struct GasPricing<'a> {
+    gas_price: auction::GasPrice,
+    tokens: &'a auction::Tokens,
 }
 
 impl<'a> Solver<'a> {
+    /// Creates a solver routing over the specified liquidity. Time dependent
+    /// liquidity is evaluated at the specified Unix timestamp.
     pub fn new(
         weth: &eth::WethAddress,
         base_tokens: &HashSet<eth::TokenAddress>,
         liquidity: &'a [liquidity::Liquidity],
+        timestamp: u64,
     ) -> Self {
         Self {
+            weth: eth::TokenAddress(weth.0),
             base_tokens: to_boundary_base_tokens(weth, base_tokens),
-            onchain_liquidity: to_boundary_liquidity(liquidity),
+            onchain_liquidity: to_boundary_liquidity(liquidity, timestamp),
             liquidity: liquidity
                 .iter()
                 .map(|liquidity| (liquidity.id.clone(), liquidity))
                 .collect(),
+            gas_pricing: None,
+        }
+    }
+
+    /// Makes route selection account for the cost of the gas used by each
+    /// route, so that routes are compared by their net output (or input for
+    /// buy orders) instead of their raw one. Routes for tokens without a
+    /// reference price are still compared by their raw amounts.
+    pub fn with_gas_price(self, gas_price: auction::GasPrice, tokens: &'a auction::Tokens) -> Self {
+        Self {
+            gas_pricing: Some(GasPricing { gas_price, tokens }),
+            ..self
         }
     }
 
-    pub fn route(&self, request: solver::Request, max_hops: usize) -> Option<solver::Route<'a>> {
+    pub fn route(
+        &self,
+        request: solver::baseline::Request,
+        max_hops: usize,
+    ) -> Option<solver::baseline::Route<'a>> {
+        self.route_over(&self.onchain_liquidity, request, max_hops)
+    }
+
+    /// Finds a route for the request like [`Self::route`], without using any
+    /// of the excluded liquidity.
+    pub fn route_excluding(
+        &self,
+        request: solver::baseline::Request,
+        max_hops: usize,
+        excluded: &HashSet<liquidity::Id>,
+    ) -> Option<solver::baseline::Route<'a>> {
+        let onchain_liquidity = self
+            .onchain_liquidity
+            .iter()
+            .map(|(pair, liquidity)| {
+                (
+                    *pair,
+                    liquidity
+                        .iter()
+                        .filter(|liquidity| !excluded.contains(&liquidity.id))
+                        .cloned()
+                        .collect(),
+                )
+            })
+            .collect();
+        self.route_over(&onchain_liquidity, request, max_hops)
+    }
+
+    /// Computes a route over the same path of liquidity as an existing route,
+    /// but for a different request.
+    pub fn reroute(
+        &self,
+        route: &solver::baseline::Route<'a>,
+        request: solver::baseline::Request,
+    ) -> Option<solver::baseline::Route<'a>> {
+        self.route_along(&route.hops(), request)
+    }
+
+    /// Computes a route for the request along the specified hops. Returns
+    /// `None` if the hops don't connect the request's tokens, if some of their
+    /// liquidity is not available, or if the route does not satisfy the
+    /// request.
+    pub fn route_along(
+        &self,
+        hops: &[solver::baseline::Hop],
+        request: solver::baseline::Request,
+    ) -> Option<solver::baseline::Route<'a>> {
+        let connected = hops.first()?.input == request.sell.token
+            && hops.last()?.output == request.buy.token
+            && hops.windows(2).all(|pair| pair[0].output == pair[1].input);
+        connected.then_some(())?;
+
+        let path = hops
+            .iter()
+            .map(|hop| {
+                let token_pair = TokenPair::new(hop.input.0, hop.output.0)?;
+                self.onchain_liquidity
+                    .get(&token_pair)?
+                    .iter()
+                    .find(|liquidity| liquidity.id == hop.liquidity)
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let sell = match request.side {
+            order::Side::Sell => request.sell.amount,
+            order::Side::Buy => path.iter().zip(hops).rev().try_fold(
+                request.buy.amount,
+                |amount, (liquidity, hop)| {
+                    liquidity.get_amount_in(hop.input.0, (amount, hop.output.0))
+                },
+            )?,
+        };
+        let segments = self.traverse_path(&path, request.sell.token.0, sell)?;
+
+        let buy = segments.last()?.output.amount;
+        (buy >= request.buy.amount && sell <= request.sell.amount).then_some(())?;
+        solver::baseline::Route::new(segments)
+    }
+
+    fn route_over(
+        &self,
+        onchain_liquidity: &HashMap<TokenPair, Vec<OnchainLiquidity>>,
+        request: solver::baseline::Request,
+        max_hops: usize,
+    ) -> Option<solver::baseline::Route<'a>> {
         let candidates = self.base_tokens.path_candidates_with_hops(
             request.sell.token.0,
             request.buy.token.0,
@@ -47,7 +161,7 @@ impl<'a> Solver<'a> {
                     let sell = baseline_solver::estimate_sell_amount(
                         request.buy.amount,
                         path,
-                        &self.onchain_liquidity,
+                        onchain_liquidity,
                     )?;
                     let segments =
                         self.traverse_path(&sell.path, request.sell.token.0, sell.value)?;
@@ -64,14 +178,17 @@ impl<'a> Solver<'a> {
 
                     (sell.value <= request.sell.amount).then_some((segments, sell))
                 })
-                .min_by_key(|(_, sell)| sell.value)?,
+                .min_by_key(|(segments, sell)| {
+                    sell.value
+                        .saturating_add(self.gas_cost(segments_gas(segments), request.sell.token))
+                })?,
             order::Side::Sell => candidates
                 .iter()
                 .filter_map(|path| {
                     let buy = baseline_solver::estimate_buy_amount(
                         request.sell.amount,
                         path,
-                        &self.onchain_liquidity,
+                        onchain_liquidity,
                     )?;
                     let segments =
                         self.traverse_path(&buy.path, request.sell.token.0, request.sell.amount)?;
@@ -88,10 +205,32 @@ impl<'a> Solver<'a> {
 
                     (buy.value >= request.buy.amount).then_some((segments, buy))
                 })
-                .max_by_key(|(_, buy)| buy.value)?,
+                .max_by_key(|(segments, buy)| {
+                    buy.value
+                        .saturating_sub(self.gas_cost(segments_gas(segments), request.buy.token))
+                })?,
+        };
+
+        solver::baseline::Route::new(segments)
+    }
+
+    /// Returns the cost of the specified amount of gas, denominated in the
+    /// specified token. Returns zero if the gas cost can't be priced in that
+    /// token.
+    pub fn gas_cost(&self, gas: eth::Gas, token: eth::TokenAddress) -> U256 {
+        let Some(pricing) = &self.gas_pricing else {
+            return U256::zero();
+        };
+        let price = match pricing.tokens.reference_price(&token) {
+            Some(price) if !price.0 .0.is_zero() => price,
+            None if token == self.weth => auction::Price(eth::Ether(U256::exp10(18))),
+            _ => return U256::zero(),
         };
 
-        solver::Route::new(segments)
+        gas.0
+            .checked_mul(pricing.gas_price.0 .0)
+            .and_then(|cost| price.ether_value(eth::Ether(cost)))
+            .unwrap_or(U256::MAX)
     }
 
     fn traverse_path(
@@ -99,7 +238,7 @@ impl<'a> Solver<'a> {
         path: &[&OnchainLiquidity],
         mut sell_token: H160,
         mut sell_amount: U256,
-    ) -> Option<Vec<solver::Segment<'a>>> {
+    ) -> Option<Vec<solver::baseline::Segment<'a>>> {
         let mut segments = Vec::new();
         for liquidity in path {
             let reference_liquidity = self
@@ -113,7 +252,7 @@ impl<'a> Solver<'a> {
                 .expect("Inconsistent path");
             let buy_amount = liquidity.get_amount_out(buy_token, (sell_amount, sell_token))?;
 
-            segments.push(solver::Segment {
+            segments.push(solver::baseline::Segment {
                 liquidity: reference_liquidity,
                 input: eth::Asset {
                     token: eth::TokenAddress(sell_token),
@@ -123,7 +262,11 @@ impl<'a> Solver<'a> {
                     token: eth::TokenAddress(buy_token),
                     amount: buy_amount,
                 },
-                gas: eth::Gas(liquidity.gas_cost().into()),
+                gas: eth::Gas(
+                    liquidity
+                        .gas_cost_for_swap(buy_token, (sell_amount, sell_token))
+                        .into(),
+                ),
             });
 
             sell_token = buy_token;
@@ -133,8 +276,15 @@ impl<'a> Solver<'a> {
     }
 }
 
+fn segments_gas(segments: &[solver::baseline::Segment]) -> eth::Gas {
+    eth::Gas(segments.iter().fold(U256::zero(), |acc, segment| {
+        acc.saturating_add(segment.gas.0)
+    }))
+}
+
 fn to_boundary_liquidity(
     liquidity: &[liquidity::Liquidity],
+    timestamp: u64,
 ) -> HashMap<TokenPair, Vec<OnchainLiquidity>> {
     liquidity
         .iter()
@@ -192,6 +342,58 @@ fn to_boundary_liquidity(
                         }
                     }
                 }
+                liquidity::State::LiquidityBootstrapping(pool) => {
+                    if let Some(boundary_pool) = pool.at(timestamp).and_then(|pool| {
+                        boundary::liquidity::weighted_product::to_boundary_pool(
+                            liquidity.address,
+                            &pool,
+                        )
+                    }) {
+                        for pair in pool.reserves.token_pairs() {
+                            let token_pair = to_boundary_token_pair(&pair);
+                            onchain_liquidity.entry(token_pair).or_default().push(
+                                OnchainLiquidity {
+                                    id: liquidity.id.clone(),
+                                    token_pair,
+                                    source: LiquiditySource::WeightedProduct(boundary_pool.clone()),
+                                },
+                            );
+                        }
+                    }
+                }
+                liquidity::State::ComposableStable(pool) => {
+                    if let Some(boundary_pool) =
+                        boundary::liquidity::stable::to_boundary_composable_pool(
+                            liquidity.address,
+                            pool,
+                        )
+                    {
+                        for pair in pool.token_pairs() {
+                            let token_pair = to_boundary_token_pair(&pair);
+                            onchain_liquidity.entry(token_pair).or_default().push(
+                                OnchainLiquidity {
+                                    id: liquidity.id.clone(),
+                                    token_pair,
+                                    source: LiquiditySource::Stable(boundary_pool.clone()),
+                                },
+                            );
+                        }
+                    }
+                }
+                liquidity::State::Concentrated(pool) => {
+                    if let Some(boundary_pool) =
+                        boundary::liquidity::concentrated::to_boundary_pool(liquidity.gas, pool)
+                    {
+                        onchain_liquidity
+                            .entry(boundary_pool.tokens)
+                            .or_default()
+                            .push(OnchainLiquidity {
+                                id: liquidity.id.clone(),
+                                token_pair: boundary_pool.tokens,
+                                source: LiquiditySource::Concentrated(boundary_pool),
+                            });
+                    }
+                }
                 liquidity::State::LimitOrder(limit_order) => {
                     if let Some(token_pair) =
                         TokenPair::new(limit_order.maker.token.0, limit_order.taker.token.0)
@@ -206,34 +408,46 @@ fn to_boundary_liquidity(
                             })
                     }
                 }
-                // The baseline solver does not currently support other AMMs.
-                _ => {}
             };
             onchain_liquidity
         })
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 struct OnchainLiquidity {
     id: liquidity::Id,
     token_pair: TokenPair,
     source: LiquiditySource,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 enum LiquiditySource {
     ConstantProduct(boundary::liquidity::constant_product::Pool),
     WeightedProduct(boundary::liquidity::weighted_product::Pool),
     Stable(boundary::liquidity::stable::Pool),
+    Concentrated(boundary::liquidity::concentrated::Pool),
     LimitOrder(liquidity::limit_order::LimitOrder),
 }
 
+impl OnchainLiquidity {
+    /// Returns the gas cost for swapping the specified input. This is the same
+    /// as [`BaselineSolvable::gas_cost`] except for liquidity where the gas
+    /// cost depends on the traded amounts.
+    fn gas_cost_for_swap(&self, out_token: H160, input: (U256, H160)) -> usize {
+        match &self.source {
+            LiquiditySource::Concentrated(pool) => pool.gas_cost_for_swap(out_token, input),
+            _ => self.gas_cost(),
+        }
+    }
+}
+
 impl BaselineSolvable for OnchainLiquidity {
     fn get_amount_out(&self, out_token: H160, input: (U256, H160)) -> Option<U256> {
         match &self.source {
             LiquiditySource::ConstantProduct(pool) => pool.get_amount_out(out_token, input),
             LiquiditySource::WeightedProduct(pool) => pool.get_amount_out(out_token, input),
             LiquiditySource::Stable(pool) => pool.get_amount_out(out_token, input),
+            LiquiditySource::Concentrated(pool) => pool.get_amount_out(out_token, input),
             LiquiditySource::LimitOrder(limit_order) => {
                 limit_order.get_amount_out(out_token, input)
             }
@@ -245,6 +459,7 @@ impl BaselineSolvable for OnchainLiquidity {
             LiquiditySource::ConstantProduct(pool) => pool.get_amount_in(in_token, out),
             LiquiditySource::WeightedProduct(pool) => pool.get_amount_in(in_token, out),
             LiquiditySource::Stable(pool) => pool.get_amount_in(in_token, out),
+            LiquiditySource::Concentrated(pool) => pool.get_amount_in(in_token, out),
             LiquiditySource::LimitOrder(limit_order) => limit_order.get_amount_in(in_token, out),
         }
     }
@@ -254,6 +469,7 @@ impl BaselineSolvable for OnchainLiquidity {
             LiquiditySource::ConstantProduct(pool) => pool.gas_cost(),
             LiquiditySource::WeightedProduct(pool) => pool.gas_cost(),
             LiquiditySource::Stable(pool) => pool.gas_cost(),
+            LiquiditySource::Concentrated(pool) => pool.gas_cost(),
             LiquiditySource::LimitOrder(limit_order) => limit_order.gas_cost(),
         }
     }
@@ -0,0 +1,719 @@
+//! Swap simulation for Uniswap V3-like concentrated liquidity pools.
+//!
+//! Unlike the other liquidity sources, [`shared`] does not provide
+//! [`BaselineSolvable`] logic for concentrated liquidity, so this module ports
+//! the relevant parts of the Uniswap V3 core contracts (`TickMath`,
+//! `SqrtPriceMath`, `SwapMath` and the `swap` loop of `UniswapV3Pool`). The
+//! main difference with the on-chain implementation is that we step directly
+//! from one initialized tick to the next instead of walking the tick bitmap one
+//! word at a time. This can cause the computed amounts to be off by a few wei
+//! for swaps that cover large price ranges.
+
+use {
+    crate::{
+        domain::{eth, liquidity},
+        util,
+    },
+    ethereum_types::{H160, U256, U512},
+    model::TokenPair,
+    shared::baseline_solver::BaselineSolvable,
+};
+
+/// The minimum tick that can be used in a pool.
+const MIN_TICK: i32 = -887272;
+/// The maximum tick that can be used in a pool.
+const MAX_TICK: i32 = -MIN_TICK;
+
+/// Gas used for each initialized tick that gets crossed during a swap. This is
+/// the same value that the Uniswap smart order router uses for its estimates.
+pub const GAS_PER_TICK_CROSSED: u64 = 31_000;
+
+/// Fee denominator used by Uniswap V3 pools (the fee is specified in "pips",
+/// or hundredths of a basis point).
+const FEE_DENOMINATOR: u32 = 1_000_000;
+
+/// A concentrated liquidity pool that can be used for baseline routing.
+#[derive(Clone, Debug)]
+pub struct Pool {
+    pub tokens: TokenPair,
+    sqrt_price: U256,
+    liquidity: u128,
+    tick: i32,
+    liquidity_net: Vec<(i32, i128)>,
+    fee: u32,
+    gas: u64,
+}
+
+/// Converts a domain pool into a concentrated liquidity pool used for baseline
+/// routing. Returns `None` if the domain pool cannot be represented as a
+/// boundary pool.
+pub fn to_boundary_pool(gas: eth::Gas, pool: &liquidity::concentrated::Pool) -> Option<Pool> {
+    let (token0, token1) = pool.tokens.get();
+    let tokens = TokenPair::new(token0.0, token1.0)?;
+
+    // Only support fees that can be represented exactly in pips, which is all
+    // of the fee tiers that Uniswap V3 pools can be configured with.
+    let fee = pool.fee.0.numer().checked_mul(FEE_DENOMINATOR.into())?;
+    if !(fee % pool.fee.0.denom()).is_zero() {
+        return None;
+    }
+    let fee = fee / pool.fee.0.denom();
+    if fee >= FEE_DENOMINATOR.into() {
+        return None;
+    }
+
+    if pool.sqrt_price.0 < min_sqrt_ratio() || pool.sqrt_price.0 >= max_sqrt_ratio() {
+        return None;
+    }
+    if !(MIN_TICK..=MAX_TICK).contains(&pool.tick.0) {
+        return None;
+    }
+
+    Some(Pool {
+        tokens,
+        sqrt_price: pool.sqrt_price.0,
+        liquidity: pool.liquidity.0,
+        tick: pool.tick.0,
+        liquidity_net: pool
+            .liquidity_net
+            .iter()
+            .map(|(tick, net)| (tick.0, net.0))
+            .collect(),
+        fee: fee.as_u32(),
+        gas: gas.0.try_into().unwrap_or(u64::MAX),
+    })
+}
+
+/// Returns the state of a domain pool after swapping the specified input
+/// through it. Returns `None` if the swap is not possible.
+pub fn to_swapped_pool(
+    pool: &liquidity::concentrated::Pool,
+    input: eth::Asset,
+) -> Option<liquidity::concentrated::Pool> {
+    let boundary_pool = to_boundary_pool(eth::Gas::default(), pool)?;
+    let out_token = boundary_pool.tokens.other(&input.token.0)?;
+    let zero_for_one = boundary_pool.zero_for_one(input.token.0, out_token)?;
+    let swap = boundary_pool.swap(zero_for_one, Amount::ExactIn(input.amount))?;
+
+    Some(liquidity::concentrated::Pool {
+        sqrt_price: liquidity::concentrated::SqrtPrice(swap.sqrt_price),
+        liquidity: liquidity::concentrated::Amount(swap.liquidity),
+        tick: liquidity::concentrated::Tick(swap.tick),
+        ..pool.clone()
+    })
+}
+
+impl Pool {
+    /// Returns the gas needed for swapping the specified input through the
+    /// pool. This accounts for the additional costs of crossing initialized
+    /// ticks. Falls back to the base gas cost if the swap is not possible.
+    pub fn gas_cost_for_swap(&self, out_token: H160, (in_amount, in_token): (U256, H160)) -> usize {
+        let ticks_crossed = self
+            .zero_for_one(in_token, out_token)
+            .and_then(|zero_for_one| self.swap(zero_for_one, Amount::ExactIn(in_amount)))
+            .map(|swap| swap.ticks_crossed)
+            .unwrap_or_default();
+        let gas = self
+            .gas
+            .saturating_add(GAS_PER_TICK_CROSSED.saturating_mul(ticks_crossed));
+        gas.try_into().unwrap_or(usize::MAX)
+    }
+
+    /// Returns whether a swap from `in_token` to `out_token` goes from `token0`
+    /// to `token1`, or `None` if the tokens do not match the pool.
+    fn zero_for_one(&self, in_token: H160, out_token: H160) -> Option<bool> {
+        let (token0, token1) = self.tokens.get();
+        match (in_token, out_token) {
+            (a, b) if a == token0 && b == token1 => Some(true),
+            (a, b) if a == token1 && b == token0 => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Returns the next initialized tick in the swap direction along with its
+    /// net liquidity. If there are no more initialized ticks, then the minimum
+    /// or maximum tick is returned instead.
+    fn next_tick(&self, tick: i32, zero_for_one: bool) -> (i32, Option<i128>) {
+        let next = if zero_for_one {
+            self.liquidity_net
+                .iter()
+                .rev()
+                .find(|(initialized, _)| *initialized <= tick)
+        } else {
+            self.liquidity_net
+                .iter()
+                .find(|(initialized, _)| *initialized > tick)
+        };
+        match next {
+            Some((tick, net)) => ((*tick).clamp(MIN_TICK, MAX_TICK), Some(*net)),
+            None if zero_for_one => (MIN_TICK, None),
+            None => (MAX_TICK, None),
+        }
+    }
+
+    /// Simulates a swap through the pool, crossing ticks as needed. Returns
+    /// `None` if the pool does not have enough liquidity for the swap or on
+    /// arithmetic errors.
+    fn swap(&self, zero_for_one: bool, amount: Amount) -> Option<Swap> {
+        let limit = if zero_for_one {
+            min_sqrt_ratio() + 1
+        } else {
+            max_sqrt_ratio() - 1
+        };
+
+        let (exact_in, mut remaining) = match amount {
+            Amount::ExactIn(amount) => (true, amount),
+            Amount::ExactOut(amount) => (false, amount),
+        };
+        let mut calculated = U256::zero();
+        let mut sqrt_price = self.sqrt_price;
+        let mut tick = self.tick;
+        let mut liquidity = self.liquidity;
+        let mut ticks_crossed = 0;
+
+        while !remaining.is_zero() {
+            if sqrt_price == limit {
+                return None;
+            }
+
+            let (tick_next, liquidity_net) = self.next_tick(tick, zero_for_one);
+            let sqrt_price_next = sqrt_ratio_at_tick(tick_next)?;
+            let target = if zero_for_one {
+                sqrt_price_next.max(limit)
+            } else {
+                sqrt_price_next.min(limit)
+            };
+
+            let step =
+                compute_swap_step(sqrt_price, target, liquidity, remaining, exact_in, self.fee)?;
+            sqrt_price = step.sqrt_price_next;
+            if exact_in {
+                remaining = remaining.checked_sub(step.amount_in.checked_add(step.fee_amount)?)?;
+                calculated = calculated.checked_add(step.amount_out)?;
+            } else {
+                remaining = remaining.checked_sub(step.amount_out)?;
+                calculated =
+                    calculated.checked_add(step.amount_in.checked_add(step.fee_amount)?)?;
+            }
+
+            if sqrt_price == sqrt_price_next {
+                if let Some(liquidity_net) = liquidity_net {
+                    let liquidity_net = if zero_for_one {
+                        liquidity_net.checked_neg()?
+                    } else {
+                        liquidity_net
+                    };
+                    liquidity = add_liquidity_delta(liquidity, liquidity_net)?;
+                    ticks_crossed += 1;
+                }
+                tick = if zero_for_one {
+                    tick_next - 1
+                } else {
+                    tick_next
+                };
+            }
+        }
+
+        Some(Swap {
+            amount: calculated,
+            ticks_crossed,
+            sqrt_price,
+            tick,
+            liquidity,
+        })
+    }
+}
+
+impl BaselineSolvable for Pool {
+    fn get_amount_out(&self, out_token: H160, (in_amount, in_token): (U256, H160)) -> Option<U256> {
+        let zero_for_one = self.zero_for_one(in_token, out_token)?;
+        Some(self.swap(zero_for_one, Amount::ExactIn(in_amount))?.amount)
+    }
+
+    fn get_amount_in(&self, in_token: H160, (out_amount, out_token): (U256, H160)) -> Option<U256> {
+        let zero_for_one = self.zero_for_one(in_token, out_token)?;
+        Some(
+            self.swap(zero_for_one, Amount::ExactOut(out_amount))?
+                .amount,
+        )
+    }
+
+    fn gas_cost(&self) -> usize {
+        self.gas.try_into().unwrap_or(usize::MAX)
+    }
+}
+
+/// The specified amount of a swap.
+enum Amount {
+    ExactIn(U256),
+    ExactOut(U256),
+}
+
+/// The result of a simulated swap.
+struct Swap {
+    /// The computed amount: the output amount for exact input swaps and the
+    /// input amount (including fees) for exact output swaps.
+    amount: U256,
+    /// The number of initialized ticks that were crossed.
+    ticks_crossed: u64,
+    /// The square root price of the pool after the swap.
+    sqrt_price: U256,
+    /// The tick of the pool after the swap. Note that this is only updated
+    /// when crossing initialized ticks, which is sufficient for simulating
+    /// subsequent swaps.
+    tick: i32,
+    /// The active liquidity of the pool after the swap.
+    liquidity: u128,
+}
+
+/// A single step of a swap within a tick range.
+struct Step {
+    sqrt_price_next: U256,
+    amount_in: U256,
+    amount_out: U256,
+    fee_amount: U256,
+}
+
+fn min_sqrt_ratio() -> U256 {
+    U256::from(4295128739_u64)
+}
+
+fn max_sqrt_ratio() -> U256 {
+    U256::from_dec_str("1461446703485210103287273052203988822378723970342")
+        .expect("valid decimal number")
+}
+
+fn q96() -> U256 {
+    U256::one() << 96
+}
+
+/// Computes the Q64.96 square root price at the specified tick.
+///
+/// Port of `TickMath.getSqrtRatioAtTick`.
+fn sqrt_ratio_at_tick(tick: i32) -> Option<U256> {
+    const RATIOS: [u128; 19] = [
+        0xfff97272373d413259a46990580e213a,
+        0xfff2e50f5f656932ef12357cf3c7fdcc,
+        0xffe5caca7e10e4e61c3624eaa0941cd0,
+        0xffcb9843d60f6159c9db58835c926644,
+        0xff973b41fa98c081472e6896dfb254c0,
+        0xff2ea16466c96a3843ec78b326b52861,
+        0xfe5dee046a99a2a811c461f1969c3053,
+        0xfcbe86c7900a88aedcffc83b479aa3a4,
+        0xf987a7253ac413176f2b074cf7815e54,
+        0xf3392b0822b70005940c7a398e4b70f3,
+        0xe7159475a2c29b7443b29c7fa6e889d9,
+        0xd097f3bdfd2022b8845ad8f792aa5825,
+        0xa9f746462d870fdf8a65dc1f90e061e5,
+        0x70d869a156d2a1b890bb3df62baf32f7,
+        0x31be135f97d08fd981231505542fcfa6,
+        0x9aa508b5b7a84e1c677de54f3e99bc9,
+        0x5d6af8dedb81196699c329225ee604,
+        0x2216e584f5fa1ea926041bedfe98,
+        0x48a170391f7dc42444e8fa2,
+    ];
+
+    let abs_tick = tick.unsigned_abs();
+    if abs_tick > MAX_TICK.unsigned_abs() {
+        return None;
+    }
+
+    let mut ratio = if abs_tick & 0x1 != 0 {
+        U256::from(0xfffcb933bd6fad37aa2d162d1a594001_u128)
+    } else {
+        U256::one() << 128
+    };
+    for (i, factor) in RATIOS.iter().enumerate() {
+        if abs_tick & (0x2 << i) != 0 {
+            ratio = (ratio * U256::from(*factor)) >> 128;
+        }
+    }
+    if tick > 0 {
+        ratio = U256::MAX / ratio;
+    }
+
+    // Round up to make sure that the result is the smallest Q64.96 value whose
+    // tick is at least `tick`.
+    let rounding = if (ratio % (U256::one() << 32)).is_zero() {
+        U256::zero()
+    } else {
+        U256::one()
+    };
+    Some((ratio >> 32) + rounding)
+}
+
+/// Computes `a * b / denominator` with full precision. Returns `None` on
+/// division by zero or if the result does not fit in 256 bits.
+fn mul_div(a: U256, b: U256, denominator: U256) -> Option<U256> {
+    if denominator.is_zero() {
+        return None;
+    }
+    (a.full_mul(b) / U512::from(denominator)).try_into().ok()
+}
+
+/// Like [`mul_div`] but rounds the result up.
+fn mul_div_rounding_up(a: U256, b: U256, denominator: U256) -> Option<U256> {
+    if denominator.is_zero() {
+        return None;
+    }
+    let (quotient, remainder) = a.full_mul(b).div_mod(U512::from(denominator));
+    let quotient = U256::try_from(quotient).ok()?;
+    if remainder.is_zero() {
+        Some(quotient)
+    } else {
+        quotient.checked_add(U256::one())
+    }
+}
+
+/// Computes the amount of `token0` between two prices.
+///
+/// Port of `SqrtPriceMath.getAmount0Delta`.
+fn amount0_delta(a: U256, b: U256, liquidity: u128, round_up: bool) -> Option<U256> {
+    let (a, b) = if a > b { (b, a) } else { (a, b) };
+    if a.is_zero() {
+        return None;
+    }
+
+    let numerator1 = U256::from(liquidity) << 96;
+    let numerator2 = b - a;
+    if round_up {
+        util::math::div_ceil(mul_div_rounding_up(numerator1, numerator2, b)?, a)
+    } else {
+        Some(mul_div(numerator1, numerator2, b)? / a)
+    }
+}
+
+/// Computes the amount of `token1` between two prices.
+///
+/// Port of `SqrtPriceMath.getAmount1Delta`.
+fn amount1_delta(a: U256, b: U256, liquidity: u128, round_up: bool) -> Option<U256> {
+    let (a, b) = if a > b { (b, a) } else { (a, b) };
+    if round_up {
+        mul_div_rounding_up(liquidity.into(), b - a, q96())
+    } else {
+        mul_div(liquidity.into(), b - a, q96())
+    }
+}
+
+/// Computes the next price given a `token0` delta.
+///
+/// Port of `SqrtPriceMath.getNextSqrtPriceFromAmount0RoundingUp`.
+fn next_sqrt_price_from_amount0(
+    sqrt_price: U256,
+    liquidity: u128,
+    amount: U256,
+    add: bool,
+) -> Option<U256> {
+    if amount.is_zero() {
+        return Some(sqrt_price);
+    }
+
+    // We compute with 512-bit integers, so there is no need for the overflow
+    // handling of the original implementation.
+    let numerator1 = U512::from(U256::from(liquidity) << 96);
+    let product = amount.full_mul(sqrt_price);
+    let denominator = if add {
+        numerator1 + product
+    } else {
+        numerator1.checked_sub(product).filter(|d| !d.is_zero())?
+    };
+
+    let (quotient, remainder) = (numerator1 * U512::from(sqrt_price)).div_mod(denominator);
+    let quotient = U256::try_from(quotient).ok()?;
+    if remainder.is_zero() {
+        Some(quotient)
+    } else {
+        quotient.checked_add(U256::one())
+    }
+}
+
+/// Computes the next price given a `token1` delta.
+///
+/// Port of `SqrtPriceMath.getNextSqrtPriceFromAmount1RoundingDown`.
+fn next_sqrt_price_from_amount1(
+    sqrt_price: U256,
+    liquidity: u128,
+    amount: U256,
+    add: bool,
+) -> Option<U256> {
+    if add {
+        let quotient = mul_div(amount, q96(), liquidity.into())?;
+        sqrt_price.checked_add(quotient)
+    } else {
+        let quotient = mul_div_rounding_up(amount, q96(), liquidity.into())?;
+        sqrt_price
+            .checked_sub(quotient)
+            .filter(|price| !price.is_zero())
+    }
+}
+
+/// Computes the result of swapping some amount in or out within a single tick
+/// range.
+///
+/// Port of `SwapMath.computeSwapStep`.
+fn compute_swap_step(
+    sqrt_price: U256,
+    target: U256,
+    liquidity: u128,
+    remaining: U256,
+    exact_in: bool,
+    fee: u32,
+) -> Option<Step> {
+    let zero_for_one = sqrt_price >= target;
+    let fee_denominator = U256::from(FEE_DENOMINATOR);
+
+    let (sqrt_price_next, max_in, max_out) = if exact_in {
+        let remaining_less_fee =
+            mul_div(remaining, (FEE_DENOMINATOR - fee).into(), fee_denominator)?;
+        let max_in = if zero_for_one {
+            amount0_delta(target, sqrt_price, liquidity, true)?
+        } else {
+            amount1_delta(sqrt_price, target, liquidity, true)?
+        };
+        let next = if remaining_less_fee >= max_in {
+            target
+        } else if zero_for_one {
+            next_sqrt_price_from_amount0(sqrt_price, liquidity, remaining_less_fee, true)?
+        } else {
+            next_sqrt_price_from_amount1(sqrt_price, liquidity, remaining_less_fee, true)?
+        };
+        (next, Some(max_in), None)
+    } else {
+        let max_out = if zero_for_one {
+            amount1_delta(target, sqrt_price, liquidity, false)?
+        } else {
+            amount0_delta(sqrt_price, target, liquidity, false)?
+        };
+        let next = if remaining >= max_out {
+            target
+        } else if zero_for_one {
+            next_sqrt_price_from_amount1(sqrt_price, liquidity, remaining, false)?
+        } else {
+            next_sqrt_price_from_amount0(sqrt_price, liquidity, remaining, false)?
+        };
+        (next, None, Some(max_out))
+    };
+
+    let max = sqrt_price_next == target;
+    let (amount_in, mut amount_out) = if zero_for_one {
+        (
+            match max_in {
+                Some(max_in) if max => max_in,
+                _ => amount0_delta(sqrt_price_next, sqrt_price, liquidity, true)?,
+            },
+            match max_out {
+                Some(max_out) if max => max_out,
+                _ => amount1_delta(sqrt_price_next, sqrt_price, liquidity, false)?,
+            },
+        )
+    } else {
+        (
+            match max_in {
+                Some(max_in) if max => max_in,
+                _ => amount1_delta(sqrt_price, sqrt_price_next, liquidity, true)?,
+            },
+            match max_out {
+                Some(max_out) if max => max_out,
+                _ => amount0_delta(sqrt_price, sqrt_price_next, liquidity, false)?,
+            },
+        )
+    };
+
+    // Cap the output amount to not exceed the remaining output amount.
+    if !exact_in && amount_out > remaining {
+        amount_out = remaining;
+    }
+
+    let fee_amount = if exact_in && sqrt_price_next != target {
+        // We didn't reach the target, so take the remainder of the maximum
+        // input as fee.
+        remaining.checked_sub(amount_in)?
+    } else {
+        mul_div_rounding_up(amount_in, fee.into(), (FEE_DENOMINATOR - fee).into())?
+    };
+
+    Some(Step {
+        sqrt_price_next,
+        amount_in,
+        amount_out,
+        fee_amount,
+    })
+}
+
+/// Adds a signed liquidity delta to an amount of liquidity.
+fn add_liquidity_delta(liquidity: u128, delta: i128) -> Option<u128> {
+    if delta < 0 {
+        liquidity.checked_sub(delta.unsigned_abs())
+    } else {
+        liquidity.checked_add(delta.unsigned_abs())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, shared::addr};
+
+    fn pool(liquidity_net: Vec<(i32, i128)>, liquidity: u128, tick: i32) -> Pool {
+        Pool {
+            tokens: TokenPair::new(
+                addr!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"),
+                addr!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"),
+            )
+            .unwrap(),
+            sqrt_price: sqrt_ratio_at_tick(tick).unwrap(),
+            liquidity,
+            tick,
+            liquidity_net,
+            fee: 3000,
+            gas: 108_163,
+        }
+    }
+
+    #[test]
+    fn sqrt_ratio_at_tick_bounds() {
+        assert_eq!(sqrt_ratio_at_tick(0).unwrap(), q96());
+        assert_eq!(sqrt_ratio_at_tick(MIN_TICK).unwrap(), min_sqrt_ratio());
+        assert_eq!(sqrt_ratio_at_tick(MAX_TICK).unwrap(), max_sqrt_ratio());
+        assert!(sqrt_ratio_at_tick(MIN_TICK - 1).is_none());
+        assert!(sqrt_ratio_at_tick(MAX_TICK + 1).is_none());
+    }
+
+    #[test]
+    fn swap_within_single_tick_range() {
+        let liquidity = 10_u128.pow(24);
+        let pool = pool(
+            vec![(-600, liquidity as i128), (600, -(liquidity as i128))],
+            liquidity,
+            0,
+        );
+        let (token0, token1) = pool.tokens.get();
+
+        let amount_in = U256::exp10(18);
+        let amount_out = pool.get_amount_out(token1, (amount_in, token0)).unwrap();
+
+        // At a price of 1 and 0.3% fees, we expect slightly less than 0.997
+        // tokens out because of price impact.
+        assert_eq!(amount_out, U256::from(996_999_005_991_991_025_u64));
+        assert_eq!(
+            pool.gas_cost_for_swap(token1, (amount_in, token0)),
+            pool.gas_cost()
+        );
+    }
+
+    #[test]
+    fn amount_out_in_round_trip() {
+        let liquidity = 10_u128.pow(24);
+        let pool = pool(
+            vec![(-600, liquidity as i128), (600, -(liquidity as i128))],
+            liquidity,
+            0,
+        );
+        let (token0, token1) = pool.tokens.get();
+
+        let amount_out = U256::exp10(18);
+        let amount_in = pool.get_amount_in(token0, (amount_out, token1)).unwrap();
+        let round_trip = pool.get_amount_out(token1, (amount_in, token0)).unwrap();
+
+        assert_eq!(amount_in, U256::from(1_003_010_030_091_273_824_u64));
+        assert_eq!(round_trip, amount_out);
+    }
+
+    #[test]
+    fn swap_crosses_ticks() {
+        let pool = pool(
+            vec![
+                (-1200, 10_i128.pow(20)),
+                (-60, 10_i128.pow(18)),
+                (60, -10_i128.pow(18)),
+                (1200, -10_i128.pow(20)),
+            ],
+            10_u128.pow(20) + 10_u128.pow(18),
+            0,
+        );
+        let (token0, token1) = pool.tokens.get();
+
+        // Swapping a large amount requires crossing into the wider liquidity
+        // range, which should get accounted for in the gas cost.
+        let amount_in = U256::exp10(18);
+        assert_eq!(
+            pool.get_amount_out(token1, (amount_in, token0)).unwrap(),
+            U256::from(987_207_970_349_392_433_u64),
+        );
+        assert_eq!(
+            pool.gas_cost_for_swap(token1, (amount_in, token0)),
+            pool.gas_cost() + GAS_PER_TICK_CROSSED as usize,
+        );
+    }
+
+    #[test]
+    fn swapped_pool_crosses_ticks() {
+        let (token0, token1) = (
+            eth::TokenAddress(addr!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48")),
+            eth::TokenAddress(addr!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2")),
+        );
+        let pool = liquidity::concentrated::Pool {
+            tokens: liquidity::TokenPair::new(token0, token1).unwrap(),
+            sqrt_price: liquidity::concentrated::SqrtPrice(q96()),
+            liquidity: liquidity::concentrated::Amount(10_u128.pow(20) + 10_u128.pow(18)),
+            tick: liquidity::concentrated::Tick(0),
+            liquidity_net: [
+                (-1200, 10_i128.pow(20)),
+                (-60, 10_i128.pow(18)),
+                (60, -10_i128.pow(18)),
+                (1200, -10_i128.pow(20)),
+            ]
+            .into_iter()
+            .map(|(tick, net)| {
+                (
+                    liquidity::concentrated::Tick(tick),
+                    liquidity::concentrated::LiquidityNet(net),
+                )
+            })
+            .collect(),
+            fee: liquidity::concentrated::Fee(eth::Rational::new_raw(3.into(), 1000.into())),
+        };
+
+        let swapped = to_swapped_pool(
+            &pool,
+            eth::Asset {
+                token: token0,
+                amount: U256::exp10(18),
+            },
+        )
+        .unwrap();
+
+        // The swap crosses the narrow liquidity position, leaving only the
+        // wide position active.
+        assert_eq!(swapped.tick, liquidity::concentrated::Tick(-61));
+        assert_eq!(
+            swapped.liquidity,
+            liquidity::concentrated::Amount(10_u128.pow(20))
+        );
+        assert!(swapped.sqrt_price.0 < sqrt_ratio_at_tick(-60).unwrap());
+    }
+
+    #[test]
+    fn insufficient_liquidity() {
+        let liquidity = 10_u128.pow(18);
+        let pool = pool(
+            vec![(-60, liquidity as i128), (60, -(liquidity as i128))],
+            liquidity,
+            0,
+        );
+        let (token0, token1) = pool.tokens.get();
+
+        let amount = U256::exp10(24);
+        assert!(pool.get_amount_out(token1, (amount, token0)).is_none());
+        assert!(pool.get_amount_in(token0, (amount, token1)).is_none());
+    }
+
+    #[test]
+    fn wrong_tokens() {
+        let pool = pool(vec![], 10_u128.pow(18), 0);
+        let (token0, _) = pool.tokens.get();
+        let other = addr!("def1ca1fb7fbcdc777520aa7f396b4e015f497ab");
+
+        assert!(pool.get_amount_out(other, (U256::one(), token0)).is_none());
+        assert!(pool.get_amount_in(other, (U256::one(), token0)).is_none());
+    }
+}
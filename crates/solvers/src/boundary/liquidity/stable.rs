@@ -11,6 +11,39 @@ use {
 /// Converts a domain pool into a [`shared`] Balancer V2 stable pool. Returns
 /// `None` if the domain pool cannot be represented as a boundary pool.
 pub fn to_boundary_pool(address: H160, pool: &liquidity::stable::Pool) -> Option<Pool> {
+    to_boundary(
+        address,
+        &pool.reserves,
+        &pool.amplification_parameter,
+        &pool.fee,
+    )
+}
+
+/// Converts a domain composable stable pool into a [`shared`] Balancer V2
+/// stable pool. The BPT reserve is kept, as the [`shared`] stable pool math
+/// already excludes the reserve for the token at the pool address. Returns
+/// `None` if the domain pool cannot be represented as a boundary pool.
+pub fn to_boundary_composable_pool(
+    address: H160,
+    pool: &liquidity::composable_stable::Pool,
+) -> Option<Pool> {
+    if pool.bpt.0 != address {
+        return None;
+    }
+    to_boundary(
+        address,
+        &pool.reserves,
+        &pool.amplification_parameter,
+        &pool.fee,
+    )
+}
+
+fn to_boundary(
+    address: H160,
+    reserves: &liquidity::stable::Reserves,
+    amplification_parameter: &eth::Rational,
+    fee: &eth::Rational,
+) -> Option<Pool> {
     // NOTE: this is only used for encoding and not for solving, so it's OK to
     // use this an approximate value for now. In fact, Balancer V2 pool IDs
     // are `pool address || pool kind || pool index`, so this approximation is
@@ -21,9 +54,8 @@ pub fn to_boundary_pool(address: H160, pool: &liquidity::stable::Pool) -> Option
         H256(buf)
     };
 
-    let swap_fee = to_fixed_point(&pool.fee)?;
-    let reserves = pool
-        .reserves
+    let swap_fee = to_fixed_point(fee)?;
+    let reserves = reserves
         .iter()
         .map(|reserve| {
             Some((
@@ -36,8 +68,8 @@ pub fn to_boundary_pool(address: H160, pool: &liquidity::stable::Pool) -> Option
         })
         .collect::<Option<_>>()?;
     let amplification_parameter = AmplificationParameter::try_new(
-        *pool.amplification_parameter.numer(),
-        *pool.amplification_parameter.denom(),
+        *amplification_parameter.numer(),
+        *amplification_parameter.denom(),
     )
     .ok()?;
 
@@ -100,4 +100,11 @@ impl Deadline {
     pub fn reduce(self, duration: chrono::Duration) -> Self {
         Self(self.0 - duration)
     }
+
+    /// Returns the deadline as a Unix timestamp in seconds. Solutions are
+    /// expected to get executed around this time, so it is used for evaluating
+    /// liquidity whose state depends on time.
+    pub fn timestamp(&self) -> u64 {
+        self.0.timestamp().try_into().unwrap_or(0)
+    }
 }
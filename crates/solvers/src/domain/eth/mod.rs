@@ -63,6 +63,18 @@ impl std::ops::Add<SignedGas> for Gas {
     }
 }
 
+impl std::ops::Sub<SignedGas> for Gas {
+    type Output = Self;
+
+    fn sub(self, rhs: SignedGas) -> Self::Output {
+        if rhs.0.is_positive() {
+            Self(self.0.saturating_sub(rhs.0.into()))
+        } else {
+            Self(self.0.saturating_add(rhs.0.abs().into()))
+        }
+    }
+}
+
 /// A 256-bit rational type.
 pub type Rational = num::rational::Ratio<U256>;
 
@@ -0,0 +1,35 @@
+use crate::domain::{
+    eth,
+    liquidity::{self, stable},
+};
+
+/// The state of a Balancer-like composable stable pool. These are stable pools
+/// that include their own pool token (BPT) in their reserves. The BPT can't be
+/// swapped and is not considered when swapping between the pool's other
+/// tokens.
+#[derive(Clone, Debug)]
+pub struct Pool {
+    /// The pool token, whose address is the pool's address.
+    pub bpt: eth::TokenAddress,
+    /// The pool reserves, including the BPT reserve.
+    pub reserves: stable::Reserves,
+    pub amplification_parameter: eth::Rational,
+    pub fee: eth::Rational,
+}
+
+impl Pool {
+    /// Returns an iterator over the token reserves, excluding the BPT reserve.
+    pub fn tradable_reserves(&self) -> impl Iterator<Item = stable::Reserve> + '_ {
+        self.reserves
+            .iter()
+            .filter(|reserve| reserve.asset.token != self.bpt)
+    }
+
+    /// Returns an iterator over the tokens pairs that can be swapped with the
+    /// pool, excluding any pairs with the BPT.
+    pub fn token_pairs(&self) -> impl Iterator<Item = liquidity::TokenPair> + '_ {
+        self.reserves
+            .token_pairs()
+            .filter(|pair| pair.get().0 != self.bpt && pair.get().1 != self.bpt)
+    }
+}
@@ -0,0 +1,145 @@
+use {
+    crate::domain::{
+        eth,
+        liquidity::{self, weighted_product},
+    },
+    ethereum_types::U256,
+    itertools::Itertools as _,
+};
+
+/// The state of a Balancer-like liquidity bootstrapping pool. These are
+/// weighted product pools whose weights change linearly from their start to
+/// their end weights over the pool's weight update period.
+#[derive(Clone, Debug)]
+pub struct Pool {
+    pub reserves: Reserves,
+    pub fee: eth::Rational,
+    pub schedule: Schedule,
+    pub swap_enabled: bool,
+}
+
+impl Pool {
+    /// Returns the state of the pool as a weighted product pool with its
+    /// weights at the specified Unix timestamp. Returns `None` if swaps are
+    /// disabled for the pool.
+    pub fn at(&self, timestamp: u64) -> Option<weighted_product::Pool> {
+        if !self.swap_enabled {
+            return None;
+        }
+
+        let progress = self.schedule.progress(timestamp);
+        let reserves = self
+            .reserves
+            .iter()
+            .map(|reserve| {
+                Some(weighted_product::Reserve {
+                    asset: reserve.asset,
+                    weight: reserve.weight(progress)?,
+                    scale: reserve.scale,
+                })
+            })
+            .collect::<Option<_>>()?;
+
+        Some(weighted_product::Pool {
+            reserves: weighted_product::Reserves::new(reserves)?,
+            fee: self.fee,
+            // Liquidity bootstrapping pools use the original weighted pool
+            // math.
+            version: weighted_product::Version::V0,
+        })
+    }
+}
+
+/// The period over which the weights of a liquidity bootstrapping pool change.
+#[derive(Clone, Copy, Debug)]
+pub struct Schedule {
+    /// The Unix timestamp in seconds at which the weights start changing.
+    pub start: u64,
+    /// The Unix timestamp in seconds at which the weights reach their end
+    /// values.
+    pub end: u64,
+}
+
+impl Schedule {
+    /// Returns the progress of the weight update at the specified timestamp as
+    /// an 18 decimal fixed point value between 0 and 1.
+    fn progress(&self, timestamp: u64) -> U256 {
+        if timestamp <= self.start {
+            U256::zero()
+        } else if timestamp >= self.end {
+            U256::exp10(18)
+        } else {
+            U256::from(timestamp - self.start) * U256::exp10(18) / U256::from(self.end - self.start)
+        }
+    }
+}
+
+/// A representation of liquidity bootstrapping pool reserves.
+#[derive(Clone, Debug)]
+pub struct Reserves(Vec<Reserve>);
+
+impl Reserves {
+    /// Returns a new reserve instance for specified reserve entries. Returns
+    /// `None` if it encounters duplicate entries for a token.
+    pub fn new(mut reserves: Vec<Reserve>) -> Option<Self> {
+        // Note that we sort the reserves by their token address, for the same
+        // reasons as for weighted product pools.
+        reserves.sort_unstable_by_key(|reserve| reserve.asset.token);
+
+        let has_duplicates = reserves
+            .iter()
+            .tuple_windows()
+            .any(|(a, b)| a.asset.token == b.asset.token);
+        if has_duplicates {
+            return None;
+        }
+
+        Some(Self(reserves))
+    }
+
+    /// Returns an iterator over the token reserves.
+    pub fn iter(&self) -> impl Iterator<Item = Reserve> + '_ {
+        self.0.iter().cloned()
+    }
+
+    /// Returns an iterator over the tokens pairs handled by the pool reserves.
+    pub fn token_pairs(&self) -> impl Iterator<Item = liquidity::TokenPair> + '_ {
+        self.0
+            .iter()
+            .tuple_combinations()
+            .map(|(a, b)| liquidity::TokenPair::new(a.asset.token, b.asset.token).expect("a != b"))
+    }
+}
+
+/// A liquidity bootstrapping pool token reserve.
+#[derive(Clone, Debug)]
+pub struct Reserve {
+    pub asset: eth::Asset,
+    pub start_weight: eth::Rational,
+    pub end_weight: eth::Rational,
+    pub scale: liquidity::ScalingFactor,
+}
+
+impl Reserve {
+    /// Returns the weight of the reserve for the specified progress of the
+    /// weight update. Like the pool contract, this interpolates between the
+    /// start and end weights as 18 decimal fixed point values.
+    fn weight(&self, progress: U256) -> Option<eth::Rational> {
+        let base = U256::exp10(18);
+        let to_fixed_point = |weight: &eth::Rational| {
+            weight
+                .numer()
+                .checked_mul(base)?
+                .checked_div(*weight.denom())
+        };
+        let start = to_fixed_point(&self.start_weight)?;
+        let end = to_fixed_point(&self.end_weight)?;
+
+        let weight = if end >= start {
+            start.checked_add((end - start).checked_mul(progress)? / base)?
+        } else {
+            start.checked_sub((start - end).checked_mul(progress)? / base)?
+        };
+        Some(eth::Rational::new_raw(weight, base))
+    }
+}
@@ -1,8 +1,10 @@
 //! Modelling on-chain liquidity.
 
+pub mod composable_stable;
 pub mod concentrated;
 pub mod constant_product;
 pub mod limit_order;
+pub mod liquidity_bootstrapping;
 pub mod stable;
 pub mod weighted_product;
 
@@ -18,6 +20,42 @@ pub struct Liquidity {
     pub state: State,
 }
 
+impl Liquidity {
+    /// Returns all tokens that can be traded with this liquidity.
+    pub fn tokens(&self) -> Vec<eth::TokenAddress> {
+        match &self.state {
+            State::ConstantProduct(pool) => {
+                let (a, b) = pool.tokens().get();
+                vec![a, b]
+            }
+            State::WeightedProduct(pool) => pool
+                .reserves
+                .iter()
+                .map(|reserve| reserve.asset.token)
+                .collect(),
+            State::LiquidityBootstrapping(pool) => pool
+                .reserves
+                .iter()
+                .map(|reserve| reserve.asset.token)
+                .collect(),
+            State::Stable(pool) => pool
+                .reserves
+                .iter()
+                .map(|reserve| reserve.asset.token)
+                .collect(),
+            State::ComposableStable(pool) => pool
+                .tradable_reserves()
+                .map(|reserve| reserve.asset.token)
+                .collect(),
+            State::Concentrated(pool) => {
+                let (a, b) = pool.tokens.get();
+                vec![a, b]
+            }
+            State::LimitOrder(order) => vec![order.maker.token, order.taker.token],
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Id(pub String);
 
@@ -26,7 +64,9 @@ pub struct Id(pub String);
 pub enum State {
     ConstantProduct(constant_product::Pool),
     WeightedProduct(weighted_product::Pool),
+    LiquidityBootstrapping(liquidity_bootstrapping::Pool),
     Stable(stable::Pool),
+    ComposableStable(composable_stable::Pool),
     Concentrated(concentrated::Pool),
     LimitOrder(limit_order::LimitOrder),
 }
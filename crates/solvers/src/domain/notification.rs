@@ -51,6 +51,7 @@ pub enum Settlement {
     Revert(TransactionHash),
     SimulationRevert,
     Fail,
+    Expired,
 }
 
 #[derive(Debug)]
@@ -363,6 +363,14 @@ pub struct Allowance {
 pub const INITIALIZATION_COST: u64 = 32_000;
 /// minimum gas every settlement takes (isSolver)
 pub const SETTLEMENT: u64 = 7365;
+/// gas per trade excluding erc20 transfers
+pub const TRADE: u64 =
+    // computeTradeExecutions
+    35_000 +
+    // transferFromAccounts and transferToAccount overhead
+    2 * 3000 +
+    // overhead of one interaction
+    3000;
 /// lower bound for an erc20 transfer.
 ///
 /// Value was computed by taking 52 percentile median of `transfer()` costs
@@ -0,0 +1,464 @@
+//! Back-off from tokens, orders and liquidity that repeatedly make solutions
+//! fail.
+//!
+//! The solver remembers which orders, liquidity and tokens each of its
+//! proposed solutions routed through, so that failure notifications from the
+//! driver can be attributed to them. The native token and its wrapped version
+//! are never blamed, as almost every route goes through them. Once one of them
+//! fails a configured number of consecutive times, it gets excluded from
+//! auctions for a back-off period that doubles with every further failure. A
+//! successful settlement resets the statistics of everything it touched.
+//!
+//! Backing off is disabled unless configured.
+
+use {
+    crate::domain::{auction, eth, liquidity, notification, order, solution},
+    std::{
+        collections::{HashMap, HashSet, VecDeque},
+        hash::Hash,
+        sync::Mutex,
+        time::{Duration, Instant},
+    },
+};
+
+/// The number of most recent auctions for which solution footprints are kept
+/// in order to attribute notifications to them.
+const RECENT_AUCTIONS: usize = 100;
+
+/// The address used to represent the native token.
+const NATIVE_TOKEN: eth::H160 = eth::H160([0xee; 20]);
+
+pub struct Config {
+    /// The number of consecutive failures after which to start backing off.
+    pub threshold: u32,
+    /// The initial back-off period.
+    pub period: Duration,
+    /// The maximum back-off period.
+    pub max_period: Duration,
+}
+
+pub struct Backoff {
+    /// The solver never backs off if unset.
+    config: Option<Config>,
+    weth: eth::WethAddress,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    auctions: VecDeque<i64>,
+    footprints: HashMap<(i64, u64), Footprint>,
+    tokens: Statistics<eth::TokenAddress>,
+    orders: Statistics<order::Uid>,
+    liquidity: Statistics<liquidity::Id>,
+}
+
+/// The orders, liquidity and tokens a proposed solution routed through.
+#[derive(Default)]
+struct Footprint {
+    tokens: HashSet<eth::TokenAddress>,
+    orders: HashSet<order::Uid>,
+    liquidity: HashSet<liquidity::Id>,
+}
+
+impl Backoff {
+    pub fn new(config: Option<Config>, weth: eth::WethAddress) -> Self {
+        Self {
+            config,
+            weth,
+            state: Default::default(),
+        }
+    }
+
+    /// Removes the orders and liquidity from the auction that the solver is
+    /// currently backing off from, either directly or because of one of their
+    /// tokens.
+    pub fn filter(&self, auction: &mut auction::Auction) {
+        if self.config.is_none() {
+            return;
+        }
+        let now = Instant::now();
+        let state = self.state.lock().unwrap();
+
+        let orders = auction.orders.len();
+        auction.orders.retain(|order| {
+            !state.orders.is_backing_off(&order.uid, now)
+                && !state.tokens.is_backing_off(&order.sell.token, now)
+                && !state.tokens.is_backing_off(&order.buy.token, now)
+        });
+        let liquidity = auction.liquidity.len();
+        auction.liquidity.retain(|liquidity| {
+            !state.liquidity.is_backing_off(&liquidity.id, now)
+                && !liquidity
+                    .tokens()
+                    .iter()
+                    .any(|token| state.tokens.is_backing_off(token, now))
+        });
+
+        let (orders, liquidity) = (
+            orders - auction.orders.len(),
+            liquidity - auction.liquidity.len(),
+        );
+        if orders > 0 || liquidity > 0 {
+            tracing::debug!(
+                orders,
+                liquidity,
+                "backing off from failing orders and liquidity"
+            );
+        }
+    }
+
+    /// Records the footprints of the solutions proposed for an auction, so that
+    /// later notifications about them can be attributed.
+    pub fn record(&self, auction: auction::Id, solutions: &[solution::Solution]) {
+        let Some(config) = &self.config else {
+            return;
+        };
+        // Quotes never get settled, so there won't be any notifications about
+        // them.
+        let auction::Id::Solve(auction) = auction else {
+            return;
+        };
+
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+
+        for solution in solutions {
+            state.footprints.insert(
+                (auction, solution.id.0),
+                Footprint::new(solution, self.weth),
+            );
+        }
+        state.auctions.push_back(auction);
+        if state.auctions.len() > RECENT_AUCTIONS {
+            if let Some(oldest) = state.auctions.pop_front() {
+                state.footprints.retain(|(id, _), _| *id != oldest);
+            }
+        }
+
+        let stale = config.max_period;
+        state.tokens.prune(now, stale);
+        state.orders.prune(now, stale);
+        state.liquidity.prune(now, stale);
+    }
+
+    /// Updates the failure statistics based on a notification from the driver.
+    pub fn notify(&self, notification: &notification::Notification) {
+        let Some(config) = &self.config else {
+            return;
+        };
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        let footprint = state.footprint(notification);
+
+        match &notification.kind {
+            // A simulation that succeeded at least once most likely failed
+            // because of changing on-chain state and not because of anything
+            // the solution touched.
+            notification::Kind::SimulationFailed(_, _, false)
+            | notification::Kind::Settled(
+                notification::Settlement::Revert(_) | notification::Settlement::SimulationRevert,
+            ) => {
+                state.tokens.fail(config, footprint.tokens, now);
+                state.orders.fail(config, footprint.orders, now);
+                state.liquidity.fail(config, footprint.liquidity, now);
+            }
+            notification::Kind::NonBufferableTokensUsed(tokens) => {
+                let tokens = tokens.iter().copied();
+                let tokens = tokens.filter(|token| !is_native(*token, self.weth));
+                state.tokens.fail(config, tokens, now);
+            }
+            notification::Kind::ScoringFailed(notification::ScoreKind::MissingPrice(token)) => {
+                if !is_native(*token, self.weth) {
+                    state.tokens.fail(config, [*token], now);
+                }
+            }
+            notification::Kind::Settled(notification::Settlement::Success(_)) => {
+                state.tokens.succeed(footprint.tokens);
+                state.orders.succeed(footprint.orders);
+                state.liquidity.succeed(footprint.liquidity);
+            }
+            _ => (),
+        }
+    }
+}
+
+impl State {
+    /// Returns the combined footprint of all solutions a notification is
+    /// about.
+    fn footprint(&self, notification: &notification::Notification) -> Footprint {
+        let mut footprint = Footprint::default();
+        let auction::Id::Solve(auction) = notification.auction_id else {
+            return footprint;
+        };
+        let ids = match &notification.solution_id {
+            Some(notification::Id::Single(id)) => vec![id.0],
+            Some(notification::Id::Merged(ids)) => ids.clone(),
+            None => Vec::new(),
+        };
+
+        for id in ids {
+            if let Some(solution) = self.footprints.get(&(auction, id)) {
+                footprint.tokens.extend(solution.tokens.iter().copied());
+                footprint.orders.extend(solution.orders.iter().copied());
+                footprint
+                    .liquidity
+                    .extend(solution.liquidity.iter().cloned());
+            }
+        }
+        footprint
+    }
+}
+
+impl Footprint {
+    fn new(solution: &solution::Solution, weth: eth::WethAddress) -> Self {
+        let mut footprint = Self::default();
+        for trade in &solution.trades {
+            if let solution::Trade::Fulfillment(fulfillment) = trade {
+                footprint.orders.insert(fulfillment.order().uid);
+            }
+        }
+        for interaction in &solution.interactions {
+            if let solution::Interaction::Liquidity(interaction) = interaction {
+                footprint.liquidity.insert(interaction.liquidity.id.clone());
+                footprint.tokens.extend(
+                    [interaction.input.token, interaction.output.token]
+                        .into_iter()
+                        .filter(|token| !is_native(*token, weth)),
+                );
+            }
+        }
+        footprint
+    }
+}
+
+/// Whether the token is the native token or its wrapped version.
+fn is_native(token: eth::TokenAddress, weth: eth::WethAddress) -> bool {
+    token.0 == NATIVE_TOKEN || token.0 == weth.0
+}
+
+/// Failure statistics for a kind of entity.
+struct Statistics<K>(HashMap<K, Failures>);
+
+struct Failures {
+    consecutive: u32,
+    last: Instant,
+    until: Option<Instant>,
+}
+
+impl<K> Default for Statistics<K> {
+    fn default() -> Self {
+        Self(HashMap::new())
+    }
+}
+
+impl<K: Eq + Hash> Statistics<K> {
+    fn is_backing_off(&self, key: &K, now: Instant) -> bool {
+        self.0
+            .get(key)
+            .and_then(|failures| failures.until)
+            .is_some_and(|until| until > now)
+    }
+
+    fn fail(&mut self, config: &Config, keys: impl IntoIterator<Item = K>, now: Instant) {
+        for key in keys {
+            let failures = self.0.entry(key).or_insert(Failures {
+                consecutive: 0,
+                last: now,
+                until: None,
+            });
+            failures.consecutive += 1;
+            failures.last = now;
+
+            let Some(exponent) = failures.consecutive.checked_sub(config.threshold) else {
+                continue;
+            };
+            let period = config
+                .period
+                .saturating_mul(2_u32.saturating_pow(exponent))
+                .min(config.max_period);
+            failures.until = Some(now + period);
+        }
+    }
+
+    fn succeed(&mut self, keys: impl IntoIterator<Item = K>) {
+        for key in keys {
+            self.0.remove(&key);
+        }
+    }
+
+    /// Forgets about entities that did not fail in a while and that the solver
+    /// is not currently backing off from.
+    fn prune(&mut self, now: Instant, stale: Duration) {
+        self.0.retain(|_, failures| {
+            failures.until.is_some_and(|until| until > now) || now - failures.last < stale
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::domain::liquidity::{constant_product, State},
+    };
+
+    const WETH: eth::WethAddress = eth::WethAddress(eth::H160([0xaa; 20]));
+    const COW: eth::TokenAddress = eth::TokenAddress(eth::H160([0xcc; 20]));
+    const DAI: eth::TokenAddress = eth::TokenAddress(eth::H160([0xdd; 20]));
+    const USDC: eth::TokenAddress = eth::TokenAddress(eth::H160([0x11; 20]));
+
+    fn config() -> Config {
+        Config {
+            threshold: 1,
+            period: Duration::from_secs(60),
+            max_period: Duration::from_secs(60),
+        }
+    }
+
+    fn swap(
+        id: &str,
+        input: eth::TokenAddress,
+        output: eth::TokenAddress,
+    ) -> solution::Interaction {
+        let asset = |token| eth::Asset {
+            amount: 1_000_000.into(),
+            token,
+        };
+        solution::Interaction::Liquidity(solution::LiquidityInteraction {
+            liquidity: liquidity::Liquidity {
+                id: liquidity::Id(id.to_owned()),
+                address: eth::H160::from_low_u64_be(id.parse().unwrap()),
+                gas: eth::Gas(100_000.into()),
+                state: State::ConstantProduct(constant_product::Pool {
+                    reserves: constant_product::Reserves::new(asset(input), asset(output)).unwrap(),
+                    fee: eth::Rational::new_raw(3.into(), 1000.into()),
+                }),
+            },
+            input: asset(input),
+            output: asset(output),
+            internalize: false,
+        })
+    }
+
+    /// A solution routing COW over WETH to DAI, which also has a clearing
+    /// price for an unrelated token.
+    fn solution() -> solution::Solution {
+        solution::Solution {
+            prices: solution::ClearingPrices::new([
+                (COW, 1.into()),
+                (DAI, 1.into()),
+                (eth::TokenAddress(WETH.0), 1.into()),
+                (USDC, 1.into()),
+            ]),
+            interactions: vec![
+                swap("1", COW, eth::TokenAddress(WETH.0)),
+                swap("2", eth::TokenAddress(WETH.0), DAI),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn revert() -> notification::Notification {
+        notification::Notification {
+            auction_id: auction::Id::Solve(1),
+            solution_id: Some(notification::Id::Single(solution::Id(0))),
+            kind: notification::Kind::Settled(notification::Settlement::Revert(eth::H256::zero())),
+        }
+    }
+
+    #[test]
+    fn footprint_only_covers_route() {
+        let footprint = Footprint::new(&solution(), WETH);
+
+        assert_eq!(footprint.tokens, HashSet::from([COW, DAI]));
+        assert_eq!(
+            footprint.liquidity,
+            HashSet::from([liquidity::Id("1".to_owned()), liquidity::Id("2".to_owned())])
+        );
+    }
+
+    #[test]
+    fn reverts_blame_route() {
+        let backoff = Backoff::new(Some(config()), WETH);
+        backoff.record(auction::Id::Solve(1), &[solution()]);
+        backoff.notify(&revert());
+
+        let now = Instant::now();
+        let state = backoff.state.lock().unwrap();
+        assert!(state.tokens.is_backing_off(&COW, now));
+        assert!(state.tokens.is_backing_off(&DAI, now));
+        assert!(!state.tokens.is_backing_off(&USDC, now));
+        assert!(!state.tokens.is_backing_off(&eth::TokenAddress(WETH.0), now));
+        assert!(state
+            .liquidity
+            .is_backing_off(&liquidity::Id("1".to_owned()), now));
+    }
+
+    #[test]
+    fn never_blames_native_tokens() {
+        let backoff = Backoff::new(Some(config()), WETH);
+        backoff.notify(&notification::Notification {
+            auction_id: auction::Id::Solve(1),
+            solution_id: None,
+            kind: notification::Kind::NonBufferableTokensUsed(
+                [
+                    COW,
+                    eth::TokenAddress(WETH.0),
+                    eth::TokenAddress(NATIVE_TOKEN),
+                ]
+                .into(),
+            ),
+        });
+
+        let now = Instant::now();
+        let state = backoff.state.lock().unwrap();
+        assert!(state.tokens.is_backing_off(&COW, now));
+        assert!(!state.tokens.is_backing_off(&eth::TokenAddress(WETH.0), now));
+        assert!(!state
+            .tokens
+            .is_backing_off(&eth::TokenAddress(NATIVE_TOKEN), now));
+    }
+
+    #[test]
+    fn disabled_without_config() {
+        let backoff = Backoff::new(None, WETH);
+        backoff.record(auction::Id::Solve(1), &[solution()]);
+        backoff.notify(&revert());
+
+        let state = backoff.state.lock().unwrap();
+        assert!(state.footprints.is_empty());
+        assert!(state.tokens.0.is_empty());
+        assert!(state.liquidity.0.is_empty());
+    }
+
+    #[test]
+    fn backs_off_exponentially() {
+        let config = Config {
+            threshold: 2,
+            period: Duration::from_secs(60),
+            max_period: Duration::from_secs(180),
+        };
+        let mut statistics = Statistics::default();
+        let now = Instant::now();
+        let later = |secs| now + Duration::from_secs(secs);
+
+        statistics.fail(&config, ["pool"], now);
+        assert!(!statistics.is_backing_off(&"pool", now));
+
+        statistics.fail(&config, ["pool"], now);
+        assert!(statistics.is_backing_off(&"pool", later(59)));
+        assert!(!statistics.is_backing_off(&"pool", later(60)));
+
+        statistics.fail(&config, ["pool"], now);
+        assert!(statistics.is_backing_off(&"pool", later(119)));
+        assert!(!statistics.is_backing_off(&"pool", later(120)));
+
+        statistics.fail(&config, ["pool"], now);
+        assert!(statistics.is_backing_off(&"pool", later(179)));
+        assert!(!statistics.is_backing_off(&"pool", later(180)));
+
+        statistics.succeed(["pool"]);
+        assert!(!statistics.is_backing_off(&"pool", now));
+    }
+}
@@ -0,0 +1,765 @@
+//! "Baseline" solver implementation.
+//!
+//! The baseline solver is a simple solver implementation that finds the best
+//! path of at most length `max_hops + 1` over a set of on-chain liquidity.
+//! Large orders can also be split into multiple parts and routed over
+//! separate paths, see [`split`] for more details.
+//!
+//! Optionally, the solver can first match orders against each other in
+//! coincidences of wants (CoWs), see [`cow`] for more details. It can also
+//! merge the solutions for individual orders into solutions settling multiple
+//! orders, see [`merge`] for more details.
+//!
+//! Quote requests are handled by a dedicated fast-path, see [`quote`] for more
+//! details.
+
+use {
+    crate::{
+        boundary,
+        domain::{
+            auction,
+            eth,
+            liquidity,
+            order::{self, Order},
+            solution,
+        },
+    },
+    ethereum_types::U256,
+    std::{cmp, collections::HashSet, sync::Arc, time::Duration},
+};
+
+pub(super) mod cow;
+mod merge;
+mod quote;
+mod split;
+
+pub struct Baseline(Arc<Inner>);
+
+/// The number of binary search steps for finding the largest executable amount
+/// of a partially fillable order.
+const PARTIAL_FILL_SEARCH_STEPS: usize = 16;
+
+/// Gas needed for every trade in a settlement in addition to the first one,
+/// which is already accounted for in the solution gas offset.
+const ADDITIONAL_TRADE_GAS: u64 = solution::TRADE + 2 * solution::ERC20_TRANSFER;
+
+pub struct Config {
+    pub weth: eth::WethAddress,
+    pub base_tokens: Vec<eth::TokenAddress>,
+    pub max_hops: usize,
+    pub max_partial_attempts: usize,
+    pub solution_gas_offset: eth::SignedGas,
+    pub native_token_price_estimation_amount: eth::U256,
+    pub cow_matching: bool,
+    pub max_splits: usize,
+    pub merge_solutions: bool,
+    pub quote_path_ttl: Duration,
+}
+
+struct Inner {
+    weth: eth::WethAddress,
+
+    /// Set of tokens to additionally consider as intermediary hops when
+    /// path-finding. This allows paths of the kind `TOKEN1 -> WETH -> TOKEN2`
+    /// to be considered.
+    base_tokens: HashSet<eth::TokenAddress>,
+
+    /// Maximum number of hops that can be considered in a trading path. A hop
+    /// is an intermediary token within a trading path. For example:
+    /// - A value of 0 indicates that only a direct trade is allowed: `A -> B`
+    /// - A value of 1 indicates that a single intermediary token can appear
+    ///   within a trading path: `A -> B -> C`
+    /// - A value of 2 indicates: `A -> B -> C -> D`
+    /// - etc.
+    max_hops: usize,
+
+    /// The maximum number of attempts to solve a partially fillable order.
+    /// Basically we continuously halve the amount to execute until we find a
+    /// valid solution or exceed this count. The executed amount is then
+    /// refined with a binary search, see [`Inner::solve_order`].
+    max_partial_attempts: usize,
+
+    /// Units of gas that get added to the gas estimate for executing a
+    /// computed trade route to arrive at a gas estimate for a whole settlement.
+    solution_gas_offset: eth::SignedGas,
+
+    /// The amount of the native token to use to estimate native price of a
+    /// token
+    native_token_price_estimation_amount: eth::U256,
+
+    /// Whether to match orders against each other in CoWs before routing the
+    /// remaining orders individually over on-chain liquidity.
+    cow_matching: bool,
+
+    /// The maximum number of disjoint paths to split a single order across. A
+    /// value of 1 means orders are always routed over a single path.
+    max_splits: usize,
+
+    /// Whether to merge the solutions for individual orders into solutions
+    /// settling multiple orders at once.
+    merge_solutions: bool,
+
+    /// Liquidity paths memoised for quotes.
+    quote_paths: quote::Paths,
+}
+
+impl Baseline {
+    /// Creates a new baseline solver for the specified configuration.
+    pub fn new(config: Config) -> Self {
+        Self(Arc::new(Inner {
+            weth: config.weth,
+            base_tokens: config.base_tokens.into_iter().collect(),
+            max_hops: config.max_hops,
+            max_partial_attempts: config.max_partial_attempts,
+            solution_gas_offset: config.solution_gas_offset,
+            native_token_price_estimation_amount: config.native_token_price_estimation_amount,
+            cow_matching: config.cow_matching,
+            max_splits: config.max_splits,
+            merge_solutions: config.merge_solutions,
+            quote_paths: quote::Paths::new(config.quote_path_ttl),
+        }))
+    }
+
+    /// Quotes the single order of a quote auction.
+    async fn quote(&self, auction: auction::Auction) -> Vec<solution::Solution> {
+        let remaining = auction
+            .deadline
+            .clone()
+            .reduce(super::DEADLINE_SLACK)
+            .remaining()
+            .unwrap_or_default();
+
+        let inner = self.0.clone();
+        let span = tracing::Span::current();
+        let background_work = tokio::task::spawn_blocking(move || {
+            let _entered = span.enter();
+            inner.quote(&auction)
+        });
+
+        match tokio::time::timeout(remaining, background_work).await {
+            Ok(Ok(solution)) => solution.into_iter().collect(),
+            Ok(Err(err)) => {
+                tracing::warn!(?err, "failed to quote order");
+                Vec::new()
+            }
+            Err(_) => {
+                tracing::debug!("reached timeout while quoting order");
+                Vec::new()
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl super::Engine for Baseline {
+    fn weth(&self) -> eth::WethAddress {
+        self.0.weth
+    }
+
+    /// Solves the specified auction, returning a vector of all possible
+    /// solutions.
+    async fn solve(&self, auction: auction::Auction) -> Vec<solution::Solution> {
+        if let (auction::Id::Quote, [_]) = (auction.id, auction.orders.as_slice()) {
+            return self.quote(auction).await;
+        }
+
+        // Make sure to push the CPU-heavy code to a separate thread in order to
+        // not lock up the [`tokio`] runtime and cause it to slow down handling
+        // the real async things. For larger settlements, this can block in the
+        // 100s of ms.
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let remaining = auction
+            .deadline
+            .clone()
+            .reduce(super::DEADLINE_SLACK)
+            .remaining()
+            .unwrap_or_default();
+
+        let inner = self.0.clone();
+        let span = tracing::Span::current();
+        let background_work = async move {
+            let _entered = span.enter();
+            inner.solve(auction, sender);
+        };
+
+        if tokio::time::timeout(remaining, tokio::spawn(background_work))
+            .await
+            .is_err()
+        {
+            tracing::debug!("reached timeout while solving orders");
+        }
+
+        let mut solutions = vec![];
+        while let Ok(solution) = receiver.try_recv() {
+            solutions.push(solution);
+        }
+        solutions
+    }
+}
+
+impl Inner {
+    fn solve(
+        &self,
+        auction: auction::Auction,
+        sender: tokio::sync::mpsc::UnboundedSender<solution::Solution>,
+    ) {
+        let timestamp = auction.deadline.timestamp();
+        let boundary_solver = boundary::baseline::Solver::new(
+            &self.weth,
+            &self.base_tokens,
+            &auction.liquidity,
+            timestamp,
+        )
+        .with_gas_price(auction.gas_price, &auction.tokens);
+
+        let mut matched = HashSet::new();
+        if self.cow_matching {
+            let matcher = cow::Matcher {
+                solver: &boundary_solver,
+                max_hops: self.max_hops,
+                tokens: &auction.tokens,
+                gas_price: auction.gas_price,
+                solution_gas_offset: self.solution_gas_offset,
+            };
+            for cow in matcher.solve(&auction.orders) {
+                // Use the index of the first matched order as the solution ID,
+                // this keeps IDs unique as every order is in at most one
+                // solution.
+                let id = solution::Id(cow.orders[0] as u64);
+                matched.extend(cow.orders);
+                if sender.send(cow.solution.with_id(id)).is_err() {
+                    tracing::debug!("deadline hit, receiver dropped");
+                    return;
+                }
+            }
+        }
+
+        // When merging solutions, stop solving orders a bit earlier so that
+        // there is enough time left to send the merged solutions.
+        let merge_deadline = auction.deadline.clone().reduce(super::DEADLINE_SLACK * 2);
+        let mut merger = self
+            .merge_solutions
+            .then(|| merge::Merger::new(&auction.liquidity, self.solution_gas_offset));
+
+        for (i, order) in auction.orders.into_iter().enumerate() {
+            if matched.contains(&i) {
+                continue;
+            }
+            let Some(sell_token_price) =
+                self.sell_token_price(&order, &auction.tokens, &boundary_solver)
+            else {
+                continue;
+            };
+
+            match &mut merger {
+                Some(merger) => {
+                    if merge_deadline.remaining().is_none() {
+                        tracing::debug!("deadline hit, stopped merging solutions");
+                        break;
+                    }
+                    merger.insert(|liquidity, gas_offset| {
+                        let boundary_solver = boundary::baseline::Solver::new(
+                            &self.weth,
+                            &self.base_tokens,
+                            liquidity,
+                            timestamp,
+                        )
+                        .with_gas_price(auction.gas_price, &auction.tokens);
+                        self.solve_order(
+                            i,
+                            &order,
+                            &boundary_solver,
+                            sell_token_price,
+                            auction.gas_price,
+                            gas_offset,
+                        )
+                    });
+                }
+                None => {
+                    let Some(solution) = self.solve_order(
+                        i,
+                        &order,
+                        &boundary_solver,
+                        sell_token_price,
+                        auction.gas_price,
+                        self.solution_gas_offset,
+                    ) else {
+                        continue;
+                    };
+                    if sender
+                        .send(solution.with_buffers_internalizations(&auction.tokens))
+                        .is_err()
+                    {
+                        tracing::debug!("deadline hit, receiver dropped");
+                        break;
+                    }
+                }
+            }
+        }
+
+        for solution in merger.into_iter().flat_map(merge::Merger::into_solutions) {
+            if sender
+                .send(solution.with_buffers_internalizations(&auction.tokens))
+                .is_err()
+            {
+                tracing::debug!("deadline hit, receiver dropped");
+                break;
+            }
+        }
+    }
+
+    /// Quotes the single order of a quote auction. The order is never
+    /// partially filled, and its route is found along the memoised path for
+    /// its token pair when possible.
+    fn quote(&self, auction: &auction::Auction) -> Option<solution::Solution> {
+        let order = auction.orders.first()?;
+        let boundary_solver = boundary::baseline::Solver::new(
+            &self.weth,
+            &self.base_tokens,
+            &auction.liquidity,
+            auction.deadline.timestamp(),
+        )
+        .with_gas_price(auction.gas_price, &auction.tokens);
+
+        // Market orders are charged a protocol computed fee, so there is no
+        // need to estimate the sell token price for computing one.
+        let sell_token_price = if order.solver_determines_fee() {
+            self.sell_token_price(order, &auction.tokens, &boundary_solver)?
+        } else {
+            auction::Price(eth::Ether(eth::U256::MAX))
+        };
+
+        let request = request_for_order(order, full_amount(order))?;
+        let memoised = self
+            .quote_paths
+            .get(&request)
+            .and_then(|hops| boundary_solver.route_along(&hops, request));
+        let routes = match memoised {
+            Some(route) => vec![route],
+            None => {
+                let routes = self.routes(&boundary_solver, request)?;
+                if let [route] = routes.as_slice() {
+                    self.quote_paths.insert(&request, route.hops());
+                }
+                routes
+            }
+        };
+
+        let solution = self.solve_routes(
+            order,
+            routes,
+            sell_token_price,
+            auction.gas_price,
+            self.solution_gas_offset,
+        )?;
+        Some(solution.with_buffers_internalizations(&auction.tokens))
+    }
+
+    /// Returns the price of the order's sell token in the native token, used
+    /// for computing solver fees. Returns `None` if the estimated price is
+    /// invalid.
+    fn sell_token_price(
+        &self,
+        order: &Order,
+        tokens: &auction::Tokens,
+        boundary_solver: &boundary::baseline::Solver,
+    ) -> Option<auction::Price> {
+        let sell_token = order.sell.token;
+        match tokens.reference_price(&sell_token) {
+            Some(price) => Some(price),
+            None if sell_token == self.weth.0.into() => {
+                // Early return if the sell token is native token
+                Some(auction::Price(eth::Ether(eth::U256::exp10(18))))
+            }
+            None => {
+                // Estimate the price of the sell token in the native token
+                let native_price_request = self.native_price_request(order);
+                if let Some(route) = boundary_solver.route(native_price_request, self.max_hops) {
+                    // how many units of buy_token are bought for one unit of sell_token
+                    // (buy_amount / sell_amount).
+                    let price = self.native_token_price_estimation_amount.to_f64_lossy()
+                        / route.input().amount.to_f64_lossy();
+                    let price = to_normalized_price(price)?;
+
+                    Some(auction::Price(eth::Ether(price)))
+                } else {
+                    // This is to allow quotes to be generated for tokens for which the sell
+                    // token price is not available, so we default to fee=0
+                    Some(auction::Price(eth::Ether(eth::U256::MAX)))
+                }
+            }
+        }
+    }
+
+    /// Solves a single order, returning a solution settling only that order.
+    /// Note that the solution's interactions are not yet internalized. The gas
+    /// offset gets added to the gas of the order's routes, and is charged to
+    /// the order if it pays a solver determined fee.
+    ///
+    /// Partially fillable orders that can't be filled completely are filled
+    /// for the largest amount that can be executed within the order's limit
+    /// price. We first halve the amount to execute until we find a valid
+    /// solution, and then binary search between that amount and the last
+    /// invalid one.
+    fn solve_order(
+        &self,
+        i: usize,
+        order: &Order,
+        boundary_solver: &boundary::baseline::Solver,
+        sell_token_price: auction::Price,
+        gas_price: auction::GasPrice,
+        gas_offset: eth::SignedGas,
+    ) -> Option<solution::Solution> {
+        let solve = |amount: U256| {
+            let request = request_for_order(order, amount)?;
+            let solution = self.solve_request(
+                order,
+                request,
+                boundary_solver,
+                sell_token_price,
+                gas_price,
+                gas_offset,
+            )?;
+            Some(solution.with_id(solution::Id(i as u64)))
+        };
+
+        let full = full_amount(order);
+        if !order.partially_fillable {
+            return solve(full);
+        }
+
+        let (mut valid, mut invalid, mut solution) = (0..self.max_partial_attempts)
+            .map(|i| (full >> i, full >> i.saturating_sub(1)))
+            .take_while(|(amount, _)| !amount.is_zero())
+            .find_map(|(amount, previous)| Some((amount, previous, solve(amount)?)))?;
+        if valid == full {
+            return Some(solution);
+        }
+
+        for _ in 0..PARTIAL_FILL_SEARCH_STEPS {
+            let amount = valid + (invalid - valid) / U256::from(2);
+            if amount == valid {
+                break;
+            }
+            match solve(amount) {
+                Some(better) => (valid, solution) = (amount, better),
+                None => invalid = amount,
+            }
+        }
+        Some(solution)
+    }
+
+    /// Solves a routing request for an order.
+    fn solve_request(
+        &self,
+        order: &Order,
+        request: Request,
+        boundary_solver: &boundary::baseline::Solver,
+        sell_token_price: auction::Price,
+        gas_price: auction::GasPrice,
+        gas_offset: eth::SignedGas,
+    ) -> Option<solution::Solution> {
+        tracing::trace!(order =% order.uid, ?request, "finding route");
+
+        let routes = self.routes(boundary_solver, request)?;
+        self.solve_routes(order, routes, sell_token_price, gas_price, gas_offset)
+    }
+
+    /// Computes the solution for executing an order over the specified routes.
+    fn solve_routes(
+        &self,
+        order: &Order,
+        routes: Vec<Route>,
+        sell_token_price: auction::Price,
+        gas_price: auction::GasPrice,
+        gas_offset: eth::SignedGas,
+    ) -> Option<solution::Solution> {
+        let interactions = routes.iter().flat_map(Route::interactions).collect();
+
+        // The baseline solver generates a path with swapping
+        // for exact output token amounts. This leads to
+        // potential rounding errors for buy orders, where we
+        // can buy slightly more than intended. Fix this by
+        // capping the output amount to the order's buy amount
+        // for buy orders.
+        let mut output = split::total(routes.iter().map(Route::output));
+        if let order::Side::Buy = order.side {
+            output.amount = cmp::min(output.amount, order.buy.amount);
+        }
+
+        let gas = eth::Gas(
+            routes
+                .iter()
+                .fold(U256::zero(), |acc, route| acc.saturating_add(route.gas().0)),
+        ) + gas_offset;
+        let fee = sell_token_price
+            .ether_value(eth::Ether(gas.0.checked_mul(gas_price.0 .0)?))?
+            .into();
+
+        solution::Single {
+            order: order.clone(),
+            input: split::total(routes.iter().map(Route::input)),
+            output,
+            interactions,
+            gas,
+        }
+        .into_solution(fee)
+    }
+
+    /// Finds the routes for executing a request. This is either a single
+    /// route, or multiple disjoint routes when splitting the request improves
+    /// its execution.
+    fn routes<'a>(
+        &self,
+        boundary_solver: &'a boundary::baseline::Solver<'a>,
+        request: Request,
+    ) -> Option<Vec<Route<'a>>> {
+        let route = boundary_solver.route(request, self.max_hops);
+        if self.max_splits <= 1 {
+            return Some(vec![route?]);
+        }
+
+        let splitter = split::Splitter {
+            solver: boundary_solver,
+            max_hops: self.max_hops,
+            max_splits: self.max_splits,
+        };
+        let Some(routes) = splitter.split(request) else {
+            return Some(vec![route?]);
+        };
+        // Every additional route costs additional gas, so the split is only
+        // used if it is better than the single route net of gas.
+        let gas = |routes: &[Route]| {
+            eth::Gas(
+                routes
+                    .iter()
+                    .fold(U256::zero(), |acc, route| acc.saturating_add(route.gas().0)),
+            )
+        };
+        let improves = match &route {
+            None => true,
+            Some(route) => match request.side {
+                order::Side::Sell => {
+                    let net = |routes: &[Route]| {
+                        split::total(routes.iter().map(Route::output))
+                            .amount
+                            .saturating_sub(
+                                boundary_solver.gas_cost(gas(routes), request.buy.token),
+                            )
+                    };
+                    net(&routes) > net(std::slice::from_ref(route))
+                }
+                order::Side::Buy => {
+                    let net = |routes: &[Route]| {
+                        split::total(routes.iter().map(Route::input))
+                            .amount
+                            .saturating_add(
+                                boundary_solver.gas_cost(gas(routes), request.sell.token),
+                            )
+                    };
+                    net(&routes) < net(std::slice::from_ref(route))
+                }
+            },
+        };
+        if improves {
+            Some(routes)
+        } else {
+            Some(vec![route?])
+        }
+    }
+
+    fn native_price_request(&self, order: &Order) -> Request {
+        let sell = eth::Asset {
+            token: order.sell.token,
+            // Note that we intentionally do not use [`eth::U256::max_value()`]
+            // as an order with this would cause overflows with the smart
+            // contract, so buy orders requiring excessively large sell amounts
+            // would not work anyway. Instead we use `2 ** 144`, the rationale
+            // being that Uniswap V2 pool reserves are 112-bit integers. Noting
+            // that `256 - 112 = 144`, this means that we can use it to trade a full
+            // `type(uint112).max` without overflowing a `uint256` on the smart
+            // contract level. Requiring to trade more than `type(uint112).max`
+            // is unlikely and would not work with Uniswap V2 anyway.
+            amount: eth::U256::one() << 144,
+        };
+
+        let buy = eth::Asset {
+            token: self.weth.0.into(),
+            amount: self.native_token_price_estimation_amount,
+        };
+
+        Request {
+            sell,
+            buy,
+            side: order::Side::Buy,
+        }
+    }
+}
+
+/// Returns the full amount of an order, denominated in its sell token for sell
+/// orders and in its buy token for buy orders.
+fn full_amount(order: &Order) -> U256 {
+    match order.side {
+        order::Side::Sell => order.sell.amount,
+        order::Side::Buy => order.buy.amount,
+    }
+}
+
+/// Returns a request for executing the specified amount of an order, where the
+/// amount is denominated in the order's sell token for sell orders and in its
+/// buy token for buy orders. The other side of the request is computed such
+/// that the order's limit price is respected.
+fn request_for_order(order: &Order, amount: U256) -> Option<Request> {
+    let (sell, buy) = match order.side {
+        order::Side::Sell => (
+            amount,
+            mul_div(order.buy.amount, amount, order.sell.amount, Rounding::Up)?,
+        ),
+        order::Side::Buy => (
+            mul_div(order.sell.amount, amount, order.buy.amount, Rounding::Down)?,
+            amount,
+        ),
+    };
+    if sell.is_zero() || buy.is_zero() {
+        return None;
+    }
+
+    Some(Request {
+        sell: eth::Asset {
+            token: order.sell.token,
+            amount: sell,
+        },
+        buy: eth::Asset {
+            token: order.buy.token,
+            amount: buy,
+        },
+        side: order.side,
+    })
+}
+
+/// The rounding direction of [`mul_div`].
+enum Rounding {
+    Down,
+    Up,
+}
+
+/// Computes `a * b / denominator` without intermediate overflows. Returns
+/// `None` when dividing by zero or if the result does not fit in 256 bits.
+fn mul_div(a: U256, b: U256, denominator: U256, rounding: Rounding) -> Option<U256> {
+    if denominator.is_zero() {
+        return None;
+    }
+    let (quotient, remainder) = a.full_mul(b).div_mod(denominator.into());
+    let quotient = U256::try_from(quotient).ok()?;
+    match rounding {
+        Rounding::Up if !remainder.is_zero() => quotient.checked_add(U256::one()),
+        _ => Some(quotient),
+    }
+}
+
+fn to_normalized_price(price: f64) -> Option<U256> {
+    let uint_max = 2.0_f64.powi(256);
+
+    let price_in_eth = 1e18 * price;
+    if price_in_eth.is_normal() && price_in_eth >= 1. && price_in_eth < uint_max {
+        Some(U256::from_f64_lossy(price_in_eth))
+    } else {
+        None
+    }
+}
+
+/// A baseline routing request.
+#[derive(Clone, Copy, Debug)]
+pub struct Request {
+    pub sell: eth::Asset,
+    pub buy: eth::Asset,
+    pub side: order::Side,
+}
+
+/// A trading route.
+#[derive(Debug)]
+pub struct Route<'a> {
+    segments: Vec<Segment<'a>>,
+}
+
+/// A hop in a trading route, identifying the liquidity and the tokens it
+/// trades without any amounts.
+#[derive(Clone, Debug)]
+pub struct Hop {
+    pub liquidity: liquidity::Id,
+    pub input: eth::TokenAddress,
+    pub output: eth::TokenAddress,
+}
+
+/// A segment in a trading route.
+#[derive(Debug)]
+pub struct Segment<'a> {
+    pub liquidity: &'a liquidity::Liquidity,
+    // TODO: There is no type-level guarantee here that both `input.token` and
+    // `output.token` are valid for the liquidity in this segment. This is
+    // unfortunate because this type leaks out of this module (currently into
+    // the `boundary::baseline` module) but should no longer need to be `pub`
+    // once the `boundary::baseline` module gets refactored into the domain
+    // logic, so I think it is fine for now.
+    pub input: eth::Asset,
+    pub output: eth::Asset,
+    pub gas: eth::Gas,
+}
+
+impl<'a> Route<'a> {
+    pub fn new(segments: Vec<Segment<'a>>) -> Option<Self> {
+        if segments.is_empty() {
+            return None;
+        }
+        Some(Self { segments })
+    }
+
+    pub fn segments(&self) -> &[Segment<'a>] {
+        &self.segments
+    }
+
+    /// Returns the hops of the route, without any amounts.
+    pub fn hops(&self) -> Vec<Hop> {
+        self.segments
+            .iter()
+            .map(|segment| Hop {
+                liquidity: segment.liquidity.id.clone(),
+                input: segment.input.token,
+                output: segment.output.token,
+            })
+            .collect()
+    }
+
+    fn input(&self) -> eth::Asset {
+        self.segments[0].input
+    }
+
+    fn output(&self) -> eth::Asset {
+        self.segments
+            .last()
+            .expect("route has at least one segment by construction")
+            .output
+    }
+
+    fn gas(&self) -> eth::Gas {
+        eth::Gas(self.segments.iter().fold(U256::zero(), |acc, segment| {
+            acc.saturating_add(segment.gas.0)
+        }))
+    }
+
+    /// Returns the liquidity interactions for executing the route.
+    fn interactions(&self) -> Vec<solution::Interaction> {
+        self.segments
+            .iter()
+            .map(|segment| {
+                solution::Interaction::Liquidity(solution::LiquidityInteraction {
+                    liquidity: segment.liquidity.clone(),
+                    input: segment.input,
+                    output: segment.output,
+                    // TODO does the baseline solver know about this optimization?
+                    internalize: false,
+                })
+            })
+            .collect()
+    }
+}
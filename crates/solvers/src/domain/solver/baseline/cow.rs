@@ -0,0 +1,394 @@
+//! Coincidence of wants (CoW) matching.
+//!
+//! Finds groups of orders in an auction that can be settled against each
+//! other at uniform clearing prices. Two kinds of CoWs are considered:
+//! - Direct CoWs between two orders trading opposite token pairs. The part of
+//!   the volume that can't be matched between the two orders gets routed over
+//!   on-chain liquidity.
+//! - Ring CoWs between three orders `A -> B -> C -> A`, where the orders'
+//!   volumes can be matched against each other without using any liquidity.
+//!
+//! Orders are always matched for their full amount, and each order appears in
+//! at most one CoW. Orders that don't get matched are left for the regular
+//! single order routing.
+
+use {
+    super::{Request, Route, ADDITIONAL_TRADE_GAS},
+    crate::{
+        boundary,
+        domain::{
+            auction,
+            eth,
+            order::{self, Order},
+            solution,
+        },
+        util,
+    },
+    ethereum_types::U256,
+    std::collections::HashMap,
+};
+
+/// Finds CoWs between orders of an auction.
+pub struct Matcher<'a> {
+    pub solver: &'a boundary::baseline::Solver<'a>,
+    pub max_hops: usize,
+    pub tokens: &'a auction::Tokens,
+    pub gas_price: auction::GasPrice,
+    pub solution_gas_offset: eth::SignedGas,
+}
+
+/// A group of orders that are settled together in a single solution.
+pub struct Match {
+    /// Indices of the matched orders in the auction.
+    pub orders: Vec<usize>,
+    pub solution: solution::Solution,
+}
+
+impl Matcher<'_> {
+    /// Finds CoWs between the specified orders. Direct CoWs are preferred over
+    /// rings, and orders earlier in the list get matched first.
+    pub fn solve(&self, orders: &[Order]) -> Vec<Match> {
+        let mut by_sell_token = HashMap::<eth::TokenAddress, Vec<usize>>::new();
+        for (i, order) in orders.iter().enumerate() {
+            by_sell_token.entry(order.sell.token).or_default().push(i);
+        }
+        let by_sell_token = &by_sell_token;
+        let selling = move |token: eth::TokenAddress| {
+            by_sell_token.get(&token).into_iter().flatten().copied()
+        };
+
+        let mut matched = vec![false; orders.len()];
+        let mut matches = Vec::new();
+
+        for i in 0..orders.len() {
+            if matched[i] {
+                continue;
+            }
+            let direct = selling(orders[i].buy.token)
+                .filter(|j| !matched[*j] && orders[*j].buy.token == orders[i].sell.token)
+                .find_map(|j| Some((j, self.direct(&orders[i], &orders[j])?)));
+            if let Some((j, solution)) = direct {
+                tracing::debug!(first =% orders[i].uid, second =% orders[j].uid, "found direct CoW");
+                matched[i] = true;
+                matched[j] = true;
+                matches.push(Match {
+                    orders: vec![i, j],
+                    solution,
+                });
+            }
+        }
+
+        for i in 0..orders.len() {
+            if matched[i] {
+                continue;
+            }
+            let unmatched = {
+                let matched = &matched;
+                move |j: &usize| !matched[*j]
+            };
+            let ring = selling(orders[i].buy.token)
+                .filter(|j| unmatched(j) && orders[*j].buy.token != orders[i].sell.token)
+                .flat_map(|j| {
+                    selling(orders[j].buy.token)
+                        .filter(move |k| {
+                            unmatched(k) && orders[*k].buy.token == orders[i].sell.token
+                        })
+                        .map(move |k| (j, k))
+                })
+                .find_map(|(j, k)| Some((j, k, self.ring(&[&orders[i], &orders[j], &orders[k]])?)));
+            if let Some((j, k, solution)) = ring {
+                tracing::debug!(
+                    first =% orders[i].uid,
+                    second =% orders[j].uid,
+                    third =% orders[k].uid,
+                    "found ring CoW"
+                );
+                matched[i] = true;
+                matched[j] = true;
+                matched[k] = true;
+                matches.push(Match {
+                    orders: vec![i, j, k],
+                    solution,
+                });
+            }
+        }
+
+        matches
+    }
+
+    /// Matches two orders trading opposite token pairs. A pure CoW is
+    /// preferred, falling back to routing the unmatched volume over on-chain
+    /// liquidity.
+    fn direct(&self, a: &Order, b: &Order) -> Option<solution::Solution> {
+        self.ring(&[a, b])
+            .or_else(|| self.direct_with_liquidity(a, b))
+            .or_else(|| self.direct_with_liquidity(b, a))
+    }
+
+    /// Matches two orders trading opposite token pairs, routing the part of
+    /// `first`'s volume that isn't bought by `second` over on-chain liquidity.
+    ///
+    /// The clearing prices are the prices that `first` would get by routing its
+    /// full volume over liquidity. Since the remaining volume is smaller, it
+    /// can be routed at the same or at better prices. This means that the
+    /// surplus of the CoW goes to `second`.
+    fn direct_with_liquidity(&self, first: &Order, second: &Order) -> Option<solution::Solution> {
+        let route = self.solver.route(
+            Request {
+                sell: first.sell,
+                buy: first.buy,
+                side: first.side,
+            },
+            self.max_hops,
+        )?;
+        let prices = HashMap::from([
+            (first.sell.token, route.output().amount),
+            (first.buy.token, route.input().amount),
+        ]);
+
+        let gas = self.gas(2, Some(&route));
+        let executions = [
+            Execution::new(first, &prices, self.fee(first, gas, 2)?)?,
+            Execution::new(second, &prices, self.fee(second, gas, 2)?)?,
+        ];
+
+        let remaining = executions[0].sell.checked_sub(executions[1].buy)?;
+        let missing = executions[0].buy.checked_sub(executions[1].sell)?;
+        let route = if missing.is_zero() {
+            None
+        } else {
+            Some(self.solver.route(
+                Request {
+                    sell: eth::Asset {
+                        token: first.sell.token,
+                        amount: remaining,
+                    },
+                    buy: eth::Asset {
+                        token: first.buy.token,
+                        amount: missing,
+                    },
+                    side: order::Side::Sell,
+                },
+                self.max_hops,
+            )?)
+        };
+
+        self.solution(executions.into(), prices, route)
+    }
+
+    /// Matches a ring of orders, where every order buys the token that the
+    /// next order sells, without using any on-chain liquidity.
+    ///
+    /// For every token in the ring, the amount sold by one order has to be
+    /// exactly the amount bought by the previous order. Sell orders fix the
+    /// amount they sell and buy orders the amount they buy. If neither of the
+    /// two orders fixes the amount of a token, then the surplus is split
+    /// evenly between the two.
+    fn ring(&self, orders: &[&Order]) -> Option<solution::Solution> {
+        let n = orders.len();
+        let gas = self.gas(n, None);
+        let fees = orders
+            .iter()
+            .map(|order| self.fee(order, gas, n))
+            .collect::<Option<Vec<_>>>()?;
+
+        let flows = (0..n)
+            .map(|i| {
+                let (order, fee) = (orders[i], fees[i]);
+                let previous = orders[(i + n - 1) % n];
+                let flow = match (order.side, previous.side) {
+                    (order::Side::Sell, order::Side::Buy) => {
+                        let sold = order.sell.amount.checked_sub(fee)?;
+                        (sold == previous.buy.amount).then_some(sold)?
+                    }
+                    (order::Side::Sell, order::Side::Sell) => order.sell.amount.checked_sub(fee)?,
+                    (order::Side::Buy, order::Side::Buy) => previous.buy.amount,
+                    (order::Side::Buy, order::Side::Sell) => {
+                        let min = previous.buy.amount;
+                        let max = order.sell.amount.checked_sub(fee)?;
+                        (min <= max).then(|| min + (max - min) / 2)?
+                    }
+                };
+                (!flow.is_zero()).then_some(flow)
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        // Choosing prices inversely proportional to the token flows means that
+        // every order trades exactly the same value, which balances the flows
+        // without any rounding.
+        let prices = (0..n)
+            .map(|i| {
+                let price = flows
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .try_fold(U256::one(), |price, (_, flow)| price.checked_mul(*flow))?;
+                Some((orders[i].sell.token, price))
+            })
+            .collect::<Option<HashMap<_, _>>>()?;
+        if prices.len() != n {
+            return None;
+        }
+
+        let executions = orders
+            .iter()
+            .zip(fees)
+            .map(|(order, fee)| Execution::new(order, &prices, fee))
+            .collect::<Option<Vec<_>>>()?;
+        self.solution(executions, prices, None)
+    }
+
+    /// Creates a solution for the specified order executions. Returns `None`
+    /// if the settlement would not have enough tokens to pay out all orders.
+    fn solution(
+        &self,
+        executions: Vec<Execution>,
+        prices: HashMap<eth::TokenAddress, U256>,
+        route: Option<Route>,
+    ) -> Option<solution::Solution> {
+        if !is_balanced(&executions, route.as_ref()) {
+            return None;
+        }
+
+        let gas = self.gas(executions.len(), route.as_ref());
+        Some(
+            solution::Solution {
+                id: Default::default(),
+                prices: solution::ClearingPrices(prices),
+                trades: executions
+                    .into_iter()
+                    .map(Execution::into_trade)
+                    .collect::<Option<_>>()?,
+                pre_interactions: Default::default(),
+                interactions: route.map(|route| route.interactions()).unwrap_or_default(),
+                post_interactions: Default::default(),
+                gas: Some(gas),
+            }
+            .with_buffers_internalizations(self.tokens),
+        )
+    }
+
+    /// Estimates the gas needed for a settlement with the specified number of
+    /// trades and an optional route over on-chain liquidity.
+    fn gas(&self, trades: usize, route: Option<&Route>) -> eth::Gas {
+        let route = route.map(|route| route.gas().0).unwrap_or_default();
+        let trades =
+            U256::from(ADDITIONAL_TRADE_GAS).saturating_mul(trades.saturating_sub(1).into());
+        eth::Gas(route.saturating_add(trades)) + self.solution_gas_offset
+    }
+
+    /// Computes the fee charged to an order for its share of the settlement's
+    /// gas costs. Only limit orders are charged a fee. Returns `None` if the
+    /// fee cannot be computed because the sell token price is unknown.
+    fn fee(&self, order: &Order, gas: eth::Gas, trades: usize) -> Option<U256> {
+        if !order.solver_determines_fee() {
+            return Some(U256::zero());
+        }
+
+        let price = self.tokens.reference_price(&order.sell.token)?;
+        let cost = gas.0.checked_mul(self.gas_price.0 .0)? / U256::from(trades);
+        price.ether_value(eth::Ether(cost))
+    }
+}
+
+/// The execution of an order at a set of uniform clearing prices.
+struct Execution<'a> {
+    order: &'a Order,
+    /// The amount of sell token traded at the clearing prices. This does not
+    /// include the fee.
+    sell: U256,
+    /// The amount of buy token that the order receives.
+    buy: U256,
+    fee: U256,
+}
+
+impl<'a> Execution<'a> {
+    /// Executes an order for its full amount at the specified clearing prices.
+    /// Returns `None` if the execution doesn't satisfy the order's limit
+    /// price.
+    fn new(order: &'a Order, prices: &HashMap<eth::TokenAddress, U256>, fee: U256) -> Option<Self> {
+        let sell_price = *prices.get(&order.sell.token)?;
+        let buy_price = *prices.get(&order.buy.token)?;
+
+        // Round in favour of the settlement contract, the same way the
+        // contract does.
+        let (sell, buy) = match order.side {
+            order::Side::Sell => {
+                let sell = order.sell.amount.checked_sub(fee)?;
+                (sell, sell.checked_mul(sell_price)?.checked_div(buy_price)?)
+            }
+            order::Side::Buy => {
+                let buy = order.buy.amount;
+                (
+                    util::math::div_ceil(buy.checked_mul(buy_price)?, sell_price)?,
+                    buy,
+                )
+            }
+        };
+
+        let total = sell.checked_add(fee)?;
+        if total > order.sell.amount
+            || order.sell.amount.checked_mul(buy)? < order.buy.amount.checked_mul(total)?
+        {
+            return None;
+        }
+
+        Some(Self {
+            order,
+            sell,
+            buy,
+            fee,
+        })
+    }
+
+    fn into_trade(self) -> Option<solution::Trade> {
+        let fee = if self.order.solver_determines_fee() {
+            solution::Fee::Surplus(eth::SellTokenAmount(self.fee))
+        } else {
+            solution::Fee::Protocol
+        };
+        let executed = match self.order.side {
+            order::Side::Buy => self.buy,
+            order::Side::Sell => self.sell,
+        };
+        Some(solution::Trade::Fulfillment(solution::Fulfillment::new(
+            self.order.clone(),
+            executed,
+            fee,
+        )?))
+    }
+}
+
+/// Returns `true` if the settlement has enough of every token to pay out all
+/// order executions, accounting for the optional route over on-chain
+/// liquidity.
+fn is_balanced(executions: &[Execution], route: Option<&Route>) -> bool {
+    let mut balances = HashMap::<eth::TokenAddress, (U256, U256)>::new();
+    let mut add = |token, amount: U256, incoming: bool| {
+        let (inflow, outflow) = balances.entry(token).or_default();
+        let flow = if incoming { inflow } else { outflow };
+        match flow.checked_add(amount) {
+            Some(sum) => {
+                *flow = sum;
+                true
+            }
+            None => false,
+        }
+    };
+
+    for execution in executions {
+        if !add(execution.order.sell.token, execution.sell, true)
+            || !add(execution.order.buy.token, execution.buy, false)
+        {
+            return false;
+        }
+    }
+    if let Some(route) = route {
+        let (input, output) = (route.input(), route.output());
+        if !add(input.token, input.amount, false) || !add(output.token, output.amount, true) {
+            return false;
+        }
+    }
+
+    balances.values().all(|(inflow, outflow)| inflow >= outflow)
+}
@@ -0,0 +1,360 @@
+//! Merging of single order solutions into solutions settling multiple orders.
+//!
+//! Orders are solved one after the other, and each solution gets merged into
+//! the first group of solutions that it is compatible with. In order for a
+//! merged solution to not over-promise, every group keeps its own copy of the
+//! liquidity state which gets updated with the swaps of each solution that is
+//! merged into it. This way, subsequent orders are routed over the post-trade
+//! state of the liquidity that the group already uses.
+//!
+//! The settlement overhead is only paid once per merged solution. Orders that
+//! get merged into an existing group are therefore solved with the gas of an
+//! additional trade instead of the solution gas offset, which is also what
+//! their solver determined fees are based on.
+//!
+//! Swaps over the same liquidity and in the same direction are combined into a
+//! single interaction when this does not change the execution order. Executing
+//! the combined amounts in a single swap yields at least the sum of the
+//! individual outputs, as every swap rounds in favour of the liquidity.
+
+use {
+    super::ADDITIONAL_TRADE_GAS,
+    crate::{
+        boundary,
+        domain::{
+            eth,
+            liquidity::{
+                self,
+                composable_stable,
+                constant_product,
+                liquidity_bootstrapping,
+                stable,
+                weighted_product,
+            },
+            solution,
+        },
+    },
+    ethereum_types::U256,
+    std::collections::HashMap,
+};
+
+/// Merges single order solutions into groups of compatible solutions.
+pub struct Merger<'a> {
+    liquidity: &'a [liquidity::Liquidity],
+    solution_gas_offset: eth::SignedGas,
+    groups: Vec<Group>,
+}
+
+/// A merged solution, along with the liquidity state after executing it.
+struct Group {
+    solution: solution::Solution,
+    liquidity: Vec<liquidity::Liquidity>,
+}
+
+impl<'a> Merger<'a> {
+    pub fn new(liquidity: &'a [liquidity::Liquidity], solution_gas_offset: eth::SignedGas) -> Self {
+        Self {
+            liquidity,
+            solution_gas_offset,
+            groups: Vec::new(),
+        }
+    }
+
+    /// Solves an order over the liquidity state of each existing group, and
+    /// merges the solution into the first group it is compatible with. If
+    /// there is no such group, the order is solved over the initial liquidity
+    /// state and starts a new group.
+    ///
+    /// Orders are solved with the gas offset to add to the gas of their
+    /// routes, which is the solution gas offset only for orders starting a new
+    /// group.
+    pub fn insert(
+        &mut self,
+        solve: impl Fn(&[liquidity::Liquidity], eth::SignedGas) -> Option<solution::Solution>,
+    ) {
+        let merged_gas_offset = eth::SignedGas::from(ADDITIONAL_TRADE_GAS as i64);
+        for group in &mut self.groups {
+            let Some(solution) = solve(&group.liquidity, merged_gas_offset) else {
+                continue;
+            };
+            if group.merge(solution) {
+                return;
+            }
+        }
+
+        let Some(solution) = solve(self.liquidity, self.solution_gas_offset) else {
+            return;
+        };
+        let Some(updates) = updates(self.liquidity, &solution) else {
+            tracing::debug!(?solution, "solution swaps cannot be applied to liquidity");
+            return;
+        };
+        let mut liquidity = self.liquidity.to_vec();
+        for (index, updated) in updates {
+            liquidity[index] = updated;
+        }
+        self.groups.push(Group {
+            solution,
+            liquidity,
+        });
+    }
+
+    /// Returns the merged solutions.
+    pub fn into_solutions(self) -> impl Iterator<Item = solution::Solution> {
+        self.groups.into_iter().map(|group| group.solution)
+    }
+}
+
+impl Group {
+    /// Merges a solution into the group. Returns `false` if the solution is
+    /// not compatible with the group, in which case the group is unchanged.
+    fn merge(&mut self, solution: solution::Solution) -> bool {
+        let Some(updates) = updates(&self.liquidity, &solution) else {
+            return false;
+        };
+        let Some(prices) = merge_prices(&self.solution.prices, &solution.prices) else {
+            return false;
+        };
+
+        let solution::Solution {
+            trades,
+            pre_interactions,
+            interactions,
+            post_interactions,
+            gas,
+            ..
+        } = solution;
+
+        // The solution was solved with the gas of an additional trade instead
+        // of the settlement overhead, which is already accounted for in the
+        // group's gas.
+        self.solution.gas = self
+            .solution
+            .gas
+            .zip(gas)
+            .map(|(total, gas)| eth::Gas(total.0.saturating_add(gas.0)));
+        self.solution.prices = prices;
+        self.solution.trades.extend(trades);
+        self.solution.pre_interactions.extend(pre_interactions);
+        self.extend_interactions(interactions);
+        self.solution.post_interactions.extend(post_interactions);
+        for (index, updated) in updates {
+            self.liquidity[index] = updated;
+        }
+        true
+    }
+
+    /// Appends interactions to the group's solution, combining leading
+    /// interactions with existing ones where possible.
+    fn extend_interactions(&mut self, interactions: Vec<solution::Interaction>) {
+        let mut interactions = interactions.into_iter().peekable();
+        let mut start = 0;
+        while let Some(solution::Interaction::Liquidity(interaction)) = interactions.peek() {
+            let Some(index) = self.combinable(interaction, start) else {
+                break;
+            };
+            let solution::Interaction::Liquidity(existing) = &mut self.solution.interactions[index]
+            else {
+                unreachable!("combinable interactions are liquidity interactions");
+            };
+            let (Some(input), Some(output)) = (
+                existing.input.amount.checked_add(interaction.input.amount),
+                existing
+                    .output
+                    .amount
+                    .checked_add(interaction.output.amount),
+            ) else {
+                break;
+            };
+
+            existing.input.amount = input;
+            existing.output.amount = output;
+            interactions.next();
+            // Subsequent interactions may depend on the output of this one, so
+            // they can only be combined with interactions that come after it.
+            start = index + 1;
+        }
+        self.solution.interactions.extend(interactions);
+    }
+
+    /// Returns the index of an existing interaction at or after `start` that
+    /// the specified interaction can be combined with. This is the case when
+    /// the existing interaction is the last one to use the same liquidity and
+    /// swaps in the same direction.
+    fn combinable(
+        &self,
+        interaction: &solution::LiquidityInteraction,
+        start: usize,
+    ) -> Option<usize> {
+        let index = self
+            .solution
+            .interactions
+            .iter()
+            .rposition(|existing| match existing {
+                solution::Interaction::Liquidity(existing) => {
+                    existing.liquidity.id == interaction.liquidity.id
+                }
+                solution::Interaction::Custom(_) => false,
+            })?;
+        let solution::Interaction::Liquidity(existing) = &self.solution.interactions[index] else {
+            return None;
+        };
+
+        (index >= start
+            && existing.input.token == interaction.input.token
+            && existing.output.token == interaction.output.token)
+            .then_some(index)
+    }
+}
+
+/// Computes the liquidity state after executing the swaps of a solution.
+/// Returns the updated liquidity by index, or `None` if any of the swaps cannot
+/// be applied.
+fn updates(
+    liquidity: &[liquidity::Liquidity],
+    solution: &solution::Solution,
+) -> Option<HashMap<usize, liquidity::Liquidity>> {
+    let mut updates = HashMap::new();
+    for interaction in &solution.interactions {
+        let solution::Interaction::Liquidity(interaction) = interaction else {
+            continue;
+        };
+        let index = liquidity
+            .iter()
+            .position(|liquidity| liquidity.id == interaction.liquidity.id)?;
+        let updated = updates
+            .entry(index)
+            .or_insert_with(|| liquidity[index].clone());
+        swap(updated, interaction.input, interaction.output)?;
+    }
+    Some(updates)
+}
+
+/// Updates the liquidity state for swapping `input` for `output`.
+fn swap(liquidity: &mut liquidity::Liquidity, input: eth::Asset, output: eth::Asset) -> Option<()> {
+    match &mut liquidity.state {
+        liquidity::State::ConstantProduct(pool) => {
+            let (a, b) = pool.reserves.get();
+            pool.reserves = constant_product::Reserves::new(
+                swap_reserve(a, input, output)?,
+                swap_reserve(b, input, output)?,
+            )?;
+        }
+        liquidity::State::WeightedProduct(pool) => {
+            pool.reserves = weighted_product::Reserves::new(
+                pool.reserves
+                    .iter()
+                    .map(|reserve| {
+                        Some(weighted_product::Reserve {
+                            asset: swap_reserve(reserve.asset, input, output)?,
+                            ..reserve
+                        })
+                    })
+                    .collect::<Option<_>>()?,
+            )?;
+        }
+        liquidity::State::LiquidityBootstrapping(pool) => {
+            pool.reserves = liquidity_bootstrapping::Reserves::new(
+                pool.reserves
+                    .iter()
+                    .map(|reserve| {
+                        Some(liquidity_bootstrapping::Reserve {
+                            asset: swap_reserve(reserve.asset, input, output)?,
+                            ..reserve
+                        })
+                    })
+                    .collect::<Option<_>>()?,
+            )?;
+        }
+        liquidity::State::Stable(stable::Pool { reserves, .. })
+        | liquidity::State::ComposableStable(composable_stable::Pool { reserves, .. }) => {
+            *reserves = stable::Reserves::new(
+                reserves
+                    .iter()
+                    .map(|reserve| {
+                        Some(stable::Reserve {
+                            asset: swap_reserve(reserve.asset, input, output)?,
+                            ..reserve
+                        })
+                    })
+                    .collect::<Option<_>>()?,
+            )?;
+        }
+        liquidity::State::Concentrated(pool) => {
+            *pool = boundary::liquidity::concentrated::to_swapped_pool(pool, input)?;
+        }
+        liquidity::State::LimitOrder(order) => {
+            if (order.taker.token, order.maker.token) != (input.token, output.token) {
+                return None;
+            }
+            order.taker.amount = order.taker.amount.checked_sub(input.amount)?;
+            order.maker.amount = order.maker.amount.checked_sub(output.amount)?;
+        }
+    }
+    Some(())
+}
+
+/// Returns a liquidity reserve after swapping `input` for `output`.
+fn swap_reserve(reserve: eth::Asset, input: eth::Asset, output: eth::Asset) -> Option<eth::Asset> {
+    let amount = if reserve.token == input.token {
+        reserve.amount.checked_add(input.amount)?
+    } else if reserve.token == output.token {
+        reserve.amount.checked_sub(output.amount)?
+    } else {
+        reserve.amount
+    };
+    Some(eth::Asset {
+        token: reserve.token,
+        amount,
+    })
+}
+
+/// Merges two sets of clearing prices. If the prices have tokens in common,
+/// both sets get scaled so that the prices of common tokens are equal. Returns
+/// `None` if there is no such scaling, or if the scaled prices overflow.
+///
+/// Prices are only ever multiplied so that the merged prices are exact, and
+/// then divided by their greatest common divisor to keep them small.
+fn merge_prices(
+    a: &solution::ClearingPrices,
+    b: &solution::ClearingPrices,
+) -> Option<solution::ClearingPrices> {
+    let (scale_a, scale_b) = match a.0.keys().find(|token| b.0.contains_key(token)) {
+        Some(token) => {
+            let (price_a, price_b) = (a.0[token], b.0[token]);
+            let divisor = gcd(price_a, price_b);
+            if divisor.is_zero() {
+                return None;
+            }
+            (price_b / divisor, price_a / divisor)
+        }
+        None => (U256::one(), U256::one()),
+    };
+
+    let mut prices = HashMap::new();
+    for (token, price) in &a.0 {
+        prices.insert(*token, price.checked_mul(scale_a)?);
+    }
+    for (token, price) in &b.0 {
+        let price = price.checked_mul(scale_b)?;
+        if *prices.entry(*token).or_insert(price) != price {
+            return None;
+        }
+    }
+
+    let divisor = prices.values().copied().fold(U256::zero(), gcd);
+    if !divisor.is_zero() {
+        for price in prices.values_mut() {
+            *price /= divisor;
+        }
+    }
+    Some(solution::ClearingPrices(prices))
+}
+
+/// Computes the greatest common divisor of two integers.
+fn gcd(mut a: U256, mut b: U256) -> U256 {
+    while !b.is_zero() {
+        (a, b) = (b, a % b);
+    }
+    a
+}
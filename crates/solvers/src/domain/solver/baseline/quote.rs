@@ -0,0 +1,94 @@
+//! Fast-path for price quotes.
+//!
+//! The driver requests quotes by sending auctions without an ID that consist of
+//! a single order. Quote latency is user facing, so these auctions skip CoW
+//! matching, partial fill searches and solution merging. Additionally, the
+//! liquidity path found for a token pair can be memoised for a short time, and
+//! later quotes for the same token pair and a similar amount first try to route
+//! along that path using the liquidity of their own auction before searching
+//! all paths again.
+
+use {
+    super::{Hop, Request},
+    crate::domain::{eth, order},
+    std::{
+        collections::HashMap,
+        sync::Mutex,
+        time::{Duration, Instant},
+    },
+};
+
+/// Memoised liquidity paths per token pair and amount bucket.
+pub struct Paths {
+    ttl: Duration,
+    paths: Mutex<HashMap<Key, Path>>,
+}
+
+/// Quotes share a path if they trade the same token pair in the same direction
+/// and their fixed amounts have the same order of magnitude, since the best
+/// path for a pair heavily depends on the traded amount.
+#[derive(Debug, Eq, Hash, PartialEq)]
+struct Key {
+    sell: eth::TokenAddress,
+    buy: eth::TokenAddress,
+    side: order::Side,
+    /// The bit length of the order's fixed amount.
+    bucket: usize,
+}
+
+impl Key {
+    fn new(request: &Request) -> Self {
+        let amount = match request.side {
+            order::Side::Sell => request.sell.amount,
+            order::Side::Buy => request.buy.amount,
+        };
+        Self {
+            sell: request.sell.token,
+            buy: request.buy.token,
+            side: request.side,
+            bucket: amount.bits(),
+        }
+    }
+}
+
+struct Path {
+    hops: Vec<Hop>,
+    expires: Instant,
+}
+
+impl Paths {
+    /// Creates a new cache that memoises paths for the specified duration. A
+    /// duration of zero disables memoisation.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            paths: Default::default(),
+        }
+    }
+
+    /// Returns the memoised path for the request's token pair and amount, if
+    /// any.
+    pub fn get(&self, request: &Request) -> Option<Vec<Hop>> {
+        let paths = self.paths.lock().unwrap();
+        let path = paths.get(&Key::new(request))?;
+        (path.expires > Instant::now()).then(|| path.hops.clone())
+    }
+
+    /// Memoises the path for the request's token pair and amount.
+    pub fn insert(&self, request: &Request, hops: Vec<Hop>) {
+        if self.ttl.is_zero() {
+            return;
+        }
+
+        let now = Instant::now();
+        let mut paths = self.paths.lock().unwrap();
+        paths.retain(|_, path| path.expires > now);
+        paths.insert(
+            Key::new(request),
+            Path {
+                hops,
+                expires: now + self.ttl,
+            },
+        );
+    }
+}
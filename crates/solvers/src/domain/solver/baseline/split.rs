@@ -0,0 +1,172 @@
+//! Splitting of large orders across multiple routes.
+//!
+//! Routing a large order over a single path can incur significant price
+//! impact. Instead, we find up to `max_splits` paths that do not share any
+//! liquidity and divide the order amount between them. The amount is
+//! allocated in small parts, each going to the path that currently offers the
+//! best marginal price, so that the marginal prices of all paths end up
+//! (approximately) equal.
+//!
+//! Note that the additional gas needed for executing more routes is not taken
+//! into account when allocating amounts. Instead, the split is only used if
+//! it beats the best single route net of gas.
+
+use {
+    super::{Request, Route},
+    crate::{
+        boundary,
+        domain::{eth, order},
+    },
+    ethereum_types::U256,
+    std::{cmp, collections::HashSet},
+};
+
+/// The number of parts the order amount gets divided into when allocating it
+/// to the different paths.
+const PARTS: u64 = 100;
+
+pub struct Splitter<'a> {
+    pub solver: &'a boundary::baseline::Solver<'a>,
+    pub max_hops: usize,
+    pub max_splits: usize,
+}
+
+impl<'a> Splitter<'a> {
+    /// Splits the request across multiple disjoint routes. Returns `None` if
+    /// the request can't be split across at least two routes while respecting
+    /// its limit price.
+    pub fn split(&self, request: Request) -> Option<Vec<Route<'a>>> {
+        let amount = match request.side {
+            order::Side::Sell => request.sell.amount,
+            order::Side::Buy => request.buy.amount,
+        };
+        let part = amount / U256::from(PARTS);
+        if part.is_zero() {
+            return None;
+        }
+
+        let paths = self.paths(request, amount / U256::from(self.max_splits));
+        if paths.len() < 2 {
+            return None;
+        }
+
+        // The amount allocated to each path, and the amount of the other token
+        // that is received (for sell orders) or spent (for buy orders) when
+        // executing that allocation.
+        let mut allocations = vec![U256::zero(); paths.len()];
+        let mut values = vec![U256::zero(); paths.len()];
+        let mut remaining = amount;
+        while !remaining.is_zero() {
+            let chunk = cmp::min(part, remaining);
+            let candidates = paths.iter().enumerate().filter_map(|(i, path)| {
+                let route = self.reroute(path, request, allocations[i] + chunk)?;
+                let value = match request.side {
+                    order::Side::Sell => route.output().amount,
+                    order::Side::Buy => route.input().amount,
+                };
+                Some((i, value))
+            });
+            let (best, value) = match request.side {
+                order::Side::Sell => {
+                    candidates.max_by_key(|(i, value)| value.saturating_sub(values[*i]))?
+                }
+                order::Side::Buy => {
+                    candidates.min_by_key(|(i, value)| value.saturating_sub(values[*i]))?
+                }
+            };
+
+            allocations[best] += chunk;
+            values[best] = value;
+            remaining -= chunk;
+        }
+
+        let routes = paths
+            .iter()
+            .zip(allocations)
+            .filter(|(_, allocation)| !allocation.is_zero())
+            .map(|(path, allocation)| self.reroute(path, request, allocation))
+            .collect::<Option<Vec<_>>>()?;
+        if routes.len() < 2 {
+            return None;
+        }
+
+        let satisfied = match request.side {
+            order::Side::Sell => {
+                total(routes.iter().map(Route::output)).amount >= request.buy.amount
+            }
+            order::Side::Buy => {
+                total(routes.iter().map(Route::input)).amount <= request.sell.amount
+            }
+        };
+        satisfied.then_some(routes)
+    }
+
+    /// Finds up to `max_splits` routes for the request that do not share any
+    /// liquidity. Routes are found for a probe amount without a limit price,
+    /// as it only gets checked for the combined routes.
+    fn paths(&self, request: Request, amount: U256) -> Vec<Route<'a>> {
+        let probe = unlimited(request, amount);
+        let mut excluded = HashSet::new();
+        let mut paths = Vec::new();
+        while paths.len() < self.max_splits {
+            let Some(path) = self.solver.route_excluding(probe, self.max_hops, &excluded) else {
+                break;
+            };
+            excluded.extend(
+                path.segments()
+                    .iter()
+                    .map(|segment| segment.liquidity.id.clone()),
+            );
+            paths.push(path);
+        }
+        paths
+    }
+
+    /// Computes the route over an existing path for the specified amount of
+    /// the request, without a limit price.
+    fn reroute(&self, path: &Route<'a>, request: Request, amount: U256) -> Option<Route<'a>> {
+        self.solver.reroute(path, unlimited(request, amount))
+    }
+}
+
+/// Returns the sum of the specified assets, which are all of the same token.
+///
+/// # Panics
+///
+/// Panics if there are no assets.
+pub fn total(mut assets: impl Iterator<Item = eth::Asset>) -> eth::Asset {
+    let first = assets.next().expect("at least one asset");
+    assets.fold(first, |total, asset| eth::Asset {
+        token: total.token,
+        amount: total.amount.saturating_add(asset.amount),
+    })
+}
+
+/// Returns a request for trading the specified amount of the request's fixed
+/// side, without any limit on the other side.
+fn unlimited(request: Request, amount: U256) -> Request {
+    match request.side {
+        order::Side::Sell => Request {
+            sell: eth::Asset {
+                token: request.sell.token,
+                amount,
+            },
+            buy: eth::Asset {
+                token: request.buy.token,
+                amount: U256::zero(),
+            },
+            side: request.side,
+        },
+        order::Side::Buy => Request {
+            sell: eth::Asset {
+                token: request.sell.token,
+                amount: U256::MAX,
+            },
+            buy: eth::Asset {
+                token: request.buy.token,
+                amount,
+            },
+            side: request.side,
+        },
+    }
+}
@@ -0,0 +1,75 @@
+//! Solver engines.
+//!
+//! The `solvers` binary can run one of multiple solver engines. All engines
+//! share the same HTTP API, DTOs, metrics and liquidity plumbing, and only
+//! differ in how they compute solutions for an auction:
+//! - [`Baseline`] routes individual orders over on-chain liquidity.
+//! - [`Naive`] only matches orders against each other, without using any
+//!   on-chain liquidity.
+//!
+//! Every engine implements [`Engine`]. Engines are made selectable by name
+//! with the `engine` field of the configuration file by registering them with
+//! the binary (see [`crate::infra::config::Engines`]).
+//!
+//! Regardless of the engine, the solver can back off from tokens, orders and
+//! liquidity that repeatedly make its solutions fail (see [`backoff`]).
+
+use crate::{
+    domain::{auction, eth, notification, solution},
+    infra::metrics,
+};
+
+pub mod backoff;
+pub mod baseline;
+pub mod naive;
+
+pub use self::{baseline::Baseline, naive::Naive};
+
+/// The amount of time we aim the solver to finish before the final deadline is
+/// reached.
+const DEADLINE_SLACK: chrono::Duration = chrono::Duration::milliseconds(500);
+
+pub struct Solver {
+    engine: Box<dyn Engine>,
+    backoff: backoff::Backoff,
+}
+
+/// A solver engine.
+#[async_trait::async_trait]
+pub trait Engine: Send + Sync {
+    /// The wrapped native token the engine routes over.
+    fn weth(&self) -> eth::WethAddress;
+
+    /// Solves the specified auction, returning a vector of all possible
+    /// solutions.
+    async fn solve(&self, auction: auction::Auction) -> Vec<solution::Solution>;
+}
+
+impl Solver {
+    pub fn new(engine: Box<dyn Engine>, backoff: Option<backoff::Config>) -> Self {
+        let weth = engine.weth();
+        Self {
+            engine,
+            backoff: backoff::Backoff::new(backoff, weth),
+        }
+    }
+
+    /// Solves the specified auction, returning a vector of all possible
+    /// solutions.
+    pub async fn solve(&self, mut auction: auction::Auction) -> Vec<solution::Solution> {
+        metrics::solve(&auction);
+        let id = auction.id;
+        let deadline = auction.deadline.clone();
+        self.backoff.filter(&mut auction);
+        let solutions = self.engine.solve(auction).await;
+        self.backoff.record(id, &solutions);
+        metrics::solved(&deadline, &solutions);
+        solutions
+    }
+
+    /// Handles a notification about the outcome of a previously proposed
+    /// solution.
+    pub fn notify(&self, notification: notification::Notification) {
+        self.backoff.notify(&notification);
+    }
+}
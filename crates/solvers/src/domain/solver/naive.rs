@@ -0,0 +1,110 @@
+//! "Naive" solver implementation.
+//!
+//! The naive solver only matches orders against each other in coincidences of
+//! wants (CoWs), using the same matching logic as the baseline solver (see
+//! [`super::baseline::cow`]). It never uses on-chain liquidity, meaning that
+//! CoWs need to fully match the orders' volumes, and orders that can't be
+//! matched are not solved.
+
+use {
+    super::baseline::cow,
+    crate::{
+        boundary,
+        domain::{auction, eth, solution},
+    },
+    std::{collections::HashSet, sync::Arc},
+};
+
+pub struct Naive(Arc<Inner>);
+
+pub struct Config {
+    pub weth: eth::WethAddress,
+    pub solution_gas_offset: eth::SignedGas,
+}
+
+struct Inner {
+    weth: eth::WethAddress,
+
+    /// Units of gas that get added to the gas estimate for executing the
+    /// matched trades to arrive at a gas estimate for a whole settlement.
+    solution_gas_offset: eth::SignedGas,
+}
+
+impl Naive {
+    /// Creates a new naive solver for the specified configuration.
+    pub fn new(config: Config) -> Self {
+        Self(Arc::new(Inner {
+            weth: config.weth,
+            solution_gas_offset: config.solution_gas_offset,
+        }))
+    }
+}
+
+#[async_trait::async_trait]
+impl super::Engine for Naive {
+    fn weth(&self) -> eth::WethAddress {
+        self.0.weth
+    }
+
+    /// Solves the specified auction, returning a vector of all possible
+    /// solutions.
+    async fn solve(&self, auction: auction::Auction) -> Vec<solution::Solution> {
+        let remaining = auction
+            .deadline
+            .clone()
+            .reduce(super::DEADLINE_SLACK)
+            .remaining()
+            .unwrap_or_default();
+
+        // Matching orders can get CPU-heavy for large auctions, so make sure
+        // to not block the [`tokio`] runtime.
+        let inner = self.0.clone();
+        let span = tracing::Span::current();
+        let background_work = tokio::task::spawn_blocking(move || {
+            let _entered = span.enter();
+            inner.solve(&auction)
+        });
+
+        match tokio::time::timeout(remaining, background_work).await {
+            Ok(Ok(solutions)) => solutions,
+            Ok(Err(err)) => {
+                tracing::warn!(?err, "failed to match orders");
+                Vec::new()
+            }
+            Err(_) => {
+                tracing::debug!("reached timeout while matching orders");
+                Vec::new()
+            }
+        }
+    }
+}
+
+impl Inner {
+    fn solve(&self, auction: &auction::Auction) -> Vec<solution::Solution> {
+        let boundary_solver = boundary::baseline::Solver::new(
+            &self.weth,
+            &HashSet::new(),
+            &[],
+            auction.deadline.timestamp(),
+        );
+        let matcher = cow::Matcher {
+            solver: &boundary_solver,
+            max_hops: 0,
+            tokens: &auction.tokens,
+            gas_price: auction.gas_price,
+            solution_gas_offset: self.solution_gas_offset,
+        };
+
+        matcher
+            .solve(&auction.orders)
+            .into_iter()
+            .map(|cow| {
+                // Use the index of the first matched order as the solution ID,
+                // this keeps IDs unique as every order is in at most one
+                // solution.
+                let id = solution::Id(cow.orders[0] as u64);
+                cow.solution.with_id(id)
+            })
+            .collect()
+    }
+}
@@ -2,7 +2,7 @@
 
 use {
     clap::{Parser, Subcommand},
-    std::{net::SocketAddr, path::PathBuf},
+    std::{net::SocketAddr, path::PathBuf, time::Duration},
 };
 
 /// Run a solver engine
@@ -21,6 +21,22 @@ pub struct Args {
     #[arg(long, env, default_value = "127.0.0.1:7872")]
     pub addr: SocketAddr,
 
+    /// The number of consecutive failed solutions after which the solver stops
+    /// using a token, order or liquidity source that was part of them. The
+    /// solver never backs off if unset.
+    #[arg(long, env)]
+    pub backoff_threshold: Option<u32>,
+
+    /// The initial period for which the solver backs off from a failing token,
+    /// order or liquidity source. It doubles with every further failure.
+    #[arg(long, env, default_value = "1m", value_parser = humantime::parse_duration)]
+    pub backoff_period: Duration,
+
+    /// The maximum period for which the solver backs off from a failing token,
+    /// order or liquidity source.
+    #[arg(long, env, default_value = "1h", value_parser = humantime::parse_duration)]
+    pub max_backoff_period: Duration,
+
     #[command(subcommand)]
     pub command: Command,
 }
@@ -30,9 +46,20 @@ pub struct Args {
 #[derive(Subcommand, Debug)]
 #[clap(rename_all = "lowercase")]
 pub enum Command {
+    /// run the solver engine named by the `engine` field of the configuration
+    /// file, e.g. `engine = "baseline"`
+    Run {
+        #[clap(long, env)]
+        config: PathBuf,
+    },
     /// solve individual orders exclusively via provided onchain liquidity
     Baseline {
         #[clap(long, env)]
         config: PathBuf,
     },
+    /// solve auctions exclusively via coincidences of wants between orders
+    Naive {
+        #[clap(long, env)]
+        config: PathBuf,
+    },
 }
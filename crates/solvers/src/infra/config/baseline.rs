@@ -0,0 +1,125 @@
+use {
+    crate::{
+        domain::{
+            eth,
+            solver::{self, baseline},
+        },
+        util::serialize,
+    },
+    chain::Chain,
+    ethereum_types::H160,
+    serde::Deserialize,
+    serde_with::serde_as,
+    std::{path::Path, time::Duration},
+};
+
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct Config {
+    /// Optional chain ID. This is used to automatically determine the address
+    /// of the WETH contract.
+    chain_id: Option<Chain>,
+
+    /// Optional WETH contract address. This can be used to specify a manual
+    /// value **instead** of using the canonical WETH contract for the
+    /// configured chain.
+    weth: Option<H160>,
+
+    /// List of base tokens to use when path finding. This defines the tokens
+    /// that can appear as intermediate "hops" within a trading route. Note that
+    /// WETH is always considered as a base token.
+    base_tokens: Vec<eth::H160>,
+
+    /// The maximum number of hops to consider when finding the optimal trading
+    /// path.
+    max_hops: usize,
+
+    /// The maximum number of pieces to divide partially fillable limit orders
+    /// when trying to solve it against baseline liquidity. Once a valid fill
+    /// is found, the executed amount is refined to the largest amount that
+    /// respects the order's limit price.
+    max_partial_attempts: usize,
+
+    /// Units of gas that get added to the gas estimate for executing a
+    /// computed trade route to arrive at a gas estimate for a whole settlement.
+    #[serde(default = "super::default_gas_offset")]
+    solution_gas_offset: i64,
+
+    /// The amount of the native token to use to estimate native price of a
+    /// token
+    #[serde_as(as = "serialize::U256")]
+    native_token_price_estimation_amount: eth::U256,
+
+    /// Whether to match orders against each other in coincidences of wants
+    /// before routing the remaining orders individually.
+    #[serde(default)]
+    cow_matching: bool,
+
+    /// The maximum number of disjoint paths to split a single order across.
+    /// A value of 1 disables splitting.
+    #[serde(default = "default_max_splits")]
+    max_splits: usize,
+
+    /// Whether to merge the solutions for individual orders into solutions
+    /// settling multiple orders at once, instead of returning one solution per
+    /// order.
+    #[serde(default)]
+    merge_solutions: bool,
+
+    /// For how long the liquidity path found when quoting an order is reused
+    /// for later quotes of the same token pair and a similar amount. Paths are
+    /// not reused by default.
+    #[serde(with = "humantime_serde", default)]
+    quote_path_ttl: Duration,
+}
+
+/// Load the baseline solver configuration from a TOML file.
+///
+/// # Panics
+///
+/// This method panics if the config is invalid or on I/O errors.
+pub async fn load(path: &Path) -> baseline::Config {
+    super::load::<Config>(path).await.into()
+}
+
+/// The name selecting this engine in the `engine` field of a configuration
+/// file.
+pub const NAME: &str = "baseline";
+
+/// Creates the baseline solver engine from the engine specific options of the
+/// configuration file at `path`.
+///
+/// # Panics
+///
+/// This method panics if the config is invalid.
+pub fn engine(config: toml::Table, path: &Path) -> Box<dyn solver::Engine> {
+    let config = super::parse::<Config>(config, path).into();
+    Box::new(solver::Baseline::new(config))
+}
+
+impl From<Config> for baseline::Config {
+    fn from(config: Config) -> Self {
+        baseline::Config {
+            weth: super::weth(config.chain_id, config.weth),
+            base_tokens: config
+                .base_tokens
+                .into_iter()
+                .map(eth::TokenAddress)
+                .collect(),
+            max_hops: config.max_hops,
+            max_partial_attempts: config.max_partial_attempts,
+            solution_gas_offset: config.solution_gas_offset.into(),
+            native_token_price_estimation_amount: config.native_token_price_estimation_amount,
+            cow_matching: config.cow_matching,
+            max_splits: config.max_splits,
+            merge_solutions: config.merge_solutions,
+            quote_path_ttl: config.quote_path_ttl,
+        }
+    }
+}
+
+/// Orders are routed over a single path by default.
+fn default_max_splits() -> usize {
+    1
+}
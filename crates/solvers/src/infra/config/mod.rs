@@ -0,0 +1,225 @@
+//! Configuration files for the solver engines.
+
+use {
+    crate::{
+        domain::{eth, solver},
+        infra::contracts,
+    },
+    chain::Chain,
+    ethereum_types::H160,
+    serde::de::DeserializeOwned,
+    shared::price_estimation::gas::SETTLEMENT_OVERHEAD,
+    std::{collections::HashMap, fmt::Debug, path::Path},
+    tokio::fs,
+};
+
+pub mod baseline;
+pub mod naive;
+
+/// Creates a solver engine from the engine specific options of the
+/// configuration file at the given path.
+pub type Constructor = fn(toml::Table, &Path) -> Box<dyn solver::Engine>;
+
+/// The solver engines that can be selected by name with the `engine` field of
+/// a configuration file. The binary decides which engines are available by
+/// registering them.
+#[derive(Clone, Default)]
+pub struct Engines(HashMap<&'static str, Constructor>);
+
+impl Engines {
+    /// The engines implemented in this crate.
+    pub fn builtin() -> Self {
+        Self::default()
+            .register(baseline::NAME, baseline::engine)
+            .register(naive::NAME, naive::engine)
+    }
+
+    /// Makes an engine selectable by name, replacing any engine that was
+    /// registered with the same name before.
+    pub fn register(mut self, name: &'static str, constructor: Constructor) -> Self {
+        self.0.insert(name, constructor);
+        self
+    }
+
+    /// Load the solver engine named by the `engine` field of a TOML
+    /// configuration file. The remaining fields configure that engine.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the config is invalid, names an unknown engine or
+    /// on I/O errors.
+    pub async fn load(&self, path: &Path) -> Box<dyn solver::Engine> {
+        let mut config = read(path).await;
+        let name = match config.remove("engine") {
+            Some(toml::Value::String(name)) => name,
+            _ => panic!("invalid configuration: {path:?} must specify the solver `engine` by name"),
+        };
+        let Some(constructor) = self.0.get(name.as_str()) else {
+            let mut known = self.0.keys().collect::<Vec<_>>();
+            known.sort();
+            panic!(
+                "invalid configuration: unknown solver engine {name:?}, expected one of {known:?}"
+            )
+        };
+        constructor(config, path)
+    }
+}
+
+/// Load a solver engine configuration from a TOML file.
+///
+/// # Panics
+///
+/// This method panics if the config is invalid or on I/O errors.
+async fn load<T: DeserializeOwned>(path: &Path) -> T {
+    parse(read(path).await, path)
+}
+
+/// Reads the raw TOML table of a configuration file.
+///
+/// # Panics
+///
+/// This method panics if the file is not valid TOML or on I/O errors.
+async fn read(path: &Path) -> toml::Table {
+    let data = fs::read_to_string(path)
+        .await
+        .unwrap_or_else(|e| panic!("I/O error while reading {path:?}: {e:?}"));
+    // Not printing detailed error because it could potentially leak secrets.
+    unwrap_or_log(toml::de::from_str(&data), &path)
+}
+
+/// Parses a solver engine configuration from a raw TOML table read from the
+/// file at `path`.
+///
+/// # Panics
+///
+/// This method panics if the config is invalid.
+fn parse<T: DeserializeOwned>(config: toml::Table, path: &Path) -> T {
+    unwrap_or_log(toml::Value::Table(config).try_into::<T>(), &path)
+}
+
+/// Returns the WETH address for the configured `chain-id` and `weth` options.
+///
+/// # Panics
+///
+/// This method panics if not exactly one of the options is specified.
+fn weth(chain_id: Option<Chain>, weth: Option<H160>) -> eth::WethAddress {
+    match (chain_id, weth) {
+        (Some(chain_id), None) => contracts::Contracts::for_chain(chain_id).weth,
+        (None, Some(weth)) => eth::WethAddress(weth),
+        (Some(_), Some(_)) => panic!(
+            "invalid configuration: cannot specify both `chain-id` and `weth` configuration \
+             options",
+        ),
+        (None, None) => panic!(
+            "invalid configuration: must specify either `chain-id` or `weth` configuration options",
+        ),
+    }
+}
+
+/// Unwraps result or logs a `TOML` parsing error.
+fn unwrap_or_log<T, E, P>(result: Result<T, E>, path: &P) -> T
+where
+    E: Debug,
+    P: Debug,
+{
+    result.unwrap_or_else(|err| {
+        if std::env::var("TOML_TRACE_ERROR").is_ok_and(|v| v == "1") {
+            panic!("failed to parse TOML config at {path:?}: {err:#?}")
+        } else {
+            panic!(
+                "failed to parse TOML config at: {path:?}. Set TOML_TRACE_ERROR=1 to print \
+                 parsing error but this may leak secrets."
+            )
+        }
+    })
+}
+
+/// Returns minimum gas used for settling a single order.
+/// (not accounting for the cost of additional interactions)
+fn default_gas_offset() -> i64 {
+    SETTLEMENT_OVERHEAD.try_into().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::io::Write};
+
+    fn config_file(config: &str) -> tempfile::TempPath {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(config.as_bytes()).unwrap();
+        file.into_temp_path()
+    }
+
+    #[tokio::test]
+    async fn loads_engine_by_name() {
+        let naive = config_file(
+            r#"
+                engine = "naive"
+                chain-id = "1"
+            "#,
+        );
+        assert_eq!(
+            Engines::builtin().load(&naive).await.weth(),
+            contracts::Contracts::for_chain(Chain::Mainnet).weth,
+        );
+
+        let baseline = config_file(
+            r#"
+                engine = "baseline"
+                chain-id = "1"
+                base-tokens = []
+                max-hops = 1
+                max-partial-attempts = 1
+                native-token-price-estimation-amount = "1000000000000000000"
+            "#,
+        );
+        assert_eq!(
+            Engines::builtin().load(&baseline).await.weth(),
+            contracts::Contracts::for_chain(Chain::Mainnet).weth,
+        );
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "unknown solver engine")]
+    async fn rejects_unknown_engine() {
+        let config = config_file(
+            r#"
+                engine = "legacy"
+                chain-id = "1"
+            "#,
+        );
+        Engines::builtin().load(&config).await;
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn rejects_options_of_other_engines() {
+        let config = config_file(
+            r#"
+                engine = "naive"
+                chain-id = "1"
+                max-hops = 1
+            "#,
+        );
+        Engines::builtin().load(&config).await;
+    }
+
+    #[tokio::test]
+    async fn loads_registered_engine() {
+        fn engine(config: toml::Table, path: &Path) -> Box<dyn solver::Engine> {
+            naive::engine(config, path)
+        }
+
+        let config = config_file(
+            r#"
+                engine = "custom"
+                weth = "0x0101010101010101010101010101010101010101"
+            "#,
+        );
+        let engines = Engines::builtin().register("custom", engine);
+        assert_eq!(
+            engines.load(&config).await.weth(),
+            eth::WethAddress(H160([1; 20])),
+        );
+    }
+}
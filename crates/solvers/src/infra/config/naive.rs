@@ -0,0 +1,58 @@
+use {
+    crate::domain::solver::{self, naive},
+    chain::Chain,
+    ethereum_types::H160,
+    serde::Deserialize,
+    std::path::Path,
+};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct Config {
+    /// Optional chain ID. This is used to automatically determine the address
+    /// of the WETH contract.
+    chain_id: Option<Chain>,
+
+    /// Optional WETH contract address. This can be used to specify a manual
+    /// value **instead** of using the canonical WETH contract for the
+    /// configured chain.
+    weth: Option<H160>,
+
+    /// Units of gas that get added to the gas estimate for executing the
+    /// matched trades to arrive at a gas estimate for a whole settlement.
+    #[serde(default = "super::default_gas_offset")]
+    solution_gas_offset: i64,
+}
+
+/// Load the naive solver configuration from a TOML file.
+///
+/// # Panics
+///
+/// This method panics if the config is invalid or on I/O errors.
+pub async fn load(path: &Path) -> naive::Config {
+    super::load::<Config>(path).await.into()
+}
+
+/// The name selecting this engine in the `engine` field of a configuration
+/// file.
+pub const NAME: &str = "naive";
+
+/// Creates the naive solver engine from the engine specific options of the
+/// configuration file at `path`.
+///
+/// # Panics
+///
+/// This method panics if the config is invalid.
+pub fn engine(config: toml::Table, path: &Path) -> Box<dyn solver::Engine> {
+    let config = super::parse::<Config>(config, path).into();
+    Box::new(solver::Naive::new(config))
+}
+
+impl From<Config> for naive::Config {
+    fn from(config: Config) -> Self {
+        naive::Config {
+            weth: super::weth(config.chain_id, config.weth),
+            solution_gas_offset: config.solution_gas_offset.into(),
+        }
+    }
+}
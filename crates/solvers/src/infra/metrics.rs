@@ -1,4 +1,4 @@
-use crate::domain::{auction, solution};
+use crate::domain::{auction, order, solution};
 
 /// Metrics for the solver engine.
 #[derive(Debug, Clone, prometheus_metric_storage::MetricStorage)]
@@ -18,6 +18,11 @@ struct Metrics {
 
     /// The number of solutions that were found.
     solutions: prometheus::IntCounter,
+
+    /// The fraction of the amount of partially fillable orders that gets
+    /// executed in solutions.
+    #[metric(buckets(0, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1))]
+    partial_fill_fraction: prometheus::Histogram,
 }
 
 /// Setup the metrics registry.
@@ -40,6 +45,39 @@ pub fn solved(deadline: &auction::Deadline, solutions: &[solution::Solution]) {
         .remaining_time
         .observe(deadline.remaining().unwrap_or_default().as_secs_f64());
     get().solutions.inc_by(solutions.len() as u64);
+
+    let fulfillments = solutions
+        .iter()
+        .flat_map(|solution| &solution.trades)
+        .filter_map(|trade| match trade {
+            solution::Trade::Fulfillment(fulfillment) => Some(fulfillment),
+            solution::Trade::Jit(_) => None,
+        })
+        .filter(|fulfillment| fulfillment.order().partially_fillable);
+    for fulfillment in fulfillments {
+        get()
+            .partial_fill_fraction
+            .observe(fill_fraction(fulfillment));
+    }
+}
+
+/// Returns the fraction of the order's amount that gets executed by a
+/// fulfillment.
+fn fill_fraction(fulfillment: &solution::Fulfillment) -> f64 {
+    let order = fulfillment.order();
+    let (executed, full) = match order.side {
+        order::Side::Buy => (fulfillment.executed().amount, order.buy.amount),
+        order::Side::Sell => (
+            fulfillment.executed().amount.saturating_add(
+                fulfillment
+                    .surplus_fee()
+                    .map(|fee| fee.amount)
+                    .unwrap_or_default(),
+            ),
+            order.sell.amount,
+        ),
+    };
+    executed.to_f64_lossy() / full.to_f64_lossy()
 }
 
 /// Get the metrics instance.
@@ -11,4 +11,8 @@ mod run;
 mod tests;
 mod util;
 
-pub use self::run::{run, start};
+pub use self::{
+    domain::solver::Engine,
+    infra::config::{Constructor, Engines},
+    run::{run, start},
+};
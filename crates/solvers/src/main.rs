@@ -3,5 +3,5 @@ static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
 #[tokio::main]
 async fn main() {
-    solvers::start(std::env::args()).await;
+    solvers::start(std::env::args(), solvers::Engines::builtin()).await;
 }
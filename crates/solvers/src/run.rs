@@ -10,30 +10,49 @@ use {
     tokio::sync::oneshot,
 };
 
-pub async fn start(args: impl IntoIterator<Item = String>) {
+pub async fn start(args: impl IntoIterator<Item = String>, engines: config::Engines) {
     observe::panic_hook::install();
     let args = cli::Args::parse_from(args);
-    run_with(args, None).await;
+    run_with(args, engines, None).await;
 }
 
 pub async fn run(
     args: impl IntoIterator<Item = String>,
+    engines: config::Engines,
     bind: Option<oneshot::Sender<SocketAddr>>,
 ) {
     let args = cli::Args::parse_from(args);
-    run_with(args, bind).await;
+    run_with(args, engines, bind).await;
 }
 
-async fn run_with(args: cli::Args, bind: Option<oneshot::Sender<SocketAddr>>) {
+async fn run_with(
+    args: cli::Args,
+    engines: config::Engines,
+    bind: Option<oneshot::Sender<SocketAddr>>,
+) {
     observe::tracing::initialize_reentrant(&args.log);
     tracing::info!("running solver engine with {args:#?}");
 
-    let solver = match args.command {
+    let engine: Box<dyn solver::Engine> = match args.command {
+        cli::Command::Run { config } => engines.load(&config).await,
         cli::Command::Baseline { config } => {
-            let config = config::load(&config).await;
-            solver::Solver::new(config)
+            let config = config::baseline::load(&config).await;
+            Box::new(solver::Baseline::new(config))
+        }
+        cli::Command::Naive { config } => {
+            let config = config::naive::load(&config).await;
+            Box::new(solver::Naive::new(config))
         }
     };
+    let solver = solver::Solver::new(
+        engine,
+        args.backoff_threshold
+            .map(|threshold| solver::backoff::Config {
+                threshold,
+                period: args.backoff_period,
+                max_period: args.max_backoff_period,
+            }),
+    );
 
     crate::api::Api {
         addr: args.addr,
@@ -619,3 +619,290 @@ async fn composable_stable_v4() {
         }),
     );
 }
+
+#[tokio::test]
+async fn liquidity_bootstrapping() {
+    let engine = tests::SolverEngine::new(
+        "baseline",
+        tests::Config::String(
+            r#"
+                chain-id = "1"
+                base-tokens = []
+                max-hops = 0
+                max-partial-attempts = 1
+                native-token-price-estimation-amount = "100000000000000000"
+            "#
+            .to_owned(),
+        ),
+    )
+    .await;
+
+    let solution = engine
+        .solve(json!({
+            "id": "1",
+            "tokens": {
+                "0x6810e776880c02933d47db1b9fc05908e5386b96": {
+                    "decimals": 18,
+                    "symbol": "GNO",
+                    "referencePrice": "59970737022467696",
+                    "availableBalance": "0",
+                    "trusted": true
+                },
+                "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2": {
+                    "decimals": 18,
+                    "symbol": "WETH",
+                    "referencePrice": "1000000000000000000",
+                    "availableBalance": "0",
+                    "trusted": true
+                },
+                "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab": {
+                    "decimals": 18,
+                    "symbol": "COW",
+                    "referencePrice": "35756662383952",
+                    "availableBalance": "0",
+                    "trusted": true
+                },
+            },
+            "orders": [
+                {
+                    "uid": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a",
+                    "sellToken": "0x6810e776880c02933d47db1b9fc05908e5386b96",
+                    "buyToken": "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab",
+                    "sellAmount": "1000000000000000000",
+                    "fullSellAmount": "1000000000000000000",
+                    "buyAmount": "1",
+                    "fullBuyAmount": "1",
+                    "feePolicies": [],
+                    "validTo": 0,
+                    "kind": "sell",
+                    "owner": "0x5b1e2c2762667331bc91648052f646d1b0d35984",
+                    "partiallyFillable": false,
+                    "preInteractions": [],
+                    "postInteractions": [],
+                    "sellTokenSource": "erc20",
+                    "buyTokenDestination": "erc20",
+                    "class": "market",
+                    "appData": "0x6000000000000000000000000000000000000000000000000000000000000007",
+                    "signingScheme": "presign",
+                    "signature": "0x",
+                }
+            ],
+            "liquidity": [
+                {
+                    "kind": "liquidityBootstrapping",
+                    "tokens": {
+                        "0x6810e776880c02933d47db1b9fc05908e5386b96": {
+                            "balance": "11260752191375725565253",
+                            "scalingFactor": "1",
+                            "startWeight": "0.9",
+                            "endWeight": "0.5",
+                        },
+                        "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab": {
+                            "balance": "18764168403990393422000071",
+                            "scalingFactor": "1",
+                            "startWeight": "0.1",
+                            "endWeight": "0.5",
+                        }
+                    },
+                    "fee": "0.005",
+                    "id": "0",
+                    "address": "0x92762b42a06dcdddc5b7362cfb01e631c4d44b40",
+                    "balancerPoolId": "0x5c78d05b8ecf97507d1cf70646082c54faa4da950000000000000000000005ca",
+                    "gasEstimate": "88892",
+                    // The weight update has ended, so the pool trades like a
+                    // weighted product pool with its end weights.
+                    "startTime": 1600000000,
+                    "endTime": 1600086400,
+                    "swapEnabled": true,
+                },
+            ],
+            "effectiveGasPrice": "1000000000",
+            "deadline": "2106-01-01T00:00:00.000Z",
+            "surplusCapturingJitOrderOwners": []
+        }))
+        .await;
+
+    assert_eq!(
+        solution,
+        json!({
+            "solutions": [{
+                "id": 0,
+                "prices": {
+                    "0x6810e776880c02933d47db1b9fc05908e5386b96": "1657855325872947866705",
+                    "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab": "1000000000000000000"
+                },
+                "trades": [
+                    {
+                        "kind": "fulfillment",
+                        "order": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a",
+                        "executedAmount": "1000000000000000000"
+                    }
+                ],
+                "preInteractions": [],
+                "interactions": [
+                    {
+                        "kind": "liquidity",
+                        "internalize": false,
+                        "id": "0",
+                        "inputToken": "0x6810e776880c02933d47db1b9fc05908e5386b96",
+                        "outputToken": "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab",
+                        "inputAmount": "1000000000000000000",
+                        "outputAmount": "1657855325872947866705"
+                    },
+                ],
+                "postInteractions": [],
+                "gas": 206391,
+            }]
+        }),
+    );
+}
+
+#[tokio::test]
+async fn composable_stable() {
+    let engine = tests::SolverEngine::new(
+        "baseline",
+        tests::Config::String(
+            r#"
+                chain-id = "100"
+                base-tokens = []
+                max-hops = 0
+                max-partial-attempts = 1
+                native-token-price-estimation-amount = "1000000000000000000"
+            "#
+            .to_owned(),
+        ),
+    )
+    .await;
+
+    let solution = engine
+        .solve(json!({
+            "id": "1",
+            "tokens": {
+                "0x4b1e2c2762667331bc91648052f646d1b0d35984": {
+                    "decimals": 18,
+                    "symbol": "agEUR",
+                    "referencePrice": "1090118822951692177",
+                    "availableBalance": "0",
+                    "trusted": false
+                },
+                "0x5c78d05b8ecf97507d1cf70646082c54faa4da95": {
+                    "decimals": 18,
+                    "symbol": "bb-agEUR-EURe",
+                    "referencePrice": "10915976478387159906",
+                    "availableBalance": "0",
+                    "trusted": false
+                },
+                "0xcb444e90d8198415266c6a2724b7900fb12fc56e": {
+                    "decimals": 18,
+                    "symbol": "EURe",
+                    "referencePrice": "10917431192660550458",
+                    "availableBalance": "0",
+                    "trusted": true
+                },
+                "0xe91d153e0b41518a2ce8dd3d7944fa863463a97d": {
+                    "decimals": 18,
+                    "symbol": "wxDAI",
+                    "referencePrice": "1000000000000000000",
+                    "availableBalance": "0",
+                    "trusted": true
+                },
+            },
+            "orders": [
+                {
+                    "uid": "0x0101010101010101010101010101010101010101010101010101010101010101\
+                              0101010101010101010101010101010101010101\
+                              01010101",
+                    "sellToken": "0x4b1e2c2762667331bc91648052f646d1b0d35984",
+                    "buyToken": "0xcb444e90d8198415266c6a2724b7900fb12fc56e",
+                    "sellAmount": "10000000000000000000",
+                    "fullSellAmount": "10000000000000000000",
+                    "buyAmount": "9500000000000000000",
+                    "fullBuyAmount": "9500000000000000000",
+                    "feePolicies": [],
+                    "validTo": 0,
+                    "kind": "sell",
+                    "owner": "0x5b1e2c2762667331bc91648052f646d1b0d35984",
+                    "partiallyFillable": false,
+                    "preInteractions": [],
+                    "postInteractions": [],
+                    "sellTokenSource": "erc20",
+                    "buyTokenDestination": "erc20",
+                    "class": "market",
+                    "appData": "0x6000000000000000000000000000000000000000000000000000000000000007",
+                    "signingScheme": "presign",
+                    "signature": "0x",
+                },
+            ],
+            "liquidity": [
+                {
+                    "kind": "composableStable",
+                    "tokens": {
+                        "0x4b1e2c2762667331bc91648052f646d1b0d35984": {
+                            "balance": "126041615528606990697699",
+                            "scalingFactor": "1",
+                        },
+                        "0x5c78d05b8ecf97507d1cf70646082c54faa4da95": {
+                            "balance": "2596148429267369423681023550322451",
+                            "scalingFactor": "1",
+                        },
+                        "0xcb444e90d8198415266c6a2724b7900fb12fc56e": {
+                            "balance": "170162457652825667152980",
+                            "scalingFactor": "1",
+                        },
+                    },
+                    "fee": "0.0001",
+                    "amplificationParameter": "100.0",
+                    "id": "0",
+                    "address": "0x5c78d05b8ecf97507d1cf70646082c54faa4da95",
+                    "balancerPoolId": "0x5c78d05b8ecf97507d1cf70646082c54faa4da950000000000000000000005ca",
+                    "gasEstimate": "183520",
+                },
+            ],
+            "effectiveGasPrice": "1000000000",
+            "deadline": "2106-01-01T00:00:00.000Z",
+            "surplusCapturingJitOrderOwners": []
+        }))
+        .await;
+
+    assert_eq!(
+        solution,
+        json!({
+            "solutions": [
+                {
+                    "id": 0,
+                    "prices": {
+                        "0x4b1e2c2762667331bc91648052f646d1b0d35984": "10029862202766050434",
+                        "0xcb444e90d8198415266c6a2724b7900fb12fc56e": "10000000000000000000"
+                    },
+                    "trades": [
+                        {
+                            "kind": "fulfillment",
+                            "order": "0x0101010101010101010101010101010101010101010101010101010101010101\
+                                        0101010101010101010101010101010101010101\
+                                        01010101",
+                            "executedAmount": "10000000000000000000"
+                        }
+                    ],
+                    "preInteractions": [],
+                    "interactions": [
+                        {
+                            "kind": "liquidity",
+                            "internalize": false,
+                            "id": "0",
+                            "inputToken": "0x4b1e2c2762667331bc91648052f646d1b0d35984",
+                            "outputToken": "0xcb444e90d8198415266c6a2724b7900fb12fc56e",
+                            "inputAmount": "10000000000000000000",
+                            "outputAmount": "10029862202766050434"
+                        },
+                    ],
+                    "postInteractions": [],
+                    "gas": 289911,
+                },
+            ]
+        }),
+    );
+}
@@ -0,0 +1,246 @@
+//! Test cases to verify baseline computation of Uniswap V3 concentrated
+//! liquidity, including swaps that cross initialized ticks.
+
+use {crate::tests, serde_json::json};
+
+#[tokio::test]
+async fn sell() {
+    let engine = tests::SolverEngine::new(
+        "baseline",
+        tests::Config::File("config/example.baseline.toml".into()),
+    )
+    .await;
+
+    let solution = engine
+        .solve(json!({
+            "id": "1",
+            "tokens": {
+                "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2": {
+                    "decimals": 18,
+                    "symbol": "WETH",
+                    "referencePrice": "1000000000000000000",
+                    "availableBalance": "0",
+                    "trusted": true
+                },
+                "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB": {
+                    "decimals": 18,
+                    "symbol": "COW",
+                    "referencePrice": "1000000000000000000",
+                    "availableBalance": "0",
+                    "trusted": true
+                }
+            },
+            "orders": [
+                {
+                    "uid": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a",
+                    "sellToken": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                    "buyToken": "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB",
+                    "sellAmount": "1000000000000000000",
+                    "fullSellAmount": "1000000000000000000",
+                    "buyAmount": "900000000000000000",
+                    "fullBuyAmount": "900000000000000000",
+                    "feePolicies": [],
+                    "validTo": 0,
+                    "kind": "sell",
+                    "owner": "0x5b1e2c2762667331bc91648052f646d1b0d35984",
+                    "partiallyFillable": false,
+                    "preInteractions": [],
+                    "postInteractions": [],
+                    "sellTokenSource": "erc20",
+                    "buyTokenDestination": "erc20",
+                    "class": "market",
+                    "appData": "0x6000000000000000000000000000000000000000000000000000000000000007",
+                    "signingScheme": "presign",
+                    "signature": "0x",
+                }
+            ],
+            "liquidity": [
+                {
+                    "kind": "concentratedLiquidity",
+                    "tokens": [
+                        "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                        "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB"
+                    ],
+                    "sqrtPrice": "79228162514264337593543950336",
+                    "liquidity": "101000000000000000000",
+                    "tick": 0,
+                    "liquidityNet": {
+                        "-1200": "100000000000000000000",
+                        "-60": "1000000000000000000",
+                        "60": "-1000000000000000000",
+                        "1200": "-100000000000000000000"
+                    },
+                    "fee": "0.003",
+                    "id": "0",
+                    "address": "0x1111111111111111111111111111111111111111",
+                    "router": "0xe592427a0aece92de3edee1f18e0157c05861564",
+                    "gasEstimate": "108163"
+                }
+            ],
+            "effectiveGasPrice": "15000000000",
+            "deadline": "2106-01-01T00:00:00.000Z",
+            "surplusCapturingJitOrderOwners": []
+        }))
+        .await;
+
+    assert_eq!(
+        solution,
+        json!({
+            "solutions": [{
+                "id": 0,
+                "prices": {
+                    "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2": "987207970349392433",
+                    "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab": "1000000000000000000"
+                },
+                "trades": [
+                    {
+                        "kind": "fulfillment",
+                        "order": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a",
+                        "executedAmount": "1000000000000000000"
+                    }
+                ],
+                "preInteractions": [],
+                "interactions": [
+                    {
+                        "kind": "liquidity",
+                        "internalize": false,
+                        "id": "0",
+                        "inputToken": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                        "outputToken": "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab",
+                        "inputAmount": "1000000000000000000",
+                        "outputAmount": "987207970349392433"
+                    }
+                ],
+                "postInteractions": [],
+                // 108163 for the swap, 31000 for the crossed tick and 106391
+                // for the settlement overhead.
+                "gas": 245554,
+            }]
+        }),
+    );
+}
+
+#[tokio::test]
+async fn buy() {
+    let engine = tests::SolverEngine::new(
+        "baseline",
+        tests::Config::File("config/example.baseline.toml".into()),
+    )
+    .await;
+
+    let solution = engine
+        .solve(json!({
+            "id": "1",
+            "tokens": {
+                "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2": {
+                    "decimals": 18,
+                    "symbol": "WETH",
+                    "referencePrice": "1000000000000000000",
+                    "availableBalance": "0",
+                    "trusted": true
+                },
+                "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB": {
+                    "decimals": 18,
+                    "symbol": "COW",
+                    "referencePrice": "1000000000000000000",
+                    "availableBalance": "0",
+                    "trusted": true
+                }
+            },
+            "orders": [
+                {
+                    "uid": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a",
+                    "sellToken": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                    "buyToken": "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB",
+                    "sellAmount": "1100000000000000000",
+                    "fullSellAmount": "1100000000000000000",
+                    "buyAmount": "1000000000000000000",
+                    "fullBuyAmount": "1000000000000000000",
+                    "feePolicies": [],
+                    "validTo": 0,
+                    "kind": "buy",
+                    "owner": "0x5b1e2c2762667331bc91648052f646d1b0d35984",
+                    "partiallyFillable": false,
+                    "preInteractions": [],
+                    "postInteractions": [],
+                    "sellTokenSource": "erc20",
+                    "buyTokenDestination": "erc20",
+                    "class": "market",
+                    "appData": "0x6000000000000000000000000000000000000000000000000000000000000007",
+                    "signingScheme": "presign",
+                    "signature": "0x",
+                }
+            ],
+            "liquidity": [
+                {
+                    "kind": "concentratedLiquidity",
+                    "tokens": [
+                        "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                        "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB"
+                    ],
+                    "sqrtPrice": "79228162514264337593543950336",
+                    "liquidity": "101000000000000000000",
+                    "tick": 0,
+                    "liquidityNet": {
+                        "-1200": "100000000000000000000",
+                        "-60": "1000000000000000000",
+                        "60": "-1000000000000000000",
+                        "1200": "-100000000000000000000"
+                    },
+                    "fee": "0.003",
+                    "id": "0",
+                    "address": "0x1111111111111111111111111111111111111111",
+                    "router": "0xe592427a0aece92de3edee1f18e0157c05861564",
+                    "gasEstimate": "108163"
+                }
+            ],
+            "effectiveGasPrice": "15000000000",
+            "deadline": "2106-01-01T00:00:00.000Z",
+            "surplusCapturingJitOrderOwners": []
+        }))
+        .await;
+
+    assert_eq!(
+        solution,
+        json!({
+            "solutions": [{
+                "id": 0,
+                "prices": {
+                    "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2": "1000000000000000000",
+                    "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab": "1013088549482783266"
+                },
+                "trades": [
+                    {
+                        "kind": "fulfillment",
+                        "order": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a",
+                        "executedAmount": "1000000000000000000"
+                    }
+                ],
+                "preInteractions": [],
+                "interactions": [
+                    {
+                        "kind": "liquidity",
+                        "internalize": false,
+                        "id": "0",
+                        "inputToken": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                        "outputToken": "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab",
+                        "inputAmount": "1013088549482783266",
+                        "outputAmount": "1000000000000000000"
+                    }
+                ],
+                "postInteractions": [],
+                // 108163 for the swap, 31000 for the crossed tick and 106391
+                // for the settlement overhead.
+                "gas": 245554,
+            }]
+        }),
+    );
+}
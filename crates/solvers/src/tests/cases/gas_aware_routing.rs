@@ -0,0 +1,226 @@
+//! Test cases to verify that the baseline solver accounts for gas costs when
+//! selecting a route, preferring routes with the best output net of gas.
+
+use {crate::tests, serde_json::json};
+
+fn config() -> tests::Config {
+    tests::Config::String(
+        r#"
+            chain-id = "1"
+            base-tokens = ["0x6810e776880C02933D47DB1b9fc05908e5386b96"]
+            max-hops = 1
+            max-partial-attempts = 1
+            native-token-price-estimation-amount = "100000000000000000"
+        "#
+        .to_owned(),
+    )
+}
+
+/// An auction with a single WETH -> COW order, that can be routed either
+/// directly or over GNO. The route over GNO has a slightly better output, but
+/// uses twice as much gas.
+fn auction(gas_price: &str) -> serde_json::Value {
+    json!({
+        "id": "1",
+        "tokens": {
+            "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2": {
+                "decimals": 18,
+                "symbol": "WETH",
+                "referencePrice": "1000000000000000000",
+                "availableBalance": "0",
+                "trusted": true
+            },
+            "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB": {
+                "decimals": 18,
+                "symbol": "COW",
+                "referencePrice": "100000000000000",
+                "availableBalance": "0",
+                "trusted": true
+            },
+            "0x6810e776880C02933D47DB1b9fc05908e5386b96": {
+                "decimals": 18,
+                "symbol": "GNO",
+                "referencePrice": "100000000000000000",
+                "availableBalance": "0",
+                "trusted": true
+            }
+        },
+        "orders": [
+            {
+                "uid": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                          2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                          2a2a2a2a",
+                "sellToken": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                "buyToken": "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB",
+                "sellAmount": "1000000000000000000",
+                "fullSellAmount": "1000000000000000000",
+                "buyAmount": "9000000000000000000000",
+                "fullBuyAmount": "9000000000000000000000",
+                "feePolicies": [],
+                "validTo": 0,
+                "kind": "sell",
+                "owner": "0x5b1e2c2762667331bc91648052f646d1b0d35984",
+                "partiallyFillable": false,
+                "preInteractions": [],
+                "postInteractions": [],
+                "sellTokenSource": "erc20",
+                "buyTokenDestination": "erc20",
+                "class": "market",
+                "appData": "0x6000000000000000000000000000000000000000000000000000000000000007",
+                "signingScheme": "presign",
+                "signature": "0x",
+            }
+        ],
+        "liquidity": [
+            {
+                "kind": "constantProduct",
+                "tokens": {
+                    "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2": {
+                        "balance": "100000000000000000000"
+                    },
+                    "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB": {
+                        "balance": "1000000000000000000000000"
+                    }
+                },
+                "fee": "0.003",
+                "id": "0",
+                "address": "0x97b744df0b59d93A866304f97431D8EfAd29a08d",
+                "router": "0x7a250d5630b4cf539739df2c5dacb4c659f2488d",
+                "gasEstimate": "110000"
+            },
+            {
+                "kind": "constantProduct",
+                "tokens": {
+                    "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2": {
+                        "balance": "100000000000000000000"
+                    },
+                    "0x6810e776880C02933D47DB1b9fc05908e5386b96": {
+                        "balance": "1000000000000000000000"
+                    }
+                },
+                "fee": "0.003",
+                "id": "1",
+                "address": "0x3a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a",
+                "router": "0x7a250d5630b4cf539739df2c5dacb4c659f2488d",
+                "gasEstimate": "110000"
+            },
+            {
+                "kind": "constantProduct",
+                "tokens": {
+                    "0x6810e776880C02933D47DB1b9fc05908e5386b96": {
+                        "balance": "1000000000000000000000"
+                    },
+                    "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB": {
+                        "balance": "1016000000000000000000000"
+                    }
+                },
+                "fee": "0.003",
+                "id": "2",
+                "address": "0x3b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b",
+                "router": "0x7a250d5630b4cf539739df2c5dacb4c659f2488d",
+                "gasEstimate": "110000"
+            }
+        ],
+        "effectiveGasPrice": gas_price,
+        "deadline": "2106-01-01T00:00:00.000Z",
+        "surplusCapturingJitOrderOwners": []
+    })
+}
+
+#[tokio::test]
+async fn prefers_direct_route_when_gas_is_expensive() {
+    let engine = tests::SolverEngine::new("baseline", config()).await;
+
+    // At 100 Gwei, the additional hop costs 60 COW worth of gas, which is more
+    // than the ~30 COW of additional output.
+    let solution = engine.solve(auction("100000000000")).await;
+
+    assert_eq!(
+        solution,
+        json!({
+            "solutions": [{
+                "id": 0,
+                "prices": {
+                    "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2": "9871580343970612988504",
+                    "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab": "1000000000000000000"
+                },
+                "trades": [
+                    {
+                        "kind": "fulfillment",
+                        "order": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a",
+                        "executedAmount": "1000000000000000000"
+                    }
+                ],
+                "preInteractions": [],
+                "interactions": [
+                    {
+                        "kind": "liquidity",
+                        "internalize": false,
+                        "id": "0",
+                        "inputToken": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                        "outputToken": "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab",
+                        "inputAmount": "1000000000000000000",
+                        "outputAmount": "9871580343970612988504"
+                    }
+                ],
+                "postInteractions": [],
+                "gas": 166391,
+            }]
+        }),
+    );
+}
+
+#[tokio::test]
+async fn prefers_better_route_when_gas_is_cheap() {
+    let engine = tests::SolverEngine::new("baseline", config()).await;
+
+    // At 1 Gwei, the additional hop only costs 0.6 COW worth of gas.
+    let solution = engine.solve(auction("1000000000")).await;
+
+    assert_eq!(
+        solution,
+        json!({
+            "solutions": [{
+                "id": 0,
+                "prices": {
+                    "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2": "9901982085499320826886",
+                    "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab": "1000000000000000000"
+                },
+                "trades": [
+                    {
+                        "kind": "fulfillment",
+                        "order": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a",
+                        "executedAmount": "1000000000000000000"
+                    }
+                ],
+                "preInteractions": [],
+                "interactions": [
+                    {
+                        "kind": "liquidity",
+                        "internalize": false,
+                        "id": "1",
+                        "inputToken": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                        "outputToken": "0x6810e776880c02933d47db1b9fc05908e5386b96",
+                        "inputAmount": "1000000000000000000",
+                        "outputAmount": "9871580343970612988"
+                    },
+                    {
+                        "kind": "liquidity",
+                        "internalize": false,
+                        "id": "2",
+                        "inputToken": "0x6810e776880c02933d47db1b9fc05908e5386b96",
+                        "outputToken": "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab",
+                        "inputAmount": "9871580343970612988",
+                        "outputAmount": "9901982085499320826886"
+                    }
+                ],
+                "postInteractions": [],
+                "gas": 226391,
+            }]
+        }),
+    );
+}
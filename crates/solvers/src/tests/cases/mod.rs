@@ -1,8 +1,15 @@
-//! Baseline solver test cases.
+//! Solver engine test cases.
 
 mod bal_liquidity;
 mod buy_order_rounding;
+mod concentrated_liquidity;
+mod cow_matching;
 mod direct_swap;
+mod gas_aware_routing;
 mod internalization;
 mod limit_order_quoting;
+mod naive;
+mod order_splitting;
 mod partial_fill;
+mod quoting;
+mod solution_merging;
@@ -1,5 +1,6 @@
 //! Simple test case that verifies that the baseline solver can settle a
-//! partially fillable limit order with a Uniswap V2 pool.
+//! partially fillable limit order with a Uniswap V2 pool, filling it for the
+//! largest amount that respects the order's limit price.
 
 use {crate::tests, serde_json::json};
 
@@ -86,8 +87,8 @@ async fn test() {
             "solutions": [{
                 "id": 0,
                 "prices": {
-                    "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2": "20694705425542464884657",
-                    "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab": "500000000000000000"
+                    "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2": "25423961924060978273099",
+                    "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab": "633102416992187500"
                 },
                 "trades": [
                     {
@@ -95,7 +96,7 @@ async fn test() {
                         "order": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
                                     2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
                                     2a2a2a2a",
-                        "executedAmount": "500000000000000000",
+                        "executedAmount": "633102416992187500",
                         "fee": "2495865000000000"
                     }
                 ],
@@ -107,8 +108,8 @@ async fn test() {
                         "id": "0",
                         "inputToken": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
                         "outputToken": "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab",
-                        "inputAmount": "500000000000000000",
-                        "outputAmount": "20694705425542464884657"
+                        "inputAmount": "633102416992187500",
+                        "outputAmount": "25423961924060978273099"
                     }
                 ],
                 "postInteractions": [],
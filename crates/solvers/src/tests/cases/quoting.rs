@@ -0,0 +1,237 @@
+//! Test cases to verify that the baseline solver quotes orders over memoised
+//! liquidity paths.
+
+use {crate::tests, serde_json::json};
+
+const ONE_WETH: &str = "1000000000000000000";
+
+/// A quote auction for selling WETH for COW over the specified liquidity.
+fn auction(sell: &str, liquidity: serde_json::Value) -> serde_json::Value {
+    json!({
+        "id": null,
+        "tokens": {
+            "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2": {
+                "decimals": 18,
+                "symbol": "WETH",
+                "referencePrice": null,
+                "availableBalance": "0",
+                "trusted": false
+            },
+            "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB": {
+                "decimals": 18,
+                "symbol": "COW",
+                "referencePrice": null,
+                "availableBalance": "0",
+                "trusted": false
+            }
+        },
+        "orders": [
+            {
+                "uid": "0x0000000000000000000000000000000000000000000000000000000000000000\
+                          0000000000000000000000000000000000000000\
+                          00000000",
+                "sellToken": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                "buyToken": "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB",
+                "sellAmount": sell,
+                "fullSellAmount": sell,
+                "buyAmount": "1",
+                "fullBuyAmount": "1",
+                "feePolicies": [],
+                "validTo": 0,
+                "kind": "sell",
+                "owner": "0x0000000000000000000000000000000000000000",
+                "partiallyFillable": false,
+                "preInteractions": [],
+                "postInteractions": [],
+                "sellTokenSource": "erc20",
+                "buyTokenDestination": "erc20",
+                "class": "market",
+                "appData": "0x0000000000000000000000000000000000000000000000000000000000000000",
+                "signingScheme": "eip1271",
+                "signature": "0x",
+            }
+        ],
+        "liquidity": liquidity,
+        "effectiveGasPrice": "15000000000",
+        "deadline": "2106-01-01T00:00:00.000Z",
+        "surplusCapturingJitOrderOwners": []
+    })
+}
+
+/// A WETH/COW Uniswap V2 pool with the specified COW reserves.
+fn pool(id: &str, cow: &str) -> serde_json::Value {
+    json!({
+        "kind": "constantProduct",
+        "tokens": {
+            "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2": {
+                "balance": "100000000000000000000"
+            },
+            "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB": {
+                "balance": cow
+            }
+        },
+        "fee": "0.003",
+        "id": id,
+        "address": format!("0x{id:0>40}"),
+        "router": "0x7a250d5630b4cf539739df2c5dacb4c659f2488d",
+        "gasEstimate": "110000"
+    })
+}
+
+/// The expected quote selling the specified amount over the pool with the
+/// specified ID and output.
+fn quote(id: &str, sell: &str, output: &str) -> serde_json::Value {
+    json!({
+        "solutions": [{
+            "id": 0,
+            "prices": {
+                "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2": output,
+                "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab": sell
+            },
+            "trades": [
+                {
+                    "kind": "fulfillment",
+                    "order": "0x0000000000000000000000000000000000000000000000000000000000000000\
+                                0000000000000000000000000000000000000000\
+                                00000000",
+                    "executedAmount": sell
+                }
+            ],
+            "preInteractions": [],
+            "interactions": [
+                {
+                    "kind": "liquidity",
+                    "internalize": false,
+                    "id": id,
+                    "inputToken": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                    "outputToken": "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab",
+                    "inputAmount": sell,
+                    "outputAmount": output
+                }
+            ],
+            "postInteractions": [],
+            "gas": 166391,
+        }]
+    })
+}
+
+#[tokio::test]
+async fn reuses_memoised_path() {
+    let engine = tests::SolverEngine::new(
+        "baseline",
+        tests::Config::String(
+            r#"
+                chain-id = "1"
+                base-tokens = []
+                max-hops = 0
+                max-partial-attempts = 5
+                native-token-price-estimation-amount = "100000000000000000"
+                quote-path-ttl = "1h"
+            "#
+            .to_owned(),
+        ),
+    )
+    .await;
+
+    let solution = engine
+        .solve(auction(
+            ONE_WETH,
+            json!([pool("1", "1000000000000000000000000")]),
+        ))
+        .await;
+    assert_eq!(solution, quote("1", ONE_WETH, "9871580343970612988504"));
+
+    // The memoised path is re-evaluated against the pool's new reserves, even
+    // though a better pool became available in the meantime.
+    let solution = engine
+        .solve(auction(
+            ONE_WETH,
+            json!([
+                pool("1", "1010000000000000000000000"),
+                pool("2", "1100000000000000000000000"),
+            ]),
+        ))
+        .await;
+    assert_eq!(solution, quote("1", ONE_WETH, "9970296147410319118389"));
+}
+
+#[tokio::test]
+async fn searches_paths_without_memoisation() {
+    let engine = tests::SolverEngine::new(
+        "baseline",
+        tests::Config::String(
+            r#"
+                chain-id = "1"
+                base-tokens = []
+                max-hops = 0
+                max-partial-attempts = 5
+                native-token-price-estimation-amount = "100000000000000000"
+                quote-path-ttl = "0s"
+            "#
+            .to_owned(),
+        ),
+    )
+    .await;
+
+    let solution = engine
+        .solve(auction(
+            ONE_WETH,
+            json!([pool("1", "1000000000000000000000000")]),
+        ))
+        .await;
+    assert_eq!(solution, quote("1", ONE_WETH, "9871580343970612988504"));
+
+    let solution = engine
+        .solve(auction(
+            ONE_WETH,
+            json!([
+                pool("1", "1010000000000000000000000"),
+                pool("2", "1100000000000000000000000"),
+            ]),
+        ))
+        .await;
+    assert_eq!(solution, quote("2", ONE_WETH, "10858738378367674287355"));
+}
+
+#[tokio::test]
+async fn does_not_reuse_path_for_different_amount() {
+    let engine = tests::SolverEngine::new(
+        "baseline",
+        tests::Config::String(
+            r#"
+                chain-id = "1"
+                base-tokens = []
+                max-hops = 0
+                max-partial-attempts = 5
+                native-token-price-estimation-amount = "100000000000000000"
+                quote-path-ttl = "1h"
+            "#
+            .to_owned(),
+        ),
+    )
+    .await;
+
+    let solution = engine
+        .solve(auction(
+            ONE_WETH,
+            json!([pool("1", "1000000000000000000000000")]),
+        ))
+        .await;
+    assert_eq!(solution, quote("1", ONE_WETH, "9871580343970612988504"));
+
+    // Quoting an amount of a different order of magnitude searches all paths
+    // again instead of using the path memoised for the first quote.
+    let solution = engine
+        .solve(auction(
+            "10000000000000000000",
+            json!([
+                pool("1", "1010000000000000000000000"),
+                pool("2", "1100000000000000000000000"),
+            ]),
+        ))
+        .await;
+    assert_eq!(
+        solution,
+        quote("2", "10000000000000000000", "99727198326816404473947")
+    );
+}
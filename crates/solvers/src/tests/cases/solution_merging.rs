@@ -0,0 +1,194 @@
+//! Test case that verifies that the baseline solver merges the solutions for
+//! individual orders into a single solution when configured to do so. The
+//! second order is routed over the state of the shared pool after the first
+//! order's swap, and both swaps through it get combined into one interaction.
+
+use {crate::tests, serde_json::json};
+
+#[tokio::test]
+async fn test() {
+    let engine = tests::SolverEngine::new(
+        "baseline",
+        tests::Config::String(
+            r#"
+                chain-id = "1"
+                base-tokens = ["0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB"]
+                max-hops = 1
+                max-partial-attempts = 1
+                native-token-price-estimation-amount = "100000000000000000"
+                merge-solutions = true
+            "#
+            .to_owned(),
+        ),
+    )
+    .await;
+
+    let solution = engine
+        .solve(json!({
+            "id": "1",
+            "tokens": {
+                "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2": {
+                    "decimals": 18,
+                    "symbol": "WETH",
+                    "referencePrice": "1000000000000000000",
+                    "availableBalance": "0",
+                    "trusted": true
+                },
+                "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB": {
+                    "decimals": 18,
+                    "symbol": "COW",
+                    "referencePrice": "333333333333333",
+                    "availableBalance": "0",
+                    "trusted": true
+                },
+                "0x6810e776880C02933D47DB1b9fc05908e5386b96": {
+                    "decimals": 18,
+                    "symbol": "GNO",
+                    "referencePrice": "100000000000000000",
+                    "availableBalance": "0",
+                    "trusted": true
+                }
+            },
+            "orders": [
+                {
+                    "uid": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a",
+                    "sellToken": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                    "buyToken": "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB",
+                    "sellAmount": "1000000000000000000",
+                    "fullSellAmount": "1000000000000000000",
+                    "buyAmount": "2900000000000000000000",
+                    "fullBuyAmount": "2900000000000000000000",
+                    "feePolicies": [],
+                    "validTo": 0,
+                    "kind": "sell",
+                    "owner": "0x5b1e2c2762667331bc91648052f646d1b0d35984",
+                    "partiallyFillable": false,
+                    "preInteractions": [],
+                    "postInteractions": [],
+                    "sellTokenSource": "erc20",
+                    "buyTokenDestination": "erc20",
+                    "class": "market",
+                    "appData": "0x6000000000000000000000000000000000000000000000000000000000000007",
+                    "signingScheme": "presign",
+                    "signature": "0x",
+                },
+                {
+                    "uid": "0x2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b\
+                              2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b\
+                              2b2b2b2b",
+                    "sellToken": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                    "buyToken": "0x6810e776880C02933D47DB1b9fc05908e5386b96",
+                    "sellAmount": "1000000000000000000",
+                    "fullSellAmount": "1000000000000000000",
+                    "buyAmount": "9000000000000000000",
+                    "fullBuyAmount": "9000000000000000000",
+                    "feePolicies": [],
+                    "validTo": 0,
+                    "kind": "sell",
+                    "owner": "0x5b1e2c2762667331bc91648052f646d1b0d35984",
+                    "partiallyFillable": false,
+                    "preInteractions": [],
+                    "postInteractions": [],
+                    "sellTokenSource": "erc20",
+                    "buyTokenDestination": "erc20",
+                    "class": "market",
+                    "appData": "0x6000000000000000000000000000000000000000000000000000000000000007",
+                    "signingScheme": "presign",
+                    "signature": "0x",
+                }
+            ],
+            "liquidity": [
+                {
+                    "kind": "constantProduct",
+                    "tokens": {
+                        "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2": {
+                            "balance": "100000000000000000000"
+                        },
+                        "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB": {
+                            "balance": "300000000000000000000000"
+                        }
+                    },
+                    "fee": "0.003",
+                    "id": "0",
+                    "address": "0x97b744df0b59d93a866304f97431d8efad29a08d",
+                    "router": "0x7a250d5630b4cf539739df2c5dacb4c659f2488d",
+                    "gasEstimate": "110000"
+                },
+                {
+                    "kind": "constantProduct",
+                    "tokens": {
+                        "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB": {
+                            "balance": "300000000000000000000000"
+                        },
+                        "0x6810e776880C02933D47DB1b9fc05908e5386b96": {
+                            "balance": "1000000000000000000000"
+                        }
+                    },
+                    "fee": "0.003",
+                    "id": "1",
+                    "address": "0x6a9d4a0e4b1a4cc1b6fd6a2be5d7e6d2f0c2c9e1",
+                    "router": "0x7a250d5630b4cf539739df2c5dacb4c659f2488d",
+                    "gasEstimate": "110000"
+                }
+            ],
+            "effectiveGasPrice": "15000000000",
+            "deadline": "2106-01-01T00:00:00.000Z",
+            "surplusCapturingJitOrderOwners": []
+        }))
+        .await;
+
+    assert_eq!(
+        solution,
+        json!({
+            "solutions": [{
+                "id": 0,
+                "prices": {
+                    "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2": "3537869925668471416060415892678644383719",
+                    "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab": "1194631390447136769000000000000000000",
+                    "0x6810e776880c02933d47db1b9fc05908e5386b96": "370184262898897987068875000000000000000"
+                },
+                "trades": [
+                    {
+                        "kind": "fulfillment",
+                        "order": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                    2a2a2a2a",
+                        "executedAmount": "1000000000000000000"
+                    },
+                    {
+                        "kind": "fulfillment",
+                        "order": "0x2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b\
+                                    2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b\
+                                    2b2b2b2b",
+                        "executedAmount": "1000000000000000000"
+                    }
+                ],
+                "preInteractions": [],
+                "interactions": [
+                    {
+                        "kind": "liquidity",
+                        "internalize": false,
+                        "id": "0",
+                        "inputToken": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                        "outputToken": "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab",
+                        "inputAmount": "2000000000000000000",
+                        "outputAmount": "5864965483517256130588"
+                    },
+                    {
+                        "kind": "liquidity",
+                        "internalize": false,
+                        "id": "1",
+                        "inputToken": "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab",
+                        "outputToken": "0x6810e776880c02933d47db1b9fc05908e5386b96",
+                        "inputAmount": "2903491380326072234037",
+                        "outputAmount": "9557051123577094152"
+                    }
+                ],
+                "postInteractions": [],
+                "gas": 385417,
+            }]
+        }),
+    );
+}
@@ -53,7 +53,7 @@ impl SolverEngine {
             }
         };
 
-        let handle = tokio::spawn(crate::run(args, Some(bind)));
+        let handle = tokio::spawn(crate::run(args, crate::Engines::builtin(), Some(bind)));
 
         let addr = bind_receiver.await.unwrap();
         let url = format!("http://{addr}/").parse().unwrap();
@@ -0,0 +1,17 @@
+-- Stores the CIP-20 payout of the winner of every auction once the deadline
+-- for settling the auction has passed and all settlements up to the deadline
+-- are indexed.
+CREATE TABLE solver_rewards (
+  auction_id bigint PRIMARY KEY,
+  solver bytea NOT NULL,
+  block_deadline bigint NOT NULL,
+  -- whether the solver settled the auction before the deadline
+  settled boolean NOT NULL,
+  -- surplus and fee observed onchain, zero if the auction was not settled in time
+  observed_quality numeric(78,0) NOT NULL,
+  -- capped reward in wei, negative values are penalties
+  reward numeric(78,0) NOT NULL
+);
+
+-- Rewards are queried per accounting period, which is a range of blocks.
+CREATE INDEX solver_rewards_block_deadline ON solver_rewards (block_deadline);
@@ -0,0 +1,8 @@
+-- Add `tiered_volume` policy fee kind
+ALTER TYPE PolicyKind ADD VALUE 'tieredvolume';
+
+-- Add tiered volume fee columns. Both arrays have the same length and are
+-- sorted by the minimal notional of the tier.
+ALTER TABLE fee_policies
+    ADD COLUMN volume_tier_min_notionals numeric(78,0)[],
+    ADD COLUMN volume_tier_factors double precision[];
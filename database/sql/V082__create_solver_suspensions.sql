@@ -0,0 +1,18 @@
+-- Stores the drivers that got automatically excluded from auctions because too
+-- many of their recent winning solutions failed to settle.
+CREATE TABLE solver_suspensions (
+  driver text NOT NULL,
+  solver bytea NOT NULL,
+  suspended_at timestamptz NOT NULL,
+  suspended_until timestamptz NOT NULL,
+  -- number of settlement attempts the failure rate was computed from
+  attempts bigint NOT NULL,
+  -- failure counts by kind within those attempts
+  failed bigint NOT NULL,
+  reverted bigint NOT NULL,
+  unsettled bigint NOT NULL,
+  PRIMARY KEY (driver, suspended_at)
+);
+
+-- Active suspensions are looked up by their end.
+CREATE INDEX solver_suspensions_suspended_until ON solver_suspensions (suspended_until);
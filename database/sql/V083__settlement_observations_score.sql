@@ -0,0 +1,3 @@
+-- The score of a settlement only includes protocol fees, while the `fee` column
+-- also includes network fees. Not known for settlements observed before.
+ALTER TABLE settlement_observations ADD COLUMN score numeric(78,0);